@@ -0,0 +1,155 @@
+//! Verifying the SHA2-256 digests of the key files and the module files against pinned digests (the
+//! `--account-sha256` and `--*-module-sha256` flags), e.g. to detect a corrupted download of a release artifact.
+
+use anyhow::{bail, Context, Error};
+use sha2::{Digest, Sha256};
+use std::{fmt, path::Path, str::FromStr};
+
+/// A SHA2-256 digest of a file, given and printed as 64 hex characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Sha256Digest([u8; 32]);
+
+impl FromStr for Sha256Digest {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(s.trim())
+            .with_context(|| format!("The SHA2-256 digest `{s}` is not valid hex."))?;
+
+        let digest = bytes.try_into().map_err(|bytes: Vec<u8>| {
+            anyhow::anyhow!(
+                "The SHA2-256 digest `{s}` has {} bytes instead of 32 bytes (64 hex characters).",
+                bytes.len()
+            )
+        })?;
+
+        Ok(Sha256Digest(digest))
+    }
+}
+
+impl fmt::Display for Sha256Digest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+/// A function that computes the SHA2-256 digest of the content of a file.
+pub fn file_sha256(file: &Path) -> Result<Sha256Digest, Error> {
+    let content =
+        std::fs::read(file).with_context(|| format!("Could not read the file {file:?}."))?;
+
+    Ok(Sha256Digest(Sha256::digest(content).into()))
+}
+
+/// A function that checks that the SHA2-256 digest of the file is one of the expected digests. The check is
+/// skipped if no digests are expected. The error states the computed and the expected digests.
+pub fn ensure_file_sha256(
+    file: &Path,
+    expected: &[Sha256Digest],
+    description: &str,
+) -> Result<(), Error> {
+    if expected.is_empty() {
+        return Ok(());
+    }
+
+    let computed = file_sha256(file)?;
+
+    if !expected.contains(&computed) {
+        let expected = expected
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>()
+            .join(" or ");
+
+        bail!(
+            "The SHA2-256 digest of the {description} {file:?} does not match: computed {computed}, expected {expected}."
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// The SHA2-256 digest of `abc`.
+    const ABC_SHA256: &str = "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad";
+
+    /// Writes the content to a file in the temporary directory that is unique to the test (and the process).
+    fn write_temp_file(name: &str, content: &str) -> PathBuf {
+        let file = std::env::temp_dir().join(format!("{}-{name}", std::process::id()));
+
+        std::fs::write(&file, content).expect("Should be able to write the temporary file");
+
+        file
+    }
+
+    #[test]
+    fn test_parse_digest() {
+        let digest: Sha256Digest = ABC_SHA256.parse().expect("Should be a valid digest");
+
+        assert_eq!(digest.to_string(), ABC_SHA256);
+
+        // Surrounding whitespace and uppercase hex are accepted
+        let uppercase: Sha256Digest = format!(" {} \n", ABC_SHA256.to_uppercase())
+            .parse()
+            .expect("Should be a valid digest");
+
+        assert_eq!(uppercase, digest);
+
+        for (digest, expected) in [
+            (ABC_SHA256.replace('b', "x"), "is not valid hex"),
+            (ABC_SHA256[1..].to_string(), "is not valid hex"),
+            (
+                ABC_SHA256[2..].to_string(),
+                "has 31 bytes instead of 32 bytes",
+            ),
+            (
+                format!("{ABC_SHA256}00"),
+                "has 33 bytes instead of 32 bytes",
+            ),
+        ] {
+            let error = digest
+                .parse::<Sha256Digest>()
+                .expect_err("The digest should be rejected");
+            assert!(
+                format!("{error:#}").contains(expected),
+                "Unexpected error for the digest `{digest}`: {error:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_ensure_file_sha256() {
+        let file = write_temp_file("checksum-abc", "abc");
+
+        let digest: Sha256Digest = ABC_SHA256.parse().expect("Should be a valid digest");
+        let other_digest = Sha256Digest([1u8; 32]);
+
+        assert_eq!(
+            file_sha256(&file).expect("Should be able to hash the file"),
+            digest
+        );
+
+        // Matching digest, also as one of several expected digests
+        ensure_file_sha256(&file, &[digest], "module file").expect("The digest should match");
+        ensure_file_sha256(&file, &[other_digest, digest], "module file")
+            .expect("One of the digests should match");
+
+        // Mismatched digest
+        let error = ensure_file_sha256(&file, &[other_digest], "module file")
+            .expect_err("The digest should not match");
+        let error = format!("{error:#}");
+        assert!(error.contains("The SHA2-256 digest of the module file"));
+        assert!(error.contains(&format!("computed {digest}, expected {other_digest}")));
+
+        // No expected digests skip the check, even for a file that does not exist
+        let missing_file =
+            std::env::temp_dir().join(format!("{}-checksum-missing", std::process::id()));
+        ensure_file_sha256(&missing_file, &[], "module file").expect("The check should be skipped");
+        ensure_file_sha256(&missing_file, &[digest], "module file")
+            .expect_err("A missing file should be rejected");
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{Context, Error};
+use concordium_rust_sdk::v2;
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+
+/// A struct containing the deployment parameters that can be provided via a TOML config file
+/// with the `--config` flag. All fields are optional. Values provided via the command line flags
+/// take precedence over the values from the config file.
+///
+/// Relative paths in the config file are resolved relative to the directory of the config file.
+///
+/// Example config file:
+/// ```toml
+/// node = "http://node.testnet.concordium.com:20000"
+/// account = "./3PXwJYYPf6fyVb4GJquxSZU8puxrHfzc4XogdMVot8MUQK53tW.export"
+/// registry_module = "../registry/registry.wasm.v1"
+/// staking_bank_module = "../staking-bank/staking_bank.wasm.v1"
+/// umbrella_feeds_module = "../umbrella-feeds/umbrella_feeds.wasm.v1"
+/// registry_module_sha256 = "<64 hex characters>"
+/// staking_bank_module_sha256 = "<64 hex characters>"
+/// umbrella_feeds_module_sha256 = "<64 hex characters>"
+/// required_signatures = 3
+/// decimals = 8
+/// signature_scheme = "ed25519"
+/// old_data_policy = "reject"
+/// name = "UmbrellaFeeds Testnet"
+/// chain_id = "concordium-testnet"
+/// code_version = "8f3c2a1"
+/// restrict_submitter = false
+/// max_future_skew = 300
+/// registry_init_amount = "0"
+/// staking_bank_init_amount = "0"
+/// umbrella_feeds_init_amount = "0"
+/// ```
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct DeployConfig {
+    /// V2 API of the Concordium node.
+    pub node: Option<String>,
+    /// Path to the file containing the Concordium account keys exported from the wallet.
+    pub account: Option<PathBuf>,
+    /// Path to the registry module.
+    pub registry_module: Option<PathBuf>,
+    /// Path to the staking_bank module.
+    pub staking_bank_module: Option<PathBuf>,
+    /// Path to the umbrella_feeds module.
+    pub umbrella_feeds_module: Option<PathBuf>,
+    /// The expected SHA2-256 digest (hex) of the registry module file.
+    pub registry_module_sha256: Option<String>,
+    /// The expected SHA2-256 digest (hex) of the staking_bank module file.
+    pub staking_bank_module_sha256: Option<String>,
+    /// The expected SHA2-256 digest (hex) of the umbrella_feeds module file.
+    pub umbrella_feeds_module_sha256: Option<String>,
+    /// Minimal number of signatures required for accepting price submission in the umbrella feeds contract.
+    pub required_signatures: Option<u16>,
+    /// Decimals for prices stored in the umbrella feeds contract.
+    pub decimals: Option<u8>,
+    /// Signature scheme used by the validators in the umbrella feeds contract (`ed25519` or `ecdsa-secp256k1`).
+    pub signature_scheme: Option<String>,
+    /// Behavior of the umbrella feeds contract for price data that is not newer than the stored price data (`reject` or `skip`).
+    pub old_data_policy: Option<String>,
+    /// Human-readable name of the umbrella feeds contract instance.
+    pub name: Option<String>,
+    /// Chain tag of the umbrella feeds contract instance.
+    pub chain_id: Option<String>,
+    /// Code version (e.g. the git commit) stored in the registry and umbrella feeds contract instances.
+    pub code_version: Option<String>,
+    /// If `true`, only validators can submit price feed updates to the umbrella feeds contract.
+    pub restrict_submitter: Option<bool>,
+    /// Maximum number of seconds that the timestamp of submitted price data can be ahead of the block time in the
+    /// umbrella feeds contract.
+    pub max_future_skew: Option<u64>,
+    /// Amount of CCD (e.g. `"1.5"`) transferred to the registry contract at its initialization.
+    pub registry_init_amount: Option<String>,
+    /// Amount of CCD transferred to the staking_bank contract at its initialization.
+    pub staking_bank_init_amount: Option<String>,
+    /// Amount of CCD transferred to the umbrella_feeds contract at its initialization.
+    pub umbrella_feeds_init_amount: Option<String>,
+}
+
+impl DeployConfig {
+    /// A function to load the deployment parameters from a TOML config file.
+    /// The returned error points at the offending field if the file cannot be parsed.
+    pub fn from_file(config_file: &Path) -> Result<DeployConfig, Error> {
+        let content = std::fs::read_to_string(config_file)
+            .with_context(|| format!("Could not read the config file {config_file:?}."))?;
+
+        let mut config: DeployConfig = toml::from_str(&content)
+            .with_context(|| format!("Could not parse the config file {config_file:?}."))?;
+
+        // Resolving relative paths relative to the directory of the config file.
+        let base_dir = config_file.parent().unwrap_or_else(|| Path::new(""));
+
+        for path in [
+            &mut config.account,
+            &mut config.registry_module,
+            &mut config.staking_bank_module,
+            &mut config.umbrella_feeds_module,
+        ]
+        .into_iter()
+        .flatten()
+        {
+            if path.is_relative() {
+                *path = base_dir.join(&path);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// A function that parses the `node` field of the config file into an endpoint.
+    pub fn node_endpoint(&self) -> Result<Option<v2::Endpoint>, Error> {
+        self.node
+            .as_deref()
+            .map(|node| {
+                node.parse()
+                    .with_context(|| format!("Invalid field `node` in the config file: {node:?}."))
+            })
+            .transpose()
+    }
+}
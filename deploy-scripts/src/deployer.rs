@@ -1,31 +1,350 @@
+use crate::{
+    errors::{
+        describe_contract_reject, describe_init_reject, describe_reject_code, DeployError,
+        ProtocolContract,
+    },
+    network::{connect, ConnectionOptions},
+    pending::PendingTransactions,
+    signer::{sign_transaction, TransactionSigner},
+    wallet::{read_wallet_accounts, KeySelection},
+};
 use anyhow::{bail, Context, Error};
 use concordium_rust_sdk::{
-    common::types::TransactionTime,
-    id::types::AccountAddress,
+    common::types::{Amount, TransactionTime},
+    id::types::{AccountAddress, AccountCredentialWithoutProofs},
     smart_contracts::{common::ModuleReference, types::DEFAULT_INVOKE_ENERGY},
     types::{
-        hashes::TransactionHash,
+        hashes::{BlockHash, TransactionHash},
         queries::AccountNonceResponse,
-        smart_contracts::{ContractContext, InvokeContractResult, WasmModule},
+        smart_contracts::{ContractContext, InvokeContractResult, WasmModule, WasmVersion},
         transactions::{
             self,
-            send::{deploy_module, init_contract, GivenEnergy},
-            InitContractPayload, UpdateContractPayload,
+            construct::{self, deploy_module, init_contract},
+            send::GivenEnergy,
+            InitContractPayload, PayloadLike, UpdateContractPayload,
         },
         AccountTransactionEffects, BlockItemSummary, BlockItemSummaryDetails, ContractAddress,
-        Energy, TransactionType, WalletAccount,
+        Energy, Nonce, RejectReason, TransactionType,
+    },
+    v2::{self, BlockIdentifier, Code},
+};
+use concordium_smart_contract_engine::v1::ConcordiumAllowedImports;
+use concordium_smart_contract_testing::{
+    Account, Address, Chain, ContractInitErrorKind, InitExecutionError, Signer,
+};
+use concordium_wasm::validate::ValidationConfig;
+use std::{
+    collections::BTreeMap,
+    fmt::Display,
+    fs::File,
+    future::Future,
+    io::BufReader,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
     },
-    v2::{self, BlockIdentifier},
+    time::{Duration, Instant},
 };
-use std::{path::Path, sync::Arc};
+use tracing::{info, warn};
+
+/// The balance of the deployer account on the local chain that is used to simulate transactions in dry-run mode.
+const DRY_RUN_ACCOUNT_BALANCE: Amount = Amount::from_ccd(1_000_000_000);
+
+/// The default time to wait for the finalization of a transaction.
+pub const DEFAULT_FINALIZATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// The default time to wait for a new contract instance to be visible to the node (see `wait_for_instance`).
+pub const DEFAULT_INSTANCE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// The delay before the second query of `wait_for_instance`. It is doubled after each query.
+const INSTANCE_POLL_BASE_DELAY: Duration = Duration::from_millis(250);
+
+/// The maximal delay between two queries of `wait_for_instance`.
+const INSTANCE_POLL_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// A struct containing the retry policy for submitting transactions and waiting for their finalization.
+///
+/// Only transient errors (e.g. the connection to the node was reset or the node is unavailable) are retried.
+/// Deterministic errors (e.g. the transaction was rejected by the node) fail immediately.
+#[derive(Debug, Clone, Copy)]
+pub struct RetryConfig {
+    /// The maximal number of attempts (including the first attempt). A value of `1` disables retries.
+    pub max_attempts: u32,
+    /// The delay before the first retry.
+    pub base_delay: Duration,
+    /// The factor by which the delay is multiplied after each retry (exponential backoff).
+    pub backoff_factor: u32,
+}
+
+impl Default for RetryConfig {
+    fn default() -> Self {
+        RetryConfig {
+            max_attempts: 5,
+            base_delay: Duration::from_secs(1),
+            backoff_factor: 2,
+        }
+    }
+}
+
+impl RetryConfig {
+    /// A retry policy that does not retry at all.
+    pub fn no_retry() -> Self {
+        RetryConfig {
+            max_attempts: 1,
+            ..RetryConfig::default()
+        }
+    }
+
+    /// A function that returns the delay before the given retry (starting at `1`).
+    fn delay(&self, retry: u32) -> Duration {
+        self.base_delay
+            .saturating_mul(self.backoff_factor.saturating_pow(retry.saturating_sub(1)))
+    }
+}
+
+/// The maximal number of times a deployer re-establishes its connection to the node after the connection was lost.
+const MAX_RECONNECTS: u32 = 3;
+
+/// A trait for errors returned by the node that might be resolved by retrying the request.
+trait TransientError {
+    /// Whether the error is transient, i.e. the node is (temporarily) unavailable or overloaded, the request timed out,
+    /// or the connection to the node is broken. Other errors (e.g. an aborted or invalid request) fail immediately.
+    fn is_transient(&self) -> bool;
+
+    /// Whether the error indicates that the connection (channel) to the node is broken, so that the
+    /// following requests only succeed after reconnecting to the node.
+    fn is_connection_error(&self) -> bool;
+}
+
+impl TransientError for v2::RPCError {
+    fn is_transient(&self) -> bool {
+        match self {
+            v2::RPCError::CallError(status) => match status.code() {
+                Code::Unavailable | Code::DeadlineExceeded | Code::ResourceExhausted => true,
+                Code::Unknown | Code::Cancelled => self.is_connection_error(),
+                _ => false,
+            },
+            _ => false,
+        }
+    }
+
+    fn is_connection_error(&self) -> bool {
+        match self {
+            v2::RPCError::CallError(status) => {
+                let message = status.message().to_lowercase();
+
+                matches!(
+                    status.code(),
+                    Code::Unavailable | Code::Unknown | Code::Cancelled
+                ) && ["transport", "connection", "broken pipe", "h2 protocol"]
+                    .iter()
+                    .any(|pattern| message.contains(pattern))
+            }
+            _ => false,
+        }
+    }
+}
+
+impl TransientError for v2::QueryError {
+    fn is_transient(&self) -> bool {
+        match self {
+            v2::QueryError::RPCError(e) => e.is_transient(),
+            v2::QueryError::NotFound => false,
+        }
+    }
+
+    fn is_connection_error(&self) -> bool {
+        match self {
+            v2::QueryError::RPCError(e) => e.is_connection_error(),
+            v2::QueryError::NotFound => false,
+        }
+    }
+}
+
+/// A function that executes the given request and retries it according to the retry policy
+/// if it fails with a transient error.
+///
+/// The request is given a clone of the client of the deployer. If the request fails because the
+/// connection to the node is broken, the deployer reconnects to the node before the next attempt
+/// (see `Deployer::ensure_connected`).
+async fn retry<T, E, Fut>(
+    deployer: &mut Deployer,
+    description: &str,
+    mut request: impl FnMut(v2::Client) -> Fut,
+) -> Result<T, DeployError>
+where
+    Fut: Future<Output = Result<T, E>>,
+    E: TransientError + Display + Into<DeployError>,
+{
+    let config = deployer.retry;
+
+    let mut attempt = 1;
+
+    loop {
+        deployer.ensure_connected().await?;
+
+        match request(deployer.client.clone()).await {
+            Ok(value) => return Ok(value),
+            Err(e) if e.is_transient() && attempt < config.max_attempts => {
+                if e.is_connection_error() {
+                    deployer.connection_lost = true;
+                }
+                let delay = config.delay(attempt);
+
+                warn!(
+                    "{description} failed with a transient error ({e}). Retrying in {delay:?} (attempt {}/{})....",
+                    attempt + 1,
+                    config.max_attempts
+                );
+
+                tokio::time::sleep(delay).await;
+
+                attempt += 1;
+            }
+            Err(e) => {
+                if e.is_connection_error() {
+                    deployer.connection_lost = true;
+                }
+
+                return Err(e.into());
+            }
+        }
+    }
+}
+
+/// Reads the wasm module from a given file path.
+/// The module is parsed directly from a buffered reader of the file (without reading the whole file into memory first).
+pub fn get_wasm_module(file: &Path) -> Result<WasmModule, Error> {
+    let wasm_file =
+        File::open(file).with_context(|| format!("Could not open the WASM file {file:?}."))?;
+
+    let size = wasm_file
+        .metadata()
+        .with_context(|| format!("Could not read the metadata of the WASM file {file:?}."))?
+        .len();
+
+    let mut reader = BufReader::new(wasm_file);
+
+    let wasm_module: WasmModule = concordium_rust_sdk::common::from_bytes(&mut reader)
+        .with_context(|| format!("Could not parse the WASM file {file:?} ({size} bytes)."))?;
+
+    Ok(wasm_module)
+}
+
+/// A function that checks that the wasm module at the given file path is a valid V1 module exporting the
+/// `init_<contract_name>` function, e.g. to catch swapped or mislabeled wasm files before sending any transaction.
+pub fn ensure_module_exports_init(file: &Path, contract_name: &str) -> Result<(), Error> {
+    let wasm_module = get_wasm_module(file)?;
+
+    if wasm_module.version != WasmVersion::V1 {
+        bail!(
+            "The module at {file:?} is a {} module, but a V1 module is expected.",
+            wasm_module.version
+        );
+    }
+
+    let skeleton = concordium_wasm::parse::parse_skeleton(wasm_module.source.as_ref())
+        .with_context(|| format!("Could not parse the module at {file:?}."))?;
+
+    let module = concordium_wasm::validate::validate_module(
+        ValidationConfig::V1,
+        &ConcordiumAllowedImports {
+            support_upgrade: true,
+        },
+        &skeleton,
+    )
+    .with_context(|| format!("The module at {file:?} is not a valid V1 module."))?;
+
+    let init_name = format!("init_{contract_name}");
+
+    if !concordium_smart_contract_engine::utils::get_inits(&module)
+        .iter()
+        .any(|name| name.as_ref() == init_name)
+    {
+        bail!("The module at {file:?} does not export {init_name}.");
+    }
+
+    Ok(())
+}
+
+/// A function that checks if the error was returned by the node because the nonce of the sent
+/// transaction conflicts with the nonce of the account (e.g. a duplicate or too large nonce).
+fn is_nonce_conflict(error: &DeployError) -> bool {
+    let DeployError::Connection(error) = error else {
+        return false;
+    };
+
+    match error.downcast_ref::<v2::RPCError>() {
+        Some(v2::RPCError::CallError(status)) => {
+            status.code() == Code::InvalidArgument
+                && status.message().to_lowercase().contains("nonce")
+        }
+        _ => false,
+    }
+}
+
+/// The error returned by the `check_outcome_of_*` functions if the block item is not an account transaction.
+fn not_an_account_transaction() -> DeployError {
+    DeployError::Other(anyhow::anyhow!(
+        "Can only parse an account transaction (no account creation transaction or chain \
+         update transaction)"
+    ))
+}
+
+/// A function that describes the reject reason of a transaction or contract invoke.
+/// Running out of energy is reported separately from rejects by the contract logic.
+/// Rejects by the contracts of the protocol are decoded into the names of their errors.
+pub fn describe_reject_reason(reject_reason: &RejectReason) -> String {
+    match reject_reason {
+        RejectReason::OutOfEnergy => {
+            "out of energy (increase the energy limit with the `--max-energy` flag)".to_string()
+        }
+        reject_reason => {
+            describe_contract_reject(reject_reason).unwrap_or_else(|| format!("{reject_reason:?}"))
+        }
+    }
+}
 
 /// A struct containing connection and wallet information.
 #[derive(Debug, Clone)]
 pub struct Deployer {
     /// The client to establish a connection to a Concordium node (V2 API).
     pub client: v2::Client,
-    /// The account keys to be used for sending transactions.
-    pub key: Arc<WalletAccount>,
+    /// The signer of the transactions (by default the account keys read from the wallet file).
+    pub signer: Arc<dyn TransactionSigner>,
+    /// If `true`, no transactions are sent to the chain. Module deployments and contract initializations
+    /// are simulated on a local chain instead and their estimated energy costs are printed.
+    pub dry_run: bool,
+    /// The local chain on which the transactions are simulated in dry-run mode.
+    simulation: Arc<Mutex<Chain>>,
+    /// The retry policy for submitting transactions and waiting for their finalization.
+    pub retry: RetryConfig,
+    /// The energy limit used for transactions and contract invokes if no energy is given explicitly.
+    /// If `None`, the default energy values of the individual functions are used.
+    pub max_energy: Option<Energy>,
+    /// The safety margin (in percent) added to the energy of contract initializations and updates sent to the chain,
+    /// e.g. `10` sends 110% of the energy. `0` sends the energy unchanged.
+    pub energy_buffer: u64,
+    /// The time to wait for the finalization of a sent transaction before aborting.
+    pub finalization_timeout: Duration,
+    /// The file in which sent transactions are recorded if their finalization cannot be awaited (see `PendingTransactions`).
+    /// If `None`, such transactions are not recorded.
+    pub pending_file: Option<PathBuf>,
+    /// The wasm modules (and their module references) read by the `*_cached` functions, keyed by their file path.
+    /// The cache is shared between clones of the deployer.
+    module_cache: Arc<Mutex<BTreeMap<PathBuf, (WasmModule, ModuleReference)>>>,
+    /// If `true`, the keys of the signer were checked against the signature thresholds of the account on chain
+    /// (see `ensure_signature_threshold`). Shared between clones of the deployer.
+    signature_threshold_checked: Arc<AtomicBool>,
+    /// The endpoint and the options used to re-establish the connection to the node if it is lost
+    /// (see `ensure_connected`). If `None`, the deployer does not reconnect.
+    reconnect: Option<(v2::Endpoint, ConnectionOptions)>,
+    /// If `true`, a request failed because the connection to the node is broken, and the deployer
+    /// reconnects before the next request.
+    connection_lost: bool,
+    /// The number of times the deployer re-established its connection to the node.
+    reconnects: u32,
 }
 
 /// A struct containing the return values of the `deploy_wasm_module` function.
@@ -39,6 +358,8 @@ pub enum DeployResult {
     ModuleDeployed(Box<ModuleDeployedResult>),
     /// Module already exists on the chain.
     ModuleExists(ModuleReference),
+    /// Module would be deployed with a deployment transaction (dry-run mode).
+    ModuleDeploySimulated(ModuleReference),
 }
 
 /// A struct containing part of the return values of the `deploy_wasm_module` function.
@@ -55,43 +376,577 @@ pub struct ModuleDeployedResult {
 /// A struct containing the return values of the `init_contract` function.
 #[derive(Debug)]
 pub struct InitResult {
-    /// The transaction hash of the initialization transaction.
-    pub tx_hash: TransactionHash,
-    /// The block_item of the initialization transaction.
-    pub block_item: BlockItemSummary,
+    /// The transaction hash of the initialization transaction (`None` in dry-run mode).
+    pub tx_hash: Option<TransactionHash>,
+    /// The hash of the block the initialization transaction is in (`None` in dry-run mode).
+    pub block_hash: Option<BlockHash>,
+    /// The energy used by the initialization transaction.
+    /// In dry-run mode, this is the estimated energy of the initialization on the local chain.
+    pub energy_used: Energy,
+    /// The block_item of the initialization transaction (`None` in dry-run mode).
+    pub block_item: Option<BlockItemSummary>,
     /// The contract address of the smart contract instance.
+    /// In dry-run mode, this is the address of the instance on the local chain.
     pub contract_address: ContractAddress,
 }
 
+/// A struct containing the return values of the `update_contract` function.
+#[derive(Debug)]
+pub struct UpdateResult {
+    /// The transaction hash of the update transaction.
+    pub tx_hash: TransactionHash,
+    /// The hash of the block the update transaction is in.
+    pub block_hash: BlockHash,
+    /// The energy used by the update transaction.
+    pub energy_used: Energy,
+    /// The block_item of the update transaction.
+    pub block_item: BlockItemSummary,
+}
+
 impl Deployer {
-    /// A function to create a new deployer instance from a network client and a path to the wallet.
-    pub fn new(client: v2::Client, wallet_account_file: &Path) -> Result<Deployer, Error> {
-        let key_data = WalletAccount::from_json_file(wallet_account_file)
-            .context("Unable to read wallet file.")?;
+    /// A function to create a new deployer instance from a network client and the paths to the wallet files.
+    ///
+    /// The wallet files can be plaintext or password-protected (encrypted) exports. The password of
+    /// an encrypted export is read from the `password_file` if provided, otherwise it is prompted for.
+    /// The keys of several files (or of all files in a directory) of a multi-signature account are combined,
+    /// so that the transactions are signed with all keys (see `read_wallet_accounts`), unless only some of the
+    /// keys are selected with the `key_selection`.
+    pub fn new(
+        client: v2::Client,
+        wallet_account_files: &[PathBuf],
+        password_file: Option<&Path>,
+        key_selection: KeySelection,
+    ) -> Result<Deployer, Error> {
+        let wallet_account = read_wallet_accounts(
+            wallet_account_files,
+            password_file,
+            &key_selection.account_sha256,
+        )?;
 
-        Ok(Deployer {
+        let key_data = key_selection.apply(wallet_account)?;
+
+        Ok(Deployer::new_with_signer(client, Arc::new(key_data)))
+    }
+
+    /// A function to create a new deployer instance from a network client and a signer, e.g. a signer
+    /// backed by a hardware security module (HSM) or a remote signing service.
+    pub fn new_with_signer(client: v2::Client, signer: Arc<dyn TransactionSigner>) -> Deployer {
+        let mut simulation = Chain::new();
+        simulation.create_account(Account::new(signer.address(), DRY_RUN_ACCOUNT_BALANCE));
+
+        Deployer {
             client,
-            key: key_data.into(),
+            signer,
+            dry_run: false,
+            simulation: Arc::new(Mutex::new(simulation)),
+            retry: RetryConfig::default(),
+            max_energy: None,
+            energy_buffer: 0,
+            finalization_timeout: DEFAULT_FINALIZATION_TIMEOUT,
+            pending_file: None,
+            module_cache: Arc::new(Mutex::new(BTreeMap::new())),
+            signature_threshold_checked: Arc::new(AtomicBool::new(false)),
+            reconnect: None,
+            connection_lost: false,
+            reconnects: 0,
+        }
+    }
+
+    /// A function to enable or disable the dry-run mode of the deployer.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// A function to set the retry policy of the deployer.
+    pub fn with_retry(mut self, retry: RetryConfig) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// A function to set the energy limit used if no energy is given explicitly.
+    pub fn with_max_energy(mut self, max_energy: Option<Energy>) -> Self {
+        self.max_energy = max_energy;
+        self
+    }
+
+    /// A function to set the safety margin (in percent) added to the energy of contract initializations and updates.
+    pub fn with_energy_buffer(mut self, energy_buffer: u64) -> Self {
+        self.energy_buffer = energy_buffer;
+        self
+    }
+
+    /// A function that adds the energy buffer of the deployer to the energy, i.e. returns
+    /// `energy * (1 + energy_buffer / 100)` (rounded up).
+    fn buffered_energy(&self, energy: Energy) -> Energy {
+        let buffered = u128::from(energy.energy) * (100 + u128::from(self.energy_buffer));
+
+        Energy {
+            energy: u64::try_from(buffered.div_ceil(100)).unwrap_or(u64::MAX),
+        }
+    }
+
+    /// A function to set the time to wait for the finalization of a sent transaction.
+    pub fn set_finalization_timeout(&mut self, finalization_timeout: Duration) {
+        self.finalization_timeout = finalization_timeout;
+    }
+
+    /// A function to set the time to wait for the finalization of a sent transaction.
+    pub fn with_finalization_timeout(mut self, finalization_timeout: Duration) -> Self {
+        self.set_finalization_timeout(finalization_timeout);
+        self
+    }
+
+    /// A function to set the file in which transactions whose finalization cannot be awaited are recorded.
+    pub fn with_pending_file(mut self, pending_file: Option<PathBuf>) -> Self {
+        self.pending_file = pending_file;
+        self
+    }
+
+    /// A function to set the endpoint (and the connection options) used to re-establish the connection
+    /// to the node if it is lost.
+    pub fn with_reconnect(mut self, endpoint: v2::Endpoint, options: ConnectionOptions) -> Self {
+        self.reconnect = Some((endpoint, options));
+        self
+    }
+
+    /// A function that re-establishes the client of the deployer from the stored endpoint if an earlier
+    /// request failed because the connection to the node is broken. A warning is logged for each reconnect.
+    ///
+    /// The connection is re-established at most `MAX_RECONNECTS` times during the lifetime of the deployer.
+    /// This is independent of the retry policy of the single requests.
+    pub async fn ensure_connected(&mut self) -> Result<(), DeployError> {
+        if !self.connection_lost {
+            return Ok(());
+        }
+
+        let Some((endpoint, options)) = self.reconnect.clone() else {
+            return Ok(());
+        };
+
+        loop {
+            if self.reconnects >= MAX_RECONNECTS {
+                return Err(DeployError::Connection(anyhow::anyhow!(
+                    "The connection to the node {} was lost and could not be re-established \
+                     ({MAX_RECONNECTS} reconnects).",
+                    endpoint.uri()
+                )));
+            }
+
+            self.reconnects += 1;
+
+            warn!(
+                "The connection to the node {} was lost. Reconnecting (reconnect {}/{MAX_RECONNECTS})....",
+                endpoint.uri(),
+                self.reconnects
+            );
+
+            match connect(endpoint.clone(), &options).await {
+                Ok(client) => {
+                    self.client = client;
+                    self.connection_lost = false;
+
+                    return Ok(());
+                }
+                Err(e) => {
+                    let delay = self.retry.delay(self.reconnects);
+
+                    warn!("Reconnecting failed ({e:#}). Retrying in {delay:?}....");
+
+                    tokio::time::sleep(delay).await;
+                }
+            }
+        }
+    }
+
+    /// A function that sends the block item to the chain and waits until it is finalized.
+    ///
+    /// Transient errors are retried according to the retry policy of the deployer. Since the transaction
+    /// hash is known before sending, waiting for the finalization is retried without resending the
+    /// transaction. If the node already knows the transaction (i.e. an earlier attempt reached the node),
+    /// it is not sent again.
+    ///
+    /// If the transaction is not finalized within the finalization timeout of the deployer, an error
+    /// containing the transaction hash is returned (the transaction might still be finalized later).
+    /// If the deployer has a pending transactions file, the transaction is recorded in it, so that the
+    /// next run re-queries its outcome (see `recover_pending_transaction`).
+    async fn send_and_wait_until_finalized(
+        &mut self,
+        bi: &transactions::BlockItem<transactions::EncodedPayload>,
+    ) -> Result<(TransactionHash, BlockHash, BlockItemSummary), DeployError> {
+        let tx_hash = self.send_block_item(bi).await?;
+
+        let (block_hash, block_item) = match self.wait_until_finalized(tx_hash).await {
+            Ok(finalized) => finalized,
+            Err(e) => return Err(self.record_pending_transaction(bi, tx_hash, e)),
+        };
+
+        Ok((tx_hash, block_hash, block_item))
+    }
+
+    /// A function that records the sent transaction in the pending transactions file of the deployer
+    /// (if any) after waiting for its finalization failed with the given error.
+    /// Returns the error, extended with a hint on how to proceed.
+    fn record_pending_transaction(
+        &self,
+        bi: &transactions::BlockItem<transactions::EncodedPayload>,
+        tx_hash: TransactionHash,
+        error: DeployError,
+    ) -> DeployError {
+        let (Some(pending_file), transactions::BlockItem::AccountTransaction(transaction)) =
+            (&self.pending_file, bi)
+        else {
+            return error;
+        };
+
+        let recorded = PendingTransactions::from_file(pending_file).and_then(|mut pending| {
+            pending
+                .transactions
+                .insert(PendingTransactions::key(&transaction.payload), tx_hash);
+            pending.write_to_file(pending_file)
+        });
+
+        match recorded {
+            Ok(()) => error.context(format!(
+                "The transaction {tx_hash} is recorded in {pending_file:?}. Re-run the command with \
+                 the same `--retry-finalization` flag to re-query its outcome instead of sending it again."
+            )),
+            Err(e) => error.context(format!(
+                "The transaction {tx_hash} could not be recorded as pending: {e:#}"
+            )),
+        }
+    }
+
+    /// A function that re-queries the outcome of a transaction with the given payload that was recorded in the
+    /// pending transactions file of the deployer by an earlier run, instead of sending the transaction again.
+    ///
+    /// Returns `None` if no such transaction is pending, or if the node does not know the pending transaction
+    /// (e.g. it expired before it was included in a block). Then the transaction has to be sent.
+    /// Otherwise, its finalization is awaited and the transaction is removed from the pending transactions file.
+    async fn recover_pending_transaction(
+        &mut self,
+        payload: &transactions::Payload,
+    ) -> Result<Option<(TransactionHash, BlockHash, BlockItemSummary)>, DeployError> {
+        let Some(pending_file) = self.pending_file.clone() else {
+            return Ok(None);
+        };
+
+        let mut pending =
+            PendingTransactions::from_file(&pending_file).map_err(DeployError::Parse)?;
+
+        let key = PendingTransactions::key(&payload.encode());
+
+        let Some(tx_hash) = pending.transactions.remove(&key) else {
+            return Ok(None);
+        };
+
+        let known = retry(
+            self,
+            "Querying the pending transaction",
+            |mut client| async move {
+                match client.get_block_item_status(&tx_hash).await {
+                    Ok(_) => Ok(true),
+                    Err(e) if e.is_not_found() => Ok(false),
+                    Err(e) => Err(e),
+                }
+            },
+        )
+        .await?;
+
+        if known {
+            info!(
+                %tx_hash,
+                "Re-querying the outcome of the pending transaction instead of sending it again...."
+            );
+
+            let (block_hash, block_item) = self.wait_until_finalized(tx_hash).await?;
+
+            pending
+                .write_to_file(&pending_file)
+                .map_err(DeployError::Other)?;
+
+            Ok(Some((tx_hash, block_hash, block_item)))
+        } else {
+            warn!(
+                %tx_hash,
+                "The pending transaction is unknown to the node (e.g. it expired). Sending the transaction again...."
+            );
+
+            pending
+                .write_to_file(&pending_file)
+                .map_err(DeployError::Other)?;
+
+            Ok(None)
+        }
+    }
+
+    /// A function that sends the block item to the chain without waiting for its finalization.
+    /// Transient errors are retried according to the retry policy of the deployer.
+    async fn send_block_item(
+        &mut self,
+        bi: &transactions::BlockItem<transactions::EncodedPayload>,
+    ) -> Result<TransactionHash, DeployError> {
+        let tx_hash = bi.hash();
+
+        retry(self, "Sending the transaction", |mut client| async move {
+            match client.send_block_item(bi).await {
+                Err(v2::RPCError::CallError(status)) if status.code() == Code::AlreadyExists => {
+                    Ok(tx_hash)
+                }
+                result => result,
+            }
         })
+        .await?;
+
+        info!(%tx_hash, "Sent transaction.");
+
+        Ok(tx_hash)
+    }
+
+    /// A function that waits until the transaction with the given hash is finalized. The hash of the block
+    /// the transaction is in and the block item are returned.
+    /// Transient errors are retried according to the retry policy of the deployer.
+    async fn wait_until_finalized(
+        &mut self,
+        tx_hash: TransactionHash,
+    ) -> Result<(BlockHash, BlockItemSummary), DeployError> {
+        let finalization_timeout = self.finalization_timeout;
+
+        let wait = retry(
+            self,
+            "Waiting for the finalization",
+            |mut client| async move { client.wait_until_finalized(&tx_hash).await },
+        );
+
+        let (block_hash, block_item) = tokio::time::timeout(finalization_timeout, wait)
+            .await
+            .map_err(|_| {
+                DeployError::Connection(anyhow::anyhow!(
+                    "Transaction not finalized within {} seconds (hash: {tx_hash}). Check the \
+                     status of the transaction with the `check-tx` subcommand before resending it.",
+                    finalization_timeout.as_secs()
+                ))
+            })??;
+
+        Ok((block_hash, block_item))
+    }
+
+    /// A function that signs the prepared transaction with the signer of the deployer.
+    async fn sign(
+        &self,
+        transaction: construct::PreAccountTransaction,
+    ) -> Result<transactions::BlockItem<transactions::EncodedPayload>, DeployError> {
+        self.ensure_signature_threshold()
+            .await
+            .map_err(DeployError::Other)?;
+
+        sign_transaction(&*self.signer, transaction)
+            .await
+            .context("Signing the transaction failed.")
+            .map_err(DeployError::Other)
+    }
+
+    /// A function that checks (once per deployer) that the keys of the signer meet the signature thresholds
+    /// of the account as reported by the node: at least `account_threshold` credentials have to be signed with
+    /// at least the threshold of the credential, and all keys have to be keys of the account. Otherwise, the
+    /// transactions would be rejected by the node. The check is skipped if the signer does not know its keys.
+    async fn ensure_signature_threshold(&self) -> Result<(), Error> {
+        if self.signature_threshold_checked.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+
+        let Some(key_indices) = self.signer.key_indices() else {
+            return Ok(());
+        };
+
+        let address = self.signer.address();
+
+        let account_info = self
+            .client
+            .clone()
+            .get_account_info(&address.into(), &BlockIdentifier::LastFinal)
+            .await
+            .context("Could not query the account info.")?
+            .response;
+
+        let mut signed_credentials = 0u8;
+        let mut details = Vec::new();
+
+        for (credential_index, keys) in &key_indices {
+            let Some(credential) = account_info.account_credentials.get(credential_index) else {
+                bail!(
+                    "The account {address} has no credential with index {}.",
+                    credential_index.index
+                );
+            };
+
+            let credential_keys = match &credential.value {
+                AccountCredentialWithoutProofs::Initial { icdv } => &icdv.cred_account,
+                AccountCredentialWithoutProofs::Normal { cdv, .. } => &cdv.cred_key_info,
+            };
+
+            if let Some(key_index) = keys
+                .iter()
+                .find(|key_index| !credential_keys.keys.contains_key(key_index))
+            {
+                bail!(
+                    "The credential {} of the account {address} has no key with index {}.",
+                    credential_index.index,
+                    key_index.0
+                );
+            }
+
+            let threshold = u8::from(credential_keys.threshold);
+
+            if keys.len() >= usize::from(threshold) {
+                signed_credentials += 1;
+            }
+
+            details.push(format!(
+                "credential {}: {} of {threshold} required keys",
+                credential_index.index,
+                keys.len()
+            ));
+        }
+
+        let account_threshold = u8::from(account_info.account_threshold);
+
+        if signed_credentials < account_threshold {
+            bail!(
+                "The provided keys do not meet the signature threshold of the account {address}: \
+                 {signed_credentials} of {account_threshold} required credentials are signed ({}). \
+                 Pass the key files of the other key holders with additional `--account` flags.",
+                details.join(", ")
+            );
+        }
+
+        self.signature_threshold_checked
+            .store(true, Ordering::Relaxed);
+
+        Ok(())
+    }
+
+    /// A function that returns the signer used for simulating transactions on the local chain.
+    /// The number of keys affects the transaction costs.
+    fn simulation_signer(&self) -> Result<Signer, DeployError> {
+        Signer::with_keys(self.signer.num_keys())
+            .context("The signer has no keys.")
+            .map_err(DeployError::Other)
     }
 
     /// A function to check if a module exists on the chain.
+    /// Transient errors are retried according to the retry policy of the deployer.
     pub async fn module_exists(
         &mut self,
         module_reference: &ModuleReference,
-    ) -> Result<bool, Error> {
-        let module_src = self
-            .client
-            .get_module_source(module_reference, &BlockIdentifier::LastFinal)
-            .await;
+    ) -> Result<bool, DeployError> {
+        retry(self, "Querying the module", |mut client| async move {
+            match client
+                .get_module_source(module_reference, &BlockIdentifier::LastFinal)
+                .await
+            {
+                Ok(_) => Ok(true),
+                Err(e) if e.is_not_found() => Ok(false),
+                Err(e) => Err(e),
+            }
+        })
+        .await
+    }
 
-        match module_src {
-            Ok(_) => Ok(true),
-            Err(e) if e.is_not_found() => Ok(false),
-            Err(e) => Err(e.into()),
+    /// A function that polls the node with exponential backoff until the contract instance is visible in the last
+    /// finalized block or the timeout elapses. Right after an initialization is finalized, the node serving the
+    /// reads (e.g. behind a load balancer) might not know the new instance yet. In dry-run mode, it returns immediately.
+    /// Transient errors of the single queries are retried according to the retry policy of the deployer.
+    pub async fn wait_for_instance(
+        &mut self,
+        contract: ContractAddress,
+        timeout: Duration,
+    ) -> Result<(), Error> {
+        if self.dry_run {
+            return Ok(());
+        }
+
+        let start = Instant::now();
+        let mut delay = INSTANCE_POLL_BASE_DELAY;
+
+        loop {
+            let visible = retry(
+                self,
+                "Querying the contract instance",
+                |mut client| async move {
+                    match client
+                        .get_instance_info(contract, &BlockIdentifier::LastFinal)
+                        .await
+                    {
+                        Ok(_) => Ok(true),
+                        Err(e) if e.is_not_found() => Ok(false),
+                        Err(e) => Err(e),
+                    }
+                },
+            )
+            .await?;
+
+            if visible {
+                return Ok(());
+            }
+
+            if start.elapsed() + delay > timeout {
+                bail!(
+                    "The contract {contract} is not visible to the node within {}s after its initialization.",
+                    timeout.as_secs()
+                );
+            }
+
+            info!("The contract {contract} is not visible to the node yet. Querying again in {delay:?}....");
+
+            tokio::time::sleep(delay).await;
+
+            delay = delay.saturating_mul(2).min(INSTANCE_POLL_MAX_DELAY);
         }
     }
 
+    /// A function to check that the account of the signer can pay the estimated costs of the transactions.
+    /// Only the balance that is neither staked nor locked in a release schedule can be spent on transaction fees.
+    /// Transient errors are retried according to the retry policy of the deployer.
+    pub async fn ensure_sufficient_balance(
+        &mut self,
+        estimated_cost: Amount,
+    ) -> Result<(), DeployError> {
+        let address = self.signer.address();
+
+        let account_info = retry(self, "Querying the account info", |mut client| async move {
+            client
+                .get_account_info(&address.into(), &BlockIdentifier::LastFinal)
+                .await
+        })
+        .await
+        .map_err(|error| error.context("Could not query the account info."))?
+        .response;
+
+        let staked = account_info
+            .account_stake
+            .as_ref()
+            .map_or(Amount::zero(), |stake| stake.staked_amount());
+
+        let locked = account_info.account_release_schedule.total.max(staked);
+
+        let available = account_info
+            .account_amount
+            .checked_sub(locked)
+            .unwrap_or(Amount::zero());
+
+        if available < estimated_cost {
+            return Err(DeployError::InsufficientFunds {
+                available,
+                required: estimated_cost,
+            });
+        }
+
+        info!("The account balance ({available} CCD) covers the estimated costs (~{estimated_cost} CCD).");
+
+        Ok(())
+    }
+
     /// A function to deploy a wasm module on the chain.
     ///
     /// If successful, the transaction hash, the block item, and
@@ -102,56 +957,70 @@ impl Deployer {
     /// An optional expiry time for the transaction
     /// can be given. If `None` is provided, the local time + 300 seconds is
     /// used as a default expiry time.
+    ///
+    /// In dry-run mode, no transaction is sent. The module is deployed on the local chain instead
+    /// and the module reference together with the estimated energy of the deployment is printed.
+    #[tracing::instrument(name = "deploy", skip_all, fields(module_ref = %wasm_module.get_module_ref()))]
     pub async fn deploy_wasm_module(
         &mut self,
         wasm_module: WasmModule,
         expiry: Option<TransactionTime>,
-    ) -> Result<DeployResult, Error> {
-        println!("\nDeploying module....");
+    ) -> Result<DeployResult, DeployError> {
+        info!("Deploying module....");
+
+        let start = Instant::now();
 
         let module_reference = wasm_module.get_module_ref();
 
         let exists = self.module_exists(&module_reference).await?;
 
+        if self.dry_run {
+            return self.simulate_deploy_wasm_module(wasm_module, exists);
+        }
+
         if exists {
-            println!(
-                "Module with reference {} already exists on the chain.",
-                module_reference
-            );
+            info!("Module already exists on the chain.");
 
             return Ok(DeployResult::ModuleExists(module_reference));
         }
 
-        let nonce = self.get_nonce(self.key.address).await?;
-
-        if !nonce.all_final {
-            anyhow::bail!("Nonce not final")
-        }
+        let payload = transactions::Payload::DeployModule {
+            module: wasm_module.clone(),
+        };
 
-        let expiry = expiry.unwrap_or_else(|| {
-            TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
-        });
+        let (tx_hash, _, block_item) = match self.recover_pending_transaction(&payload).await? {
+            Some(recovered) => recovered,
+            None => {
+                let nonce = self.get_nonce(self.signer.address()).await?;
 
-        let tx = deploy_module(
-            &*self.key,
-            self.key.address,
-            nonce.nonce,
-            expiry,
-            wasm_module,
-        );
-        let bi = transactions::BlockItem::AccountTransaction(tx);
+                if !nonce.all_final {
+                    return Err(DeployError::Other(anyhow::anyhow!("Nonce not final")));
+                }
 
-        let tx_hash = self.client.send_block_item(&bi).await?;
+                let expiry = expiry.unwrap_or_else(|| {
+                    TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
+                });
 
-        println!("Sent transaction with hash: {tx_hash}");
+                let tx = deploy_module(
+                    self.signer.num_keys(),
+                    self.signer.address(),
+                    nonce.nonce,
+                    expiry,
+                    wasm_module,
+                );
+                let bi = self.sign(tx).await?;
 
-        let (_, block_item) = self.client.wait_until_finalized(&tx_hash).await?;
+                self.send_and_wait_until_finalized(&bi).await?
+            }
+        };
 
         self.check_outcome_of_deploy_transaction(&block_item)?;
 
-        println!(
-            "Transaction finalized: tx_hash={} module_ref={}",
-            tx_hash, module_reference,
+        info!(
+            %tx_hash,
+            energy_used = block_item.energy_cost.energy,
+            duration_ms = start.elapsed().as_millis() as u64,
+            "Transaction finalized."
         );
 
         Ok(DeployResult::ModuleDeployed(Box::from(
@@ -163,62 +1032,448 @@ impl Deployer {
         )))
     }
 
+    /// A function to deploy several wasm modules on the chain without waiting for the finalization
+    /// of each deployment before sending the next one.
+    ///
+    /// The nonce of the account is queried once and incremented locally for each sent deployment.
+    /// Afterwards, the finalization of all sent deployments is awaited. If the node rejects a deployment
+    /// because of a nonce conflict (e.g. another transaction was sent from the same account at the same time),
+    /// the remaining modules are deployed one after another with `deploy_wasm_module` instead.
+    ///
+    /// The results are returned in the same order as the given modules. Modules that already exist on
+    /// the chain are not deployed again. In dry-run mode, the modules are deployed with `deploy_wasm_module`.
+    ///
+    /// As in `deploy_wasm_module`, the deployments whose finalization could not be awaited are recorded in the
+    /// pending transactions file of the deployer (if any), and the outcome of a recorded deployment is re-queried
+    /// by the next run instead of sending the deployment again.
+    #[tracing::instrument(name = "deploy_pipelined", skip_all, fields(modules = wasm_modules.len()))]
+    pub async fn deploy_wasm_modules(
+        &mut self,
+        wasm_modules: Vec<WasmModule>,
+    ) -> Result<Vec<DeployResult>, DeployError> {
+        if self.dry_run {
+            let mut results = Vec::with_capacity(wasm_modules.len());
+            for wasm_module in wasm_modules {
+                results.push(self.deploy_wasm_module(wasm_module, None).await?);
+            }
+            return Ok(results);
+        }
+
+        info!("Deploying modules (pipelined)....");
+
+        let start = Instant::now();
+
+        let mut results: Vec<Option<DeployResult>> = wasm_modules.iter().map(|_| None).collect();
+        // The sent deployments (index, transaction hash, module reference, block item).
+        let mut sent = Vec::new();
+        // The modules that are deployed one after another (index, module).
+        let mut serial = Vec::new();
+
+        let nonce = self.get_nonce(self.signer.address()).await?;
+
+        if !nonce.all_final {
+            return Err(DeployError::Other(anyhow::anyhow!("Nonce not final")));
+        }
+
+        let mut nonce = nonce.nonce;
+
+        let expiry = TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64);
+
+        for (index, wasm_module) in wasm_modules.into_iter().enumerate() {
+            let module_reference = wasm_module.get_module_ref();
+
+            if self.module_exists(&module_reference).await? {
+                info!(module_ref = %module_reference, "Module already exists on the chain.");
+
+                results[index] = Some(DeployResult::ModuleExists(module_reference));
+                continue;
+            }
+
+            if !serial.is_empty() {
+                serial.push((index, wasm_module));
+                continue;
+            }
+
+            let payload = transactions::Payload::DeployModule {
+                module: wasm_module.clone(),
+            };
+
+            if let Some((tx_hash, _, block_item)) =
+                self.recover_pending_transaction(&payload).await?
+            {
+                self.check_outcome_of_deploy_transaction(&block_item)?;
+
+                results[index] = Some(DeployResult::ModuleDeployed(Box::from(
+                    ModuleDeployedResult {
+                        tx_hash,
+                        block_item,
+                        module_reference,
+                    },
+                )));
+                continue;
+            }
+
+            let tx = deploy_module(
+                self.signer.num_keys(),
+                self.signer.address(),
+                nonce,
+                expiry,
+                wasm_module.clone(),
+            );
+            let bi = self.sign(tx).await?;
+
+            match self.send_block_item(&bi).await {
+                Ok(tx_hash) => {
+                    sent.push((index, tx_hash, module_reference, bi));
+                    nonce.next_mut();
+                }
+                Err(e) if is_nonce_conflict(&e) => {
+                    warn!(
+                        "Sending the deployment with nonce {nonce} failed because of a nonce conflict ({e}). \
+                         Deploying the remaining modules one after another...."
+                    );
+                    serial.push((index, wasm_module));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let mut sent = sent.into_iter();
+
+        while let Some((index, tx_hash, module_reference, bi)) = sent.next() {
+            let block_item = match self.wait_until_finalized(tx_hash).await {
+                Ok((_, block_item)) => block_item,
+                Err(e) => {
+                    // The finalization of the remaining deployments is not awaited either, so they are recorded
+                    // in the pending transactions file as well.
+                    let error = self.record_pending_transaction(&bi, tx_hash, e);
+
+                    return Err(sent.fold(error, |error, (_, tx_hash, _, bi)| {
+                        self.record_pending_transaction(&bi, tx_hash, error)
+                    }));
+                }
+            };
+
+            self.check_outcome_of_deploy_transaction(&block_item)?;
+
+            info!(
+                %tx_hash,
+                module_ref = %module_reference,
+                energy_used = block_item.energy_cost.energy,
+                duration_ms = start.elapsed().as_millis() as u64,
+                "Transaction finalized."
+            );
+
+            results[index] = Some(DeployResult::ModuleDeployed(Box::from(
+                ModuleDeployedResult {
+                    tx_hash,
+                    block_item,
+                    module_reference,
+                },
+            )));
+        }
+
+        for (index, wasm_module) in serial {
+            results[index] = Some(self.deploy_wasm_module(wasm_module, None).await?);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// A function that reads the wasm module from the given file path and computes its module reference.
+    /// The result is cached, so that repeated calls with the same path (also from clones of the deployer)
+    /// neither read nor hash the file again.
+    pub fn get_wasm_module_cached(
+        &self,
+        wasm_module_path: &Path,
+    ) -> Result<(WasmModule, ModuleReference), DeployError> {
+        let key = std::fs::canonicalize(wasm_module_path)
+            .unwrap_or_else(|_| wasm_module_path.to_path_buf());
+
+        let mut module_cache = self
+            .module_cache
+            .lock()
+            .map_err(|_| DeployError::Other(anyhow::anyhow!("The module cache is poisoned.")))?;
+
+        if let Some(cached) = module_cache.get(&key) {
+            return Ok(cached.clone());
+        }
+
+        let wasm_module = get_wasm_module(wasm_module_path)
+            .with_context(|| format!("Could not read the module {wasm_module_path:?}."))
+            .map_err(DeployError::Parse)?;
+        let module_reference = wasm_module.get_module_ref();
+
+        module_cache.insert(key, (wasm_module.clone(), module_reference));
+
+        Ok((wasm_module, module_reference))
+    }
+
+    /// A function to deploy the wasm module at the given file path on the chain (see `deploy_wasm_module`).
+    /// The module and its module reference are read with `get_wasm_module_cached`.
+    pub async fn deploy_wasm_module_cached(
+        &mut self,
+        wasm_module_path: &Path,
+        expiry: Option<TransactionTime>,
+    ) -> Result<DeployResult, DeployError> {
+        let (wasm_module, _) = self.get_wasm_module_cached(wasm_module_path)?;
+
+        self.deploy_wasm_module(wasm_module, expiry).await
+    }
+
+    /// A function that checks if the module is deployed on the local chain already.
+    fn is_deployed_locally(&self, module_reference: ModuleReference) -> Result<bool, DeployError> {
+        let simulation = self.lock_simulation()?;
+
+        Ok(simulation.get_module(module_reference).is_some())
+    }
+
+    /// A function that estimates the energy of deploying the wasm module (including the costs of the
+    /// transaction size and signatures) by deploying it on the local chain. No transaction is sent.
+    ///
+    /// The module stays deployed on the local chain, so that the initializations of its contracts can be
+    /// estimated afterwards. Fails if the module is deployed on the local chain already.
+    pub fn estimate_deploy_wasm_module(
+        &self,
+        wasm_module: WasmModule,
+    ) -> Result<Energy, DeployError> {
+        let signer = self.simulation_signer()?;
+
+        let mut simulation = self.lock_simulation()?;
+
+        let deployment = simulation
+            .module_deploy_v1(signer, self.signer.address(), wasm_module)
+            .context("Simulating the module deployment failed.")
+            .map_err(DeployError::Other)?;
+
+        Ok(deployment.energy_used)
+    }
+
+    /// A function that estimates the energy of initializing a smart contract instance (including the costs
+    /// of the transaction size and signatures) by initializing it on the local chain. No transaction is sent.
+    /// The module of the contract has to be deployed on the local chain before (see `estimate_deploy_wasm_module`).
+    ///
+    /// The energy limit is chosen as in `init_contract`. The address of the instance on the local chain and
+    /// the estimated energy are returned.
+    pub fn estimate_init_contract(
+        &self,
+        payload: InitContractPayload,
+        energy: Option<Energy>,
+    ) -> Result<(ContractAddress, Energy), DeployError> {
+        let energy = self.init_energy(energy);
+
+        let signer = self.simulation_signer()?;
+
+        let mut simulation = self.lock_simulation()?;
+
+        let init_name = payload.init_name.clone();
+
+        let initialization = simulation
+            .contract_init(signer, self.signer.address(), energy, payload)
+            .map_err(|error| match &error.kind {
+                ContractInitErrorKind::ExecutionError {
+                    error: InitExecutionError::Reject { reason, .. },
+                } => {
+                    let reject = ProtocolContract::from_contract_name(
+                        init_name.as_contract_name().contract_name(),
+                    )
+                    .and_then(|contract_kind| describe_reject_code(contract_kind, *reason))
+                    .unwrap_or_else(|| format!("rejected with code {reason}"));
+
+                    DeployError::Reject {
+                        reject_reason: RejectReason::RejectedInit {
+                            reject_reason: *reason,
+                        },
+                        message: format!("Simulating `{init_name}` failed: {reject}."),
+                    }
+                }
+                _ => DeployError::Other(
+                    Error::from(error).context(format!("Simulating `{init_name}` failed.")),
+                ),
+            })?;
+
+        Ok((initialization.contract_address, initialization.energy_used))
+    }
+
+    /// A function that invokes a (view) entry point of a smart contract instance on the local chain (dry-run mode)
+    /// and returns its return value. Nothing is sent to the chain.
+    pub fn simulate_invoke(&self, payload: UpdateContractPayload) -> Result<Vec<u8>, Error> {
+        let simulation = self.lock_simulation()?;
+
+        let receive_name = payload.receive_name.clone();
+
+        let invocation = simulation
+            .contract_invoke(
+                self.signer.address(),
+                Address::Account(self.signer.address()),
+                self.max_energy.unwrap_or(DEFAULT_INVOKE_ENERGY),
+                payload,
+            )
+            .map_err(|error| {
+                Error::from(error).context(format!("Simulating `{receive_name}` failed."))
+            })?;
+
+        Ok(invocation.return_value)
+    }
+
+    /// A function that locks the local chain on which the transactions are simulated.
+    fn lock_simulation(&self) -> Result<std::sync::MutexGuard<'_, Chain>, DeployError> {
+        self.simulation.lock().map_err(|_| {
+            DeployError::Other(anyhow::anyhow!("The local simulation chain is poisoned."))
+        })
+    }
+
+    /// A function that returns the energy limit of a contract initialization: the given energy, the
+    /// `max_energy` of the deployer, or 5000 energy.
+    fn init_energy(&self, energy: Option<Energy>) -> Energy {
+        energy
+            .or(self.max_energy)
+            .unwrap_or(Energy { energy: 5000 })
+    }
+
+    /// A function that deploys a wasm module on the local chain (dry-run mode).
+    ///
+    /// The module has to be deployed on the local chain even if it already exists on the real chain,
+    /// so that contract initializations from this module can be simulated afterwards.
+    fn simulate_deploy_wasm_module(
+        &self,
+        wasm_module: WasmModule,
+        exists_on_chain: bool,
+    ) -> Result<DeployResult, DeployError> {
+        let module_reference = wasm_module.get_module_ref();
+
+        // The module might have been deployed to the local chain by an earlier call already.
+        if !self.is_deployed_locally(module_reference)? {
+            let energy_used = self.estimate_deploy_wasm_module(wasm_module)?;
+
+            if !exists_on_chain {
+                info!(
+                    energy_used = energy_used.energy,
+                    "Dry run: module would be deployed (estimated energy: {} NRG).",
+                    energy_used.energy
+                );
+
+                return Ok(DeployResult::ModuleDeploySimulated(module_reference));
+            }
+        }
+
+        info!("Dry run: module already exists on the chain.");
+
+        Ok(DeployResult::ModuleExists(module_reference))
+    }
+
     /// A function to initialize a smart contract instance on the chain.
     ///
     /// If successful, the transaction hash, the block item, and the contract address are
     /// returned.
     ///
     /// An optional energy for the transaction can be given. If `None` is
-    /// provided, 5000 energy is used as a default energy value. An optional
+    /// provided, the `max_energy` of the deployer or 5000 energy is used as a default energy value. The energy
+    /// buffer of the deployer is added to the energy. An optional
     /// expiry time for the transaction can be given. If `None` is provided,
     /// the local time + 300 seconds is used as a default expiry time.
+    ///
+    /// In dry-run mode, no transaction is sent. The initialization is simulated on the local chain instead
+    /// and its estimated energy is printed. The returned contract address is the address on the local chain.
+    #[tracing::instrument(name = "init", skip_all, fields(init_name = %payload.init_name, module_ref = %payload.mod_ref))]
     pub async fn init_contract(
         &mut self,
         payload: InitContractPayload,
         energy: Option<Energy>,
         expiry: Option<TransactionTime>,
-    ) -> Result<InitResult, Error> {
-        println!("\nInitializing contract....");
+    ) -> Result<InitResult, DeployError> {
+        info!("Initializing contract....");
 
-        let nonce = self.get_nonce(self.key.address).await?;
+        let start = Instant::now();
 
-        if !nonce.all_final {
-            bail!("Nonce not final")
+        let energy = self.init_energy(energy);
+
+        if self.dry_run {
+            return self.simulate_init_contract(payload, energy);
         }
 
-        let energy = energy.unwrap_or(Energy { energy: 5000 });
+        let contract_name = payload
+            .init_name
+            .as_contract_name()
+            .contract_name()
+            .to_string();
 
-        let expiry = expiry.unwrap_or_else(|| {
-            TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
-        });
+        let pending_payload = transactions::Payload::InitContract {
+            payload: payload.clone(),
+        };
 
-        let tx = init_contract(
-            &*self.key,
-            self.key.address,
-            nonce.nonce,
-            expiry,
-            payload,
-            energy,
-        );
+        let (tx_hash, block_hash, block_item) =
+            match self.recover_pending_transaction(&pending_payload).await? {
+                Some(recovered) => recovered,
+                None => {
+                    let nonce = self.get_nonce(self.signer.address()).await?;
 
-        let bi = transactions::BlockItem::AccountTransaction(tx);
+                    if !nonce.all_final {
+                        return Err(DeployError::Other(anyhow::anyhow!("Nonce not final")));
+                    }
 
-        let tx_hash = self.client.send_block_item(&bi).await?;
+                    let expiry = expiry.unwrap_or_else(|| {
+                        TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
+                    });
 
-        println!("Sent transaction with hash: {tx_hash}");
+                    let tx = init_contract(
+                        self.signer.num_keys(),
+                        self.signer.address(),
+                        nonce.nonce,
+                        expiry,
+                        payload,
+                        self.buffered_energy(energy),
+                    );
 
-        let (_, block_item) = self.client.wait_until_finalized(&tx_hash).await?;
+                    let bi = self.sign(tx).await?;
 
-        let contract_address = self.check_outcome_of_initialization_transaction(&block_item)?;
+                    self.send_and_wait_until_finalized(&bi).await?
+                }
+            };
+
+        let contract_address =
+            self.check_outcome_of_initialization_transaction(&block_item, &contract_name)?;
 
-        println!(
-            "Transaction finalized: tx_hash={} contract=({}, {})",
-            tx_hash, contract_address.index, contract_address.subindex,
+        info!(
+            %tx_hash,
+            contract = %contract_address,
+            energy_used = block_item.energy_cost.energy,
+            duration_ms = start.elapsed().as_millis() as u64,
+            "Transaction finalized."
         );
 
         Ok(InitResult {
-            tx_hash,
-            block_item,
+            tx_hash: Some(tx_hash),
+            block_hash: Some(block_hash),
+            energy_used: block_item.energy_cost,
+            block_item: Some(block_item),
+            contract_address,
+        })
+    }
+
+    /// A function that initializes a smart contract instance on the local chain (dry-run mode).
+    /// The module of the contract has to be deployed with `deploy_wasm_module` before.
+    fn simulate_init_contract(
+        &self,
+        payload: InitContractPayload,
+        energy: Energy,
+    ) -> Result<InitResult, DeployError> {
+        let init_name = payload.init_name.clone();
+
+        let (contract_address, energy_used) = self.estimate_init_contract(payload, Some(energy))?;
+
+        info!(
+            energy_used = energy_used.energy,
+            "Dry run: contract `{}` would be initialized (estimated energy: {} NRG).",
+            init_name,
+            energy_used.energy
+        );
+
+        Ok(InitResult {
+            tx_hash: None,
+            block_hash: None,
+            energy_used,
+            block_item: None,
             contract_address,
         })
     }
@@ -226,58 +1481,131 @@ impl Deployer {
     /// A function to update a smart contract instance on the chain.
     ///
     /// If successful, the transaction
-    /// hash, and the block item are returned.
+    /// hash, the block hash, the energy used, and the block item are returned.
     ///
     /// An optional energy for the transaction can be
-    /// given. If `None` is provided, 50000 energy is used as a default energy
-    /// value. An optional expiry time for the transaction can be given. If
+    /// given. If `None` is provided, the `max_energy` of the deployer or 50000 energy
+    /// is used as a default energy value. The energy buffer of the deployer is added to the energy. An optional expiry time for the transaction can be given. If
     /// `None` is provided, the local time + 300 seconds is used as a default
     /// expiry time.
+    #[tracing::instrument(name = "update", skip_all, fields(receive_name = %update_payload.receive_name, contract = %update_payload.address))]
     pub async fn update_contract(
         &mut self,
         update_payload: UpdateContractPayload,
         energy: Option<GivenEnergy>,
         expiry: Option<TransactionTime>,
-    ) -> Result<(TransactionHash, BlockItemSummary), Error> {
-        println!("\nUpdating contract....");
+    ) -> Result<UpdateResult, DeployError> {
+        info!("Updating contract....");
+
+        let start = Instant::now();
+
+        let payload = transactions::Payload::Update {
+            payload: update_payload,
+        };
+
+        if let Some((tx_hash, block_hash, block_item)) =
+            self.recover_pending_transaction(&payload).await?
+        {
+            self.check_outcome_of_update_transaction(&block_item)?;
+
+            info!(%tx_hash, energy_used = block_item.energy_cost.energy, "Transaction finalized.");
+
+            return Ok(UpdateResult {
+                tx_hash,
+                block_hash,
+                energy_used: block_item.energy_cost,
+                block_item,
+            });
+        }
 
-        let nonce = self.get_nonce(self.key.address).await?;
+        let nonce = self.get_nonce(self.signer.address()).await?;
 
         if !nonce.all_final {
-            bail!("Nonce not final")
+            return Err(DeployError::Other(anyhow::anyhow!("Nonce not final")));
         }
 
+        let bi = self
+            .build_update_transaction(payload, nonce.nonce, energy, expiry)
+            .await?;
+
+        let (tx_hash, block_hash, block_item) = self.send_and_wait_until_finalized(&bi).await?;
+
+        self.check_outcome_of_update_transaction(&block_item)?;
+
+        info!(
+            %tx_hash,
+            energy_used = block_item.energy_cost.energy,
+            duration_ms = start.elapsed().as_millis() as u64,
+            "Transaction finalized."
+        );
+
+        Ok(UpdateResult {
+            tx_hash,
+            block_hash,
+            energy_used: block_item.energy_cost,
+            block_item,
+        })
+    }
+
+    /// A function to send an update of a smart contract instance to the chain without waiting for its
+    /// finalization ("fire and forget").
+    ///
+    /// If successful, the transaction hash is returned. The outcome of the transaction is not checked; it
+    /// can be queried later with the transaction hash (e.g. with the `check-tx` subcommand). The energy and
+    /// the expiry time are handled as in `update_contract`. Since the nonce is not required to be final,
+    /// several updates can be sent one after the other.
+    #[tracing::instrument(name = "submit", skip_all, fields(receive_name = %update_payload.receive_name, contract = %update_payload.address))]
+    pub async fn submit_update_contract(
+        &mut self,
+        update_payload: UpdateContractPayload,
+        energy: Option<GivenEnergy>,
+        expiry: Option<TransactionTime>,
+    ) -> Result<TransactionHash, DeployError> {
+        info!("Submitting contract update....");
+
         let payload = transactions::Payload::Update {
             payload: update_payload,
         };
 
-        let expiry = expiry.unwrap_or_else(|| {
-            TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
-        });
-
-        let energy = energy.unwrap_or(GivenEnergy::Absolute(Energy { energy: 50000 }));
+        let nonce = self.get_nonce(self.signer.address()).await?;
 
-        let tx = transactions::send::make_and_sign_transaction(
-            &*self.key,
-            self.key.address,
-            nonce.nonce,
-            expiry,
-            energy,
-            payload,
-        );
-        let bi = transactions::BlockItem::AccountTransaction(tx);
+        let bi = self
+            .build_update_transaction(payload, nonce.nonce, energy, expiry)
+            .await?;
 
-        let tx_hash = self.client.send_block_item(&bi).await?;
+        self.send_block_item(&bi).await
+    }
 
-        println!("Sent transaction with hash: {tx_hash}");
+    /// A function to build and sign the transaction of a contract update with the given nonce
+    /// (see `update_contract` for the defaults of the energy and the expiry time).
+    async fn build_update_transaction(
+        &self,
+        payload: transactions::Payload,
+        nonce: Nonce,
+        energy: Option<GivenEnergy>,
+        expiry: Option<TransactionTime>,
+    ) -> Result<transactions::BlockItem<transactions::EncodedPayload>, DeployError> {
+        let expiry = expiry.unwrap_or_else(|| {
+            TransactionTime::from_seconds((chrono::Utc::now().timestamp() + 300) as u64)
+        });
 
-        let (_, block_item) = self.client.wait_until_finalized(&tx_hash).await?;
+        let energy = energy.unwrap_or(GivenEnergy::Absolute(
+            self.max_energy.unwrap_or(Energy { energy: 50000 }),
+        ));
 
-        self.check_outcome_of_update_transaction(&block_item)?;
+        let energy = match energy {
+            GivenEnergy::Absolute(energy) => {
+                construct::GivenEnergy::Absolute(self.buffered_energy(energy))
+            }
+            GivenEnergy::Add(energy) => construct::GivenEnergy::Add {
+                energy: self.buffered_energy(energy),
+                num_sigs: self.signer.num_keys(),
+            },
+        };
 
-        println!("Transaction finalized: tx_hash={}", tx_hash,);
+        let tx = construct::make_transaction(self.signer.address(), nonce, expiry, energy, payload);
 
-        Ok((tx_hash, block_item))
+        self.sign(tx).await
     }
 
     /// A function to estimate the energy needed to execute a transaction on the
@@ -297,24 +1625,20 @@ impl Deployer {
     pub async fn estimate_energy(
         &mut self,
         payload: UpdateContractPayload,
-    ) -> Result<Energy, Error> {
-        let context =
-            ContractContext::new_from_payload(self.key.address, DEFAULT_INVOKE_ENERGY, payload);
-
-        let result = self
-            .client
-            .invoke_instance(&BlockIdentifier::LastFinal, &context)
-            .await?;
-
-        match result.response {
+    ) -> Result<Energy, DeployError> {
+        match self.simulate_update_contract(payload).await? {
             InvokeContractResult::Failure {
                 return_value,
                 reason,
                 used_energy,
-            } => bail!(format!(
-                "Contract invoke failed: {reason:?}, used_energy={used_energy}, return \
-                 value={return_value:?}"
-            )),
+            } => Err(DeployError::Reject {
+                message: format!(
+                    "Contract invoke failed: {}, used_energy={used_energy}, return \
+                     value={return_value:?}",
+                    describe_reject_reason(&reason)
+                ),
+                reject_reason: reason,
+            }),
             InvokeContractResult::Success {
                 return_value: _,
                 events: _,
@@ -323,16 +1647,38 @@ impl Deployer {
         }
     }
 
+    /// A function that simulates the update of a smart contract instance, sent by the wallet account, in the last
+    /// finalized block (via `invoke_instance`). Nothing is sent to the chain.
+    ///
+    /// The result of the invoke (success or the reject reason, with the energy used) is returned.
+    pub async fn simulate_update_contract(
+        &mut self,
+        payload: UpdateContractPayload,
+    ) -> Result<InvokeContractResult, DeployError> {
+        let context = ContractContext::new_from_payload(
+            self.signer.address(),
+            self.max_energy.unwrap_or(DEFAULT_INVOKE_ENERGY),
+            payload,
+        );
+
+        let result = self
+            .client
+            .invoke_instance(&BlockIdentifier::LastFinal, &context)
+            .await?;
+
+        Ok(result.response)
+    }
+
     /// A function to get the next nonce of the wallet account.
+    /// Transient errors are retried according to the retry policy of the deployer.
     pub async fn get_nonce(
         &mut self,
         address: AccountAddress,
-    ) -> Result<AccountNonceResponse, Error> {
-        let nonce = self
-            .client
-            .get_next_account_sequence_number(&address)
-            .await?;
-        Ok(nonce)
+    ) -> Result<AccountNonceResponse, DeployError> {
+        retry(self, "Querying the nonce", |mut client| async move {
+            client.get_next_account_sequence_number(&address).await
+        })
+        .await
     }
 
     /// A function that checks the outcome of the deploy transaction.
@@ -341,7 +1687,7 @@ impl Deployer {
     fn check_outcome_of_deploy_transaction(
         &self,
         block_item: &BlockItemSummary,
-    ) -> Result<(), Error> {
+    ) -> Result<(), DeployError> {
         match &block_item.details {
             BlockItemSummaryDetails::AccountTransaction(a) => match &a.effects {
                 AccountTransactionEffects::None {
@@ -349,23 +1695,24 @@ impl Deployer {
                     reject_reason,
                 } => {
                     if *transaction_type != Some(TransactionType::DeployModule) {
-                        bail!("Expected transaction type to be of type DeployModule but it was instead {transaction_type:?}",);
+                        return Err(DeployError::Other(anyhow::anyhow!("Expected transaction type to be of type DeployModule but it was instead {transaction_type:?}")));
                     }
 
-                    bail!(format!(
-                        "Module deploy rejected with reason: {reject_reason:?}"
-                    ))
+                    Err(DeployError::Reject {
+                        reject_reason: reject_reason.clone(),
+                        message: format!(
+                            "Module deploy rejected with reason: {}",
+                            describe_reject_reason(reject_reason)
+                        ),
+                    })
                 }
                 AccountTransactionEffects::ModuleDeployed { module_ref: _ } => Ok(()),
-                _ => bail!(
+                _ => Err(DeployError::Other(anyhow::anyhow!(
                     "The parsed account transaction effect should be of type `ModuleDeployed` or \
                      `None` (in case the transaction reverted)"
-                ),
+                ))),
             },
-            _ => bail!(
-                "Can only parse an account transaction (no account creation transaction or chain \
-                 update transaction)"
-            ),
+            _ => Err(not_an_account_transaction()),
         }
     }
 
@@ -375,7 +1722,8 @@ impl Deployer {
     fn check_outcome_of_initialization_transaction(
         &self,
         block_item: &BlockItemSummary,
-    ) -> Result<ContractAddress, Error> {
+        contract_name: &str,
+    ) -> Result<ContractAddress, DeployError> {
         match &block_item.details {
             BlockItemSummaryDetails::AccountTransaction(a) => match &a.effects {
                 AccountTransactionEffects::None {
@@ -383,23 +1731,25 @@ impl Deployer {
                     reject_reason,
                 } => {
                     if *transaction_type != Some(TransactionType::InitContract) {
-                        bail!("Expected transaction type to be of type InitContract but it was instead {transaction_type:?}");
+                        return Err(DeployError::Other(anyhow::anyhow!("Expected transaction type to be of type InitContract but it was instead {transaction_type:?}")));
                     }
 
-                    bail!(format!(
-                        "Contract init rejected with reason: {reject_reason:?}"
-                    ))
+                    Err(DeployError::Reject {
+                        reject_reason: reject_reason.clone(),
+                        message: format!(
+                            "Contract init rejected with reason: {}",
+                            describe_init_reject(contract_name, reject_reason)
+                                .unwrap_or_else(|| describe_reject_reason(reject_reason))
+                        ),
+                    })
                 }
                 AccountTransactionEffects::ContractInitialized { data } => Ok(data.address),
-                _ => bail!(
+                _ => Err(DeployError::Other(anyhow::anyhow!(
                     "The parsed account transaction effect should be of type \
                      `ContractInitialized` or `None` (in case the transaction reverted)"
-                ),
+                ))),
             },
-            _ => bail!(
-                "Can only parse an account transaction (no account creation transaction or chain \
-                 update transaction)"
-            ),
+            _ => Err(not_an_account_transaction()),
         }
     }
 
@@ -409,7 +1759,7 @@ impl Deployer {
     fn check_outcome_of_update_transaction(
         &self,
         block_item: &BlockItemSummary,
-    ) -> Result<(), Error> {
+    ) -> Result<(), DeployError> {
         match &block_item.details {
             BlockItemSummaryDetails::AccountTransaction(a) => match &a.effects {
                 AccountTransactionEffects::None {
@@ -417,23 +1767,146 @@ impl Deployer {
                     reject_reason,
                 } => {
                     if *transaction_type != Some(TransactionType::Update) {
-                        bail!("Expected transaction type to be of type Update but it was instead {transaction_type:?}");
+                        return Err(DeployError::Other(anyhow::anyhow!("Expected transaction type to be of type Update but it was instead {transaction_type:?}")));
                     }
 
-                    bail!(format!(
-                        "Contract update rejected with reason: {reject_reason:?}"
-                    ))
+                    Err(DeployError::Reject {
+                        reject_reason: reject_reason.clone(),
+                        message: format!(
+                            "Contract update rejected with reason: {}",
+                            describe_reject_reason(reject_reason)
+                        ),
+                    })
                 }
                 AccountTransactionEffects::ContractUpdateIssued { effects: _ } => Ok(()),
-                _ => bail!(
+                _ => Err(DeployError::Other(anyhow::anyhow!(
                     "The parsed account transaction effect should be of type \
                      `ContractUpdateIssued` or `None` (in case the transaction reverted)"
-                ),
+                ))),
             },
-            _ => bail!(
-                "Can only parse an account transaction (no account creation transaction or chain \
-                 update transaction)"
-            ),
+            _ => Err(not_an_account_transaction()),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns the error of a failed call to the node with the given status code and message.
+    fn call_error(code: Code, message: &str) -> v2::RPCError {
+        v2::RPCError::CallError(tonic::Status::new(code, message))
+    }
+
+    #[test]
+    fn test_retry_delay() {
+        let config = RetryConfig::default();
+
+        let delays: Vec<Duration> = (1..=5).map(|retry| config.delay(retry)).collect();
+
+        assert_eq!(
+            delays,
+            [1, 2, 4, 8, 16].map(Duration::from_secs).to_vec(),
+            "The delay should be doubled after each retry"
+        );
+
+        let config = RetryConfig {
+            max_attempts: 4,
+            base_delay: Duration::from_millis(100),
+            backoff_factor: 3,
+        };
+
+        assert_eq!(config.delay(1), Duration::from_millis(100));
+        assert_eq!(config.delay(3), Duration::from_millis(900));
+
+        // A retry of `0` is treated like the first retry
+        assert_eq!(config.delay(0), config.delay(1));
+
+        // The backoff factor and the delay saturate instead of overflowing for large retries
+        assert_eq!(config.delay(64), Duration::from_millis(100) * u32::MAX);
+        assert_eq!(config.delay(u32::MAX), config.delay(64));
+
+        let config = RetryConfig {
+            base_delay: Duration::MAX,
+            ..RetryConfig::default()
+        };
+
+        assert_eq!(config.delay(2), Duration::MAX);
+
+        let no_retry = RetryConfig::no_retry();
+
+        assert_eq!(no_retry.max_attempts, 1);
+        assert_eq!(no_retry.base_delay, RetryConfig::default().base_delay);
+        assert_eq!(
+            no_retry.backoff_factor,
+            RetryConfig::default().backoff_factor
+        );
+    }
+
+    #[test]
+    fn test_is_transient() {
+        for code in [
+            Code::Unavailable,
+            Code::DeadlineExceeded,
+            Code::ResourceExhausted,
+        ] {
+            assert!(
+                call_error(code, "node is busy").is_transient(),
+                "{code:?} should be transient"
+            );
+        }
+
+        // `Unknown` and `Cancelled` are only transient if the connection is broken
+        for code in [Code::Unknown, Code::Cancelled] {
+            let error = call_error(code, "error trying to connect: transport error");
+
+            assert!(error.is_connection_error());
+            assert!(error.is_transient(), "{code:?} should be transient");
+
+            let error = call_error(code, "invalid transaction");
+
+            assert!(!error.is_connection_error());
+            assert!(!error.is_transient(), "{code:?} should not be transient");
+        }
+
+        for code in [
+            Code::Aborted,
+            Code::InvalidArgument,
+            Code::NotFound,
+            Code::AlreadyExists,
+            Code::PermissionDenied,
+            Code::FailedPrecondition,
+            Code::Internal,
+        ] {
+            assert!(
+                !call_error(code, "connection reset").is_transient(),
+                "{code:?} should not be transient"
+            );
+        }
+
+        let error = v2::QueryError::RPCError(call_error(Code::Unavailable, "node is busy"));
+
+        assert!(error.is_transient());
+        assert!(!v2::QueryError::NotFound.is_transient());
+        assert!(!v2::QueryError::NotFound.is_connection_error());
+    }
+
+    #[test]
+    fn test_is_nonce_conflict() {
+        assert!(is_nonce_conflict(&DeployError::from(call_error(
+            Code::InvalidArgument,
+            "Duplicate nonce"
+        ))));
+
+        for error in [
+            call_error(Code::InvalidArgument, "invalid signature"),
+            call_error(Code::Unavailable, "duplicate nonce"),
+        ] {
+            assert!(!is_nonce_conflict(&DeployError::from(error)));
+        }
+
+        assert!(!is_nonce_conflict(&DeployError::Other(anyhow::anyhow!(
+            "duplicate nonce"
+        ))));
+    }
+}
@@ -0,0 +1,260 @@
+use concordium_rust_sdk::{
+    common::types::Amount,
+    smart_contracts::common as contracts_common,
+    types::{ContractAddress, RejectReason},
+    v2,
+};
+use std::{
+    fmt::{Debug, Display},
+    str::FromStr,
+};
+
+/// The contracts of the umbrella oracle protocol, e.g. to decode their reject reasons or as the values of the `--only`
+/// flag of the `deploy` subcommand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProtocolContract {
+    Registry,
+    StakingBank,
+    UmbrellaFeeds,
+}
+
+impl ProtocolContract {
+    /// A function that returns the protocol contract with the given contract name (e.g. `registry`).
+    pub fn from_contract_name(contract_name: &str) -> Option<ProtocolContract> {
+        match contract_name {
+            "registry" => Some(ProtocolContract::Registry),
+            "staking_bank" => Some(ProtocolContract::StakingBank),
+            "umbrella_feeds" => Some(ProtocolContract::UmbrellaFeeds),
+            _ => None,
+        }
+    }
+}
+
+impl Display for ProtocolContract {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProtocolContract::Registry => write!(f, "registry"),
+            ProtocolContract::StakingBank => write!(f, "staking_bank"),
+            ProtocolContract::UmbrellaFeeds => write!(f, "umbrella_feeds"),
+        }
+    }
+}
+
+impl FromStr for ProtocolContract {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        ProtocolContract::from_contract_name(&s.replace('-', "_")).ok_or_else(|| {
+            anyhow::anyhow!(
+                "Unknown contract `{s}`. Use `registry`, `staking_bank`, or `umbrella_feeds`."
+            )
+        })
+    }
+}
+
+/// A function that deserializes the error enum `E` from the return value of a rejected invoke.
+fn decode<E: contracts_common::Deserial + Debug>(return_value: &[u8]) -> Option<String> {
+    contracts_common::from_bytes::<E>(return_value)
+        .ok()
+        .map(|error| format!("{error:?}"))
+}
+
+/// A function that decodes the return value of a rejected invoke into the name of the error
+/// variant of the given contract (e.g. `Unauthorized`).
+/// Returns `None` if the return value is not a valid error of the contract.
+pub fn decode_reject(contract_kind: ProtocolContract, return_value: &[u8]) -> Option<String> {
+    match contract_kind {
+        ProtocolContract::Registry => decode::<registry::CustomContractError>(return_value),
+        ProtocolContract::StakingBank => decode::<staking_bank::CustomContractError>(return_value),
+        ProtocolContract::UmbrellaFeeds => {
+            decode::<umbrella_feeds::CustomContractError>(return_value)
+        }
+    }
+}
+
+/// A function that converts a reject code into the serialized tag of the error enum.
+/// The contracts derive their reject codes from the order of the error variants (`-1` for the first variant),
+/// which matches the serialized tag of the error enum.
+fn reject_code_to_tag(code: i32) -> Option<u8> {
+    u8::try_from(code.checked_neg()?.checked_sub(1)?).ok()
+}
+
+/// A function that decodes the reject code of the given contract into the name of its error variant.
+pub fn decode_reject_code(contract_kind: ProtocolContract, code: i32) -> Option<String> {
+    decode_reject(contract_kind, &[reject_code_to_tag(code)?])
+}
+
+/// A function that decodes a reject of the registry contract into its error enum, so that the callers can
+/// react to specific errors (e.g. `NameNotRegistered`).
+/// Returns `None` if the reject reason is not a reject by the registry contract.
+pub fn decode_registry_reject(
+    reject_reason: &RejectReason,
+) -> Option<registry::CustomContractError> {
+    match reject_reason {
+        RejectReason::RejectedReceive {
+            reject_reason: code,
+            receive_name,
+            ..
+        } if receive_name.as_receive_name().contract_name() == "registry" => {
+            contracts_common::from_bytes(&[reject_code_to_tag(*code)?]).ok()
+        }
+        _ => None,
+    }
+}
+
+/// The error returned by a lookup in the registry contract if no contract is registered under the name.
+#[derive(Debug, Clone)]
+pub struct NameNotRegistered {
+    /// The registry contract.
+    pub registry_contract: ContractAddress,
+    /// The name that is not registered.
+    pub name: String,
+}
+
+impl NameNotRegistered {
+    /// A function that returns the `register` command that registers a contract under the name in the
+    /// registry contract, e.g. `cargo run register --node <node> ... --entry StakingBank=<index>,<subindex>`.
+    pub fn register_command(&self, node: &str) -> String {
+        format!(
+            "cargo run register --node {node} --account <key file> --registry \"{}\" --entry {}=<index>,<subindex>",
+            self.registry_contract, self.name
+        )
+    }
+}
+
+impl Display for NameNotRegistered {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "The registry {} has no contract registered under the name `{}`. \
+             Register the contract with the `register` command first.",
+            self.registry_contract, self.name
+        )
+    }
+}
+
+impl std::error::Error for NameNotRegistered {}
+
+/// The error returned by the core functions of the deployer (e.g. `deploy_wasm_module`, `init_contract` and
+/// `update_contract`), so that the callers can react to the kind of the failure.
+#[derive(Debug)]
+pub enum DeployError {
+    /// A request to the node failed (e.g. the node is unreachable, or the finalization of a sent transaction
+    /// could not be awaited).
+    Connection(anyhow::Error),
+    /// A file (e.g. a wasm module or a pending transactions file) could not be read or parsed.
+    Parse(anyhow::Error),
+    /// A transaction was rejected on the chain, or its simulation was rejected.
+    Reject {
+        /// The reject reason, e.g. `RejectedInit` with the reject code of the contract.
+        reject_reason: RejectReason,
+        /// The description of the reject, e.g. "Contract init rejected with reason: umbrella_feeds rejected:
+        /// InvalidDecimals (code -24)".
+        message: String,
+    },
+    /// The account cannot pay the estimated costs of the transactions.
+    InsufficientFunds {
+        /// The balance of the account that is neither staked nor locked in a release schedule.
+        available: Amount,
+        /// The estimated costs.
+        required: Amount,
+    },
+    /// Any other failure, e.g. the keys do not meet the signature thresholds of the account.
+    Other(anyhow::Error),
+}
+
+impl DeployError {
+    /// A function that wraps the error with additional context (see `anyhow::Context`). The kind of the error
+    /// is kept; rejects and insufficient funds are returned unchanged.
+    pub fn context(self, context: impl Display + Send + Sync + 'static) -> DeployError {
+        match self {
+            DeployError::Connection(error) => DeployError::Connection(error.context(context)),
+            DeployError::Parse(error) => DeployError::Parse(error.context(context)),
+            DeployError::Other(error) => DeployError::Other(error.context(context)),
+            error => error,
+        }
+    }
+}
+
+impl Display for DeployError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeployError::Connection(error)
+            | DeployError::Parse(error)
+            | DeployError::Other(error) => Display::fmt(error, f),
+            DeployError::Reject { message, .. } => write!(f, "{message}"),
+            DeployError::InsufficientFunds {
+                available,
+                required,
+            } => write!(
+                f,
+                "insufficient balance: have {available} CCD, need ~{required} CCD"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for DeployError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DeployError::Connection(error)
+            | DeployError::Parse(error)
+            | DeployError::Other(error) => error.source(),
+            DeployError::Reject { .. } | DeployError::InsufficientFunds { .. } => None,
+        }
+    }
+}
+
+impl From<v2::QueryError> for DeployError {
+    fn from(error: v2::QueryError) -> Self {
+        DeployError::Connection(error.into())
+    }
+}
+
+impl From<v2::RPCError> for DeployError {
+    fn from(error: v2::RPCError) -> Self {
+        DeployError::Connection(error.into())
+    }
+}
+
+/// A function that describes a reject of a contract of the protocol,
+/// e.g. "registry rejected: UnauthorizedAccount (code -5)".
+/// Returns `None` if the reject reason is not a reject by a contract of the protocol.
+pub fn describe_contract_reject(reject_reason: &RejectReason) -> Option<String> {
+    match reject_reason {
+        RejectReason::RejectedReceive {
+            reject_reason: code,
+            receive_name,
+            ..
+        } => {
+            let contract_kind = ProtocolContract::from_contract_name(
+                receive_name.as_receive_name().contract_name(),
+            )?;
+
+            describe_reject_code(contract_kind, *code)
+        }
+        _ => None,
+    }
+}
+
+/// A function that describes a reject of the initialization of a contract of the protocol,
+/// e.g. "umbrella_feeds rejected: InvalidDecimals (code -24)".
+/// The reject reason of an initialization does not contain the contract name, so the contract name
+/// (e.g. `umbrella_feeds`) has to be given.
+/// Returns `None` if the reject reason is not a reject by the initialization of a contract of the protocol.
+pub fn describe_init_reject(contract_name: &str, reject_reason: &RejectReason) -> Option<String> {
+    match reject_reason {
+        RejectReason::RejectedInit {
+            reject_reason: code,
+        } => describe_reject_code(ProtocolContract::from_contract_name(contract_name)?, *code),
+        _ => None,
+    }
+}
+
+/// A function that describes the reject code of a contract of the protocol,
+/// e.g. "registry rejected: UnauthorizedAccount (code -5)".
+pub fn describe_reject_code(contract_kind: ProtocolContract, code: i32) -> Option<String> {
+    let error = decode_reject_code(contract_kind, code)?;
+
+    Some(format!("{contract_kind} rejected: {error} (code {code})"))
+}
@@ -0,0 +1,457 @@
+//! Reading the price data and the validator keys of the `update-feeds` subcommand (enabled with the `dev` feature),
+//! which signs price feed updates locally with the test harness of the umbrella feeds contract instead of the
+//! off-chain signer service.
+
+use anyhow::{bail, Context, Error};
+use concordium_rust_sdk::smart_contracts::common::Timestamp;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey};
+use serde::Deserialize;
+use std::path::{Path, PathBuf};
+use umbrella_feeds::PriceData;
+use zeroize::Zeroize;
+
+/// The default heartbeat in seconds of the price data in the prices file.
+const DEFAULT_HEARTBEAT: u64 = 60;
+
+/// A price of a price feed in the prices file, e.g.
+/// `{"key": "ETH-USD", "price": 180000000000, "timestamp": 1700000000000}`.
+#[derive(Debug, Deserialize)]
+struct PriceFileEntry {
+    /// The key of the price feed.
+    key: String,
+    /// The price.
+    price: u128,
+    /// The time in milliseconds since the Unix epoch when the validators decided on the price.
+    timestamp: u64,
+    /// The heartbeat in seconds. Defaults to 60 seconds.
+    #[serde(default)]
+    heartbeat: Option<u64>,
+    /// The `data` byte. Defaults to 0.
+    #[serde(default)]
+    data: u8,
+}
+
+/// A function to read the price data from a JSON file containing a list of price entries. As in the prices CSV file
+/// (see `read_prices_csv`), entries with a zero heartbeat or with a key given in an earlier entry are rejected.
+pub fn read_prices_file(prices_file: &Path) -> Result<Vec<(String, PriceData)>, Error> {
+    let content = std::fs::read_to_string(prices_file)
+        .with_context(|| format!("Unable to read the prices file {prices_file:?}."))?;
+
+    let entries: Vec<PriceFileEntry> = serde_json::from_str(&content)
+        .with_context(|| format!("Unable to parse the prices file {prices_file:?}."))?;
+
+    if entries.is_empty() {
+        bail!("The prices file {prices_file:?} contains no price entries.");
+    }
+
+    let mut prices: Vec<(String, PriceData)> = Vec::with_capacity(entries.len());
+
+    for (index, entry) in entries.into_iter().enumerate() {
+        let entry_number = index + 1;
+
+        let heartbeat = entry.heartbeat.unwrap_or(DEFAULT_HEARTBEAT);
+
+        if heartbeat == 0 {
+            bail!(
+                "Invalid entry {entry_number} of the prices file {prices_file:?}: the heartbeat has to be at least 1 \
+                 second (the contract rejects price data with a zero heartbeat)."
+            );
+        }
+
+        if prices.iter().any(|(key, _)| *key == entry.key) {
+            bail!(
+                "Invalid entry {entry_number} of the prices file {prices_file:?}: the key `{}` is given in an earlier entry.",
+                entry.key
+            );
+        }
+
+        prices.push((
+            entry.key,
+            PriceData {
+                data: entry.data,
+                heartbeat,
+                timestamp: Timestamp::from_timestamp_millis(entry.timestamp),
+                price: entry.price,
+            },
+        ));
+    }
+
+    Ok(prices)
+}
+
+/// The header of the prices CSV file.
+const PRICES_CSV_HEADER: [&str; 4] = ["key", "value", "timestamp", "heartbeat"];
+
+/// A function to read the price data from a CSV file (e.g. a spreadsheet export) with the header
+/// `key,value,timestamp,heartbeat` and one price feed per row, e.g. `ETH-USD,180000000000,1700000000000,60`.
+/// The value is the price, the timestamp is in milliseconds since the Unix epoch, and the heartbeat is in
+/// seconds (defaults to 60 seconds if the cell is empty). Rows without a key, with values out of range,
+/// or with a key given in an earlier row are rejected. Empty lines are skipped.
+pub fn read_prices_csv(prices_csv: &Path) -> Result<Vec<(String, PriceData)>, Error> {
+    let content = std::fs::read_to_string(prices_csv)
+        .with_context(|| format!("Unable to read the prices CSV file {prices_csv:?}."))?;
+
+    let mut lines = content
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty());
+
+    let Some((_, header)) = lines.next() else {
+        bail!("The prices CSV file {prices_csv:?} is empty.");
+    };
+
+    let columns: Vec<String> = header
+        .split(',')
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+
+    if columns != PRICES_CSV_HEADER {
+        bail!(
+            "The prices CSV file {prices_csv:?} has the header `{header}`, expected `{}`.",
+            PRICES_CSV_HEADER.join(",")
+        );
+    }
+
+    let mut prices: Vec<(String, PriceData)> = Vec::new();
+
+    for (line_number, line) in lines {
+        let price = parse_prices_csv_row(line).with_context(|| {
+            format!("Invalid row {line_number} of the prices CSV file {prices_csv:?}.")
+        })?;
+
+        if prices.iter().any(|(key, _)| *key == price.0) {
+            bail!(
+                "Invalid row {line_number} of the prices CSV file {prices_csv:?}: the key `{}` is given in an earlier row.",
+                price.0
+            );
+        }
+
+        prices.push(price);
+    }
+
+    if prices.is_empty() {
+        bail!("The prices CSV file {prices_csv:?} contains no price rows.");
+    }
+
+    Ok(prices)
+}
+
+/// A function to parse one row (`key,value,timestamp,heartbeat`) of the prices CSV file.
+fn parse_prices_csv_row(row: &str) -> Result<(String, PriceData), Error> {
+    let cells: Vec<&str> = row.split(',').map(str::trim).collect();
+
+    let [key, value, timestamp, heartbeat] = cells[..] else {
+        bail!("Expected 4 cells, found {}.", cells.len());
+    };
+
+    if key.is_empty() {
+        bail!("The key is missing.");
+    }
+
+    let price: u128 = value.parse().with_context(|| {
+        format!(
+            "The value `{value}` is not an integer between 0 and {}.",
+            u128::MAX
+        )
+    })?;
+
+    let timestamp: u64 = timestamp.parse().with_context(|| {
+        format!(
+            "The timestamp `{timestamp}` is not an integer between 0 and {} milliseconds.",
+            u64::MAX
+        )
+    })?;
+
+    let heartbeat = if heartbeat.is_empty() {
+        DEFAULT_HEARTBEAT
+    } else {
+        heartbeat.parse().with_context(|| {
+            format!(
+                "The heartbeat `{heartbeat}` is not an integer between 1 and {} seconds.",
+                u64::MAX
+            )
+        })?
+    };
+
+    if heartbeat == 0 {
+        bail!("The heartbeat has to be at least 1 second (the contract rejects price data with a zero heartbeat).");
+    }
+
+    Ok((
+        key.to_string(),
+        PriceData {
+            data: 0,
+            heartbeat,
+            timestamp: Timestamp::from_timestamp_millis(timestamp),
+            price,
+        },
+    ))
+}
+
+/// A function to read the Ed25519 validator keys. Each file contains one hex-encoded 32-byte secret key.
+/// A path can also be a directory, in which case all `.key` files in the directory are read.
+pub fn read_signer_keys(paths: &[PathBuf]) -> Result<Vec<Keypair>, Error> {
+    let mut files = Vec::new();
+
+    for path in paths {
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)
+                .with_context(|| format!("Unable to read the key directory {path:?}."))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .with_context(|| format!("Unable to read the key directory {path:?}."))?;
+
+            entries.retain(|entry| {
+                entry.is_file()
+                    && entry
+                        .extension()
+                        .is_some_and(|extension| extension == "key")
+            });
+
+            if entries.is_empty() {
+                bail!("The key directory {path:?} contains no `.key` files.");
+            }
+
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let mut keys: Vec<Keypair> = Vec::with_capacity(files.len());
+
+    for file in files {
+        let mut content = std::fs::read_to_string(&file)
+            .with_context(|| format!("Unable to read the key file {file:?}."))?;
+
+        let bytes = hex::decode(content.trim());
+
+        content.zeroize();
+
+        let mut bytes =
+            bytes.with_context(|| format!("The key file {file:?} does not contain hex."))?;
+
+        let secret = SecretKey::from_bytes(&bytes);
+
+        bytes.zeroize();
+
+        let secret = secret.map_err(|_| {
+            anyhow::anyhow!("The key file {file:?} does not contain a 32-byte Ed25519 secret key.")
+        })?;
+        let public = PublicKey::from(&secret);
+
+        if keys.iter().any(|key| key.public == public) {
+            bail!("The key file {file:?} contains a key that is already given.");
+        }
+
+        keys.push(Keypair { secret, public });
+    }
+
+    Ok(keys)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes the content to a file in the temporary directory that is unique to the test (and the process).
+    fn write_temp_file(name: &str, content: &str) -> PathBuf {
+        let file = std::env::temp_dir().join(format!("{}-{name}", std::process::id()));
+
+        std::fs::write(&file, content).expect("Should be able to write the temporary file");
+
+        file
+    }
+
+    #[test]
+    fn test_read_prices_file() {
+        let file = write_temp_file(
+            "prices.json",
+            r#"[{"key": "ETH-USD", "price": 180000000000, "timestamp": 1700000000000},
+                {"key": "BTC-USD", "price": 3, "timestamp": 5, "heartbeat": 120, "data": 1}]"#,
+        );
+
+        let prices = read_prices_file(&file).expect("Should be a valid prices file");
+
+        assert_eq!(
+            prices,
+            vec![
+                (
+                    "ETH-USD".to_string(),
+                    PriceData {
+                        data: 0,
+                        heartbeat: DEFAULT_HEARTBEAT,
+                        timestamp: Timestamp::from_timestamp_millis(1700000000000),
+                        price: 180000000000,
+                    }
+                ),
+                (
+                    "BTC-USD".to_string(),
+                    PriceData {
+                        data: 1,
+                        heartbeat: 120,
+                        timestamp: Timestamp::from_timestamp_millis(5),
+                        price: 3,
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_prices_file_invalid_entries() {
+        let file = write_temp_file(
+            "prices-zero-heartbeat.json",
+            r#"[{"key": "ETH-USD", "price": 1, "timestamp": 1, "heartbeat": 0}]"#,
+        );
+
+        let error = read_prices_file(&file).expect_err("A zero heartbeat should be rejected");
+        assert!(format!("{error:#}").contains("heartbeat"));
+
+        let file = write_temp_file(
+            "prices-duplicate-key.json",
+            r#"[{"key": "ETH-USD", "price": 1, "timestamp": 1},
+                {"key": "ETH-USD", "price": 2, "timestamp": 2}]"#,
+        );
+
+        let error = read_prices_file(&file).expect_err("A duplicate key should be rejected");
+        assert!(format!("{error:#}").contains("earlier entry"));
+
+        let file = write_temp_file("prices-empty.json", "[]");
+
+        read_prices_file(&file).expect_err("An empty list should be rejected");
+    }
+
+    #[test]
+    fn test_parse_prices_csv_row() {
+        assert_eq!(
+            parse_prices_csv_row(" ETH-USD , 180000000000 , 1700000000000 , 120 ")
+                .expect("Should be a valid row"),
+            (
+                "ETH-USD".to_string(),
+                PriceData {
+                    data: 0,
+                    heartbeat: 120,
+                    timestamp: Timestamp::from_timestamp_millis(1700000000000),
+                    price: 180000000000,
+                }
+            )
+        );
+
+        // An empty heartbeat cell defaults to the default heartbeat.
+        let (_, price_data) = parse_prices_csv_row("ETH-USD,1,2,").expect("Should be a valid row");
+        assert_eq!(price_data.heartbeat, DEFAULT_HEARTBEAT);
+
+        for (row, expected) in [
+            ("ETH-USD,1,2", "Expected 4 cells, found 3"),
+            ("ETH-USD,1,2,3,4", "Expected 4 cells, found 5"),
+            (",1,2,3", "The key is missing"),
+            ("ETH-USD,1.5,2,3", "The value `1.5`"),
+            ("ETH-USD,-1,2,3", "The value `-1`"),
+            ("ETH-USD,1,abc,3", "The timestamp `abc`"),
+            ("ETH-USD,1,2,x", "The heartbeat `x`"),
+            ("ETH-USD,1,2,0", "at least 1 second"),
+        ] {
+            let error = parse_prices_csv_row(row).expect_err("The row should be rejected");
+            assert!(
+                format!("{error:#}").contains(expected),
+                "Unexpected error for the row `{row}`: {error:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_prices_csv() {
+        let file = write_temp_file(
+            "prices.csv",
+            "Key, Value, Timestamp, Heartbeat\n\nETH-USD,180000000000,1700000000000,\nBTC-USD,3,5,120\n",
+        );
+
+        let prices = read_prices_csv(&file).expect("Should be a valid prices CSV file");
+
+        assert_eq!(
+            prices,
+            vec![
+                (
+                    "ETH-USD".to_string(),
+                    PriceData {
+                        data: 0,
+                        heartbeat: DEFAULT_HEARTBEAT,
+                        timestamp: Timestamp::from_timestamp_millis(1700000000000),
+                        price: 180000000000,
+                    }
+                ),
+                (
+                    "BTC-USD".to_string(),
+                    PriceData {
+                        data: 0,
+                        heartbeat: 120,
+                        timestamp: Timestamp::from_timestamp_millis(5),
+                        price: 3,
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_read_prices_csv_invalid_files() {
+        for (name, content, expected) in [
+            ("prices-empty.csv", "\n  \n", "is empty"),
+            (
+                "prices-wrong-header.csv",
+                "key,price,timestamp,heartbeat\nETH-USD,1,2,3\n",
+                "has the header",
+            ),
+            (
+                "prices-missing-header.csv",
+                "ETH-USD,1,2,3\n",
+                "has the header",
+            ),
+            (
+                "prices-no-rows.csv",
+                "key,value,timestamp,heartbeat\n",
+                "contains no price rows",
+            ),
+            (
+                "prices-zero-heartbeat.csv",
+                "key,value,timestamp,heartbeat\nETH-USD,1,2,0\n",
+                "at least 1 second",
+            ),
+            (
+                "prices-duplicate-key.csv",
+                "key,value,timestamp,heartbeat\nETH-USD,1,2,3\nETH-USD,4,5,6\n",
+                "earlier row",
+            ),
+            (
+                "prices-malformed-value.csv",
+                "key,value,timestamp,heartbeat\nETH-USD,one,2,3\n",
+                "The value `one`",
+            ),
+            (
+                "prices-wrong-column-count.csv",
+                "key,value,timestamp,heartbeat\nETH-USD,1,2\n",
+                "Expected 4 cells",
+            ),
+        ] {
+            let file = write_temp_file(name, content);
+
+            let error = read_prices_csv(&file).expect_err("The file should be rejected");
+            assert!(
+                format!("{error:#}").contains(expected),
+                "Unexpected error for the file `{name}`: {error:#}"
+            );
+        }
+
+        // The row number counts the empty lines, so that it matches the line in the file.
+        let file = write_temp_file(
+            "prices-row-number.csv",
+            "key,value,timestamp,heartbeat\n\nETH-USD,1,2,0\n",
+        );
+
+        let error = read_prices_csv(&file).expect_err("The file should be rejected");
+        assert!(format!("{error:#}").contains("Invalid row 3"));
+    }
+}
@@ -0,0 +1,81 @@
+use crate::output::write_json_atomically;
+use anyhow::{bail, Context, Error};
+use concordium_rust_sdk::types::{smart_contracts::ModuleReference, ContractAddress};
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// A journal of the completed steps of the `deploy` command. The journal is written after every step,
+/// so that an interrupted deployment can be resumed with the `--resume` flag without repeating already
+/// completed steps.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct DeployJournal {
+    /// Module reference of the deployed registry module.
+    pub registry_module: Option<ModuleReference>,
+    /// Module reference of the deployed staking_bank module.
+    pub staking_bank_module: Option<ModuleReference>,
+    /// Module reference of the deployed umbrella_feeds module.
+    pub umbrella_feeds_module: Option<ModuleReference>,
+    /// Contract address of the initialized registry.
+    pub registry: Option<ContractAddress>,
+    /// Contract address of the initialized staking_bank.
+    pub staking_bank: Option<ContractAddress>,
+    /// Contract address of the initialized umbrella_feeds.
+    pub umbrella_feeds: Option<ContractAddress>,
+}
+
+impl DeployJournal {
+    /// A function to load the journal from a file.
+    /// If the file does not exist, an empty journal is returned (i.e. no steps are skipped).
+    pub fn from_file(journal_file: &Path) -> Result<DeployJournal, Error> {
+        if !journal_file.exists() {
+            return Ok(DeployJournal::default());
+        }
+
+        let content = std::fs::read_to_string(journal_file)
+            .with_context(|| format!("Could not read the journal file {journal_file:?}."))?;
+
+        serde_json::from_str(&content)
+            .with_context(|| format!("Could not parse the journal file {journal_file:?}."))
+    }
+
+    /// A function to write the journal to a file (atomically).
+    pub fn write_to_file(&self, journal_file: &Path) -> Result<(), Error> {
+        write_json_atomically(self, journal_file)
+            .with_context(|| format!("Could not write the journal file {journal_file:?}."))
+    }
+
+    /// A function that checks that the given module references match the module references
+    /// recorded in the journal. Resuming a deployment with different modules would mix contracts
+    /// from different modules.
+    pub fn ensure_modules_match(
+        &self,
+        registry_module: ModuleReference,
+        staking_bank_module: ModuleReference,
+        umbrella_feeds_module: ModuleReference,
+    ) -> Result<(), Error> {
+        for (name, journaled, current) in [
+            ("registry", self.registry_module, registry_module),
+            (
+                "staking_bank",
+                self.staking_bank_module,
+                staking_bank_module,
+            ),
+            (
+                "umbrella_feeds",
+                self.umbrella_feeds_module,
+                umbrella_feeds_module,
+            ),
+        ] {
+            if let Some(journaled) = journaled {
+                if journaled != current {
+                    bail!(
+                        "The {name} module reference {current} differs from the module reference \
+                         {journaled} recorded in the journal. Start a new deployment without the \
+                         `--resume` flag."
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::{bail, Error};
+use std::str::FromStr;
+use tracing::Level;
+
+/// The format of the log output of the scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFormat {
+    /// Human-readable lines (default).
+    Human,
+    /// One JSON object per line, e.g. for ingestion by CI pipelines.
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "human" => Ok(LogFormat::Human),
+            "json" => Ok(LogFormat::Json),
+            _ => bail!("Unknown log format `{s}`. Use `human` or `json`."),
+        }
+    }
+}
+
+/// A function that initializes the global logger with the given format.
+/// The logs are written to stderr so that the results of the queries on stdout are not interleaved with them.
+/// In the JSON format, the fields of the current span (e.g. the step name and the module reference) are included in each line.
+/// The debug logs are only written if `verbose` is set.
+pub fn init(log_format: LogFormat, verbose: bool) {
+    let builder = tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(if verbose { Level::DEBUG } else { Level::INFO });
+
+    match log_format {
+        LogFormat::Human => builder.without_time().with_target(false).init(),
+        LogFormat::Json => builder
+            .json()
+            .with_current_span(true)
+            .with_span_list(true)
+            .init(),
+    }
+}
@@ -0,0 +1,105 @@
+use crate::output::write_json_atomically;
+use anyhow::{Context, Error};
+use concordium_rust_sdk::types::{
+    hashes::TransactionHash, smart_contracts::ModuleReference, ContractAddress,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+
+/// The name of the manifest file in the manifest directory.
+const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// A record of a deployment of the umbrella oracle protocol for audits.
+/// Written by the `deploy` command when the `--manifest-dir` flag is provided. The contracts that were not
+/// deployed (see the `--only` flag) are omitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployManifest {
+    /// The time the manifest was written (RFC 3339).
+    pub created_at: String,
+    /// The registry module and contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<ManifestEntry>,
+    /// The staking_bank module and contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staking_bank: Option<ManifestEntry>,
+    /// The umbrella_feeds module and contract.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub umbrella_feeds: Option<ManifestEntry>,
+}
+
+/// The module and the contract instance of one contract of the protocol in the manifest.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    /// The path of the wasm file the module was read from.
+    pub wasm_file: PathBuf,
+    /// The SHA2-256 digest (hex) of the wasm file.
+    pub wasm_sha256: String,
+    /// The module reference.
+    pub module_reference: ModuleReference,
+    /// The transaction hash of the module deployment. `None` if the module already existed on the chain.
+    pub deploy_tx_hash: Option<TransactionHash>,
+    /// The contract address of the instance.
+    pub contract_address: ContractAddress,
+    /// The transaction hash of the initialization. `None` if the initialization was skipped because of the journal.
+    pub init_tx_hash: Option<TransactionHash>,
+    /// The serialized init parameter (hex).
+    pub init_parameter: String,
+    /// The name of the file in the manifest directory containing the serialized init parameter.
+    pub init_parameter_file: String,
+}
+
+impl ManifestEntry {
+    /// A function to create the manifest entry of a contract. The wasm file is hashed.
+    pub fn new(
+        wasm_file: &Path,
+        module_reference: ModuleReference,
+        deploy_tx_hash: Option<TransactionHash>,
+        contract_address: ContractAddress,
+        init_tx_hash: Option<TransactionHash>,
+        init_parameter: &[u8],
+        contract_name: &str,
+    ) -> Result<ManifestEntry, Error> {
+        let wasm = std::fs::read(wasm_file)
+            .with_context(|| format!("Could not read the WASM file {wasm_file:?}."))?;
+
+        Ok(ManifestEntry {
+            wasm_file: wasm_file.to_path_buf(),
+            wasm_sha256: hex::encode(Sha256::digest(&wasm)),
+            module_reference,
+            deploy_tx_hash,
+            contract_address,
+            init_tx_hash,
+            init_parameter: hex::encode(init_parameter),
+            init_parameter_file: format!("{contract_name}.init-parameter.bin"),
+        })
+    }
+}
+
+impl DeployManifest {
+    /// A function to write the manifest (`manifest.json`) and the serialized init parameters (one file per contract)
+    /// to the given directory. The directory is created if it does not exist.
+    pub fn write_to_dir(&self, manifest_dir: &Path) -> Result<(), Error> {
+        std::fs::create_dir_all(manifest_dir).with_context(|| {
+            format!("Could not create the manifest directory {manifest_dir:?}.")
+        })?;
+
+        for entry in [&self.registry, &self.staking_bank, &self.umbrella_feeds]
+            .into_iter()
+            .flatten()
+        {
+            let parameter_file = manifest_dir.join(&entry.init_parameter_file);
+            let parameter = hex::decode(&entry.init_parameter)
+                .context("The init parameter of the manifest is not valid hex.")?;
+
+            std::fs::write(&parameter_file, parameter).with_context(|| {
+                format!("Could not write the init parameter file {parameter_file:?}.")
+            })?;
+        }
+
+        let manifest_file = manifest_dir.join(MANIFEST_FILE_NAME);
+
+        write_json_atomically(self, &manifest_file)
+            .with_context(|| format!("Could not write the manifest file {manifest_file:?}."))
+    }
+}
@@ -0,0 +1,287 @@
+use crate::proxy::ProxyConfig;
+use anyhow::{bail, Context, Error};
+use concordium_rust_sdk::v2::{self, BlockIdentifier};
+use std::{
+    io::{BufRead, IsTerminal, Write},
+    path::PathBuf,
+    str::FromStr,
+    time::Duration,
+};
+use tonic::transport::{Certificate, ClientTlsConfig};
+
+/// The word that has to be typed to confirm a mainnet deployment.
+const CONFIRMATION_WORD: &str = "MAINNET";
+
+/// The Concordium network the scripts are run against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Network {
+    Testnet,
+    Mainnet,
+}
+
+impl FromStr for Network {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "testnet" => Ok(Network::Testnet),
+            "mainnet" => Ok(Network::Mainnet),
+            _ => bail!("Unknown network `{s}`. Use `testnet` or `mainnet`."),
+        }
+    }
+}
+
+/// The block that the read-only subcommands query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadBlock {
+    /// The last finalized block.
+    LastFinal,
+    /// The best block, which includes transactions that are not finalized yet.
+    Best,
+}
+
+impl ReadBlock {
+    /// A function that returns the block identifier used in the queries.
+    pub fn block_identifier(self) -> BlockIdentifier {
+        match self {
+            ReadBlock::LastFinal => BlockIdentifier::LastFinal,
+            ReadBlock::Best => BlockIdentifier::Best,
+        }
+    }
+}
+
+impl FromStr for ReadBlock {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "last-final" => Ok(ReadBlock::LastFinal),
+            "best" => Ok(ReadBlock::Best),
+            _ => bail!("Unknown block `{s}`. Use `last-final` or `best`."),
+        }
+    }
+}
+
+/// Checks if the host of the endpoint is a testnet host.
+fn is_testnet_host(url: &v2::Endpoint) -> bool {
+    url.uri()
+        .host()
+        .is_some_and(|host| host.to_lowercase().contains("testnet"))
+}
+
+/// A function that requires an interactive confirmation before any transaction is submitted if
+/// the `--network mainnet` flag is passed or the endpoint host is not a testnet host.
+/// The confirmation is skipped if the `--yes` flag is passed.
+pub fn confirm_network(
+    url: &v2::Endpoint,
+    network: Option<Network>,
+    yes: bool,
+) -> Result<(), Error> {
+    let requires_confirmation = match network {
+        Some(network) => network == Network::Mainnet,
+        None => !is_testnet_host(url),
+    };
+
+    if !requires_confirmation || yes {
+        return Ok(());
+    }
+
+    confirm(
+        &format!("The node {} is not a testnet node.", url.uri()),
+        CONFIRMATION_WORD,
+        "submit transactions to mainnet",
+    )
+}
+
+/// A function that requires the user to type the `expected` input to continue.
+/// Fails if the input differs or if no interactive terminal is available (in that case, the
+/// `--yes` flag has to be passed to skip the confirmation).
+pub fn confirm(message: &str, expected: &str, action: &str) -> Result<(), Error> {
+    let stdin = std::io::stdin();
+
+    if !stdin.is_terminal() {
+        bail!("{message} Pass the `--yes` flag to {action} without an interactive confirmation.");
+    }
+
+    print!("\n{message} Type {expected} to continue: ");
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    stdin
+        .lock()
+        .read_line(&mut input)
+        .context("Could not read the confirmation.")?;
+
+    if input.trim() != expected {
+        bail!("Aborted. No transactions were submitted.");
+    }
+
+    Ok(())
+}
+
+/// The default timeout of the connection to the node and of each request.
+pub const DEFAULT_CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The options used to connect to the node.
+#[derive(Debug, Clone)]
+pub struct ConnectionOptions {
+    /// Path to a PEM file with the root (CA) certificate used to validate the certificate of the node.
+    pub ca_cert: Option<PathBuf>,
+    /// The domain name (SNI) that the certificate of the node is checked against.
+    pub tls_domain: Option<String>,
+    /// The timeout of the connection to the node and of each request to the node.
+    pub connect_timeout: Duration,
+    /// The SOCKS5 proxy that the connection to the node is routed through.
+    pub proxy: Option<ProxyConfig>,
+}
+
+impl Default for ConnectionOptions {
+    fn default() -> Self {
+        ConnectionOptions {
+            ca_cert: None,
+            tls_domain: None,
+            connect_timeout: DEFAULT_CONNECT_TIMEOUT,
+            proxy: None,
+        }
+    }
+}
+
+/// A function that configures the endpoint with the TLS options and the timeouts. If no TLS options are given,
+/// the endpoint is not configured for TLS (e.g. plain HTTP for the testnet node).
+fn configure_endpoint(
+    url: v2::Endpoint,
+    options: &ConnectionOptions,
+) -> Result<v2::Endpoint, Error> {
+    let url = url
+        .connect_timeout(options.connect_timeout)
+        .timeout(options.connect_timeout);
+
+    if options.ca_cert.is_none() && options.tls_domain.is_none() {
+        return Ok(url);
+    }
+
+    let mut tls_config = ClientTlsConfig::new();
+
+    if let Some(ca_cert) = &options.ca_cert {
+        let pem = std::fs::read(ca_cert)
+            .with_context(|| format!("Could not read the CA certificate {ca_cert:?}."))?;
+        tls_config = tls_config.ca_certificate(Certificate::from_pem(pem));
+    }
+
+    if let Some(tls_domain) = &options.tls_domain {
+        tls_config = tls_config.domain_name(tls_domain);
+    }
+
+    url.tls_config(tls_config)
+        .context("Could not configure TLS for the node endpoint.")
+}
+
+/// A function that points the endpoint at a local relay that tunnels the connection through the proxy to the node
+/// (see the `proxy` module). TLS is still negotiated with the node, so the host of the node URL is used as the
+/// TLS domain unless the `--tls-domain` flag is given.
+async fn route_through_proxy(
+    url: v2::Endpoint,
+    proxy: &ProxyConfig,
+    options: &ConnectionOptions,
+) -> Result<(v2::Endpoint, ConnectionOptions), Error> {
+    let uri = url.uri();
+
+    let host = uri
+        .host()
+        .with_context(|| format!("The node URL {uri} has no host."))?
+        .trim_start_matches('[')
+        .trim_end_matches(']')
+        .to_string();
+
+    let port = uri
+        .port_u16()
+        .unwrap_or(if uri.scheme_str() == Some("https") {
+            443
+        } else {
+            80
+        });
+
+    let relay_address = tokio::time::timeout(options.connect_timeout, proxy.start_relay(&host, port))
+        .await
+        .map_err(|_| {
+            anyhow::anyhow!(
+                "could not connect through the proxy {}:{} within {}s (see the `--connect-timeout` flag).",
+                proxy.host,
+                proxy.port,
+                options.connect_timeout.as_secs()
+            )
+        })?
+        .with_context(|| format!("Could not connect to the node {uri} through the proxy."))?;
+
+    let relay_uri = format!(
+        "{}://{relay_address}{}",
+        uri.scheme_str().unwrap_or("http"),
+        uri.path_and_query().map_or("", |path| path.as_str())
+    );
+
+    let relay_url = v2::Endpoint::from_shared(relay_uri)
+        .context("Could not configure the endpoint of the proxy relay.")?;
+
+    let mut options = options.clone();
+
+    if options.ca_cert.is_some() || uri.scheme_str() == Some("https") {
+        options.tls_domain.get_or_insert(host);
+    }
+
+    Ok((relay_url, options))
+}
+
+/// A function that connects to the node at the given endpoint with the given connection options.
+/// Connection errors state whether the TLS handshake or the name resolution failed, or whether the
+/// connection timed out.
+pub async fn connect(url: v2::Endpoint, options: &ConnectionOptions) -> Result<v2::Client, Error> {
+    let uri = url.uri().clone();
+
+    let (url, options) = match &options.proxy {
+        Some(proxy) => route_through_proxy(url, proxy, options).await?,
+        None => (url, options.clone()),
+    };
+
+    let url = configure_endpoint(url, &options)?;
+
+    let timeout = options.connect_timeout;
+
+    let timed_out = || {
+        anyhow::anyhow!(
+            "could not connect to node at {uri} within {}s (see the `--connect-timeout` flag).",
+            timeout.as_secs()
+        )
+    };
+
+    let client = tokio::time::timeout(timeout, v2::Client::new(url))
+        .await
+        .map_err(|_| timed_out())?;
+
+    client.map_err(|e| {
+        let error = Error::from(e);
+
+        let causes = error
+            .chain()
+            .map(|cause| cause.to_string().to_lowercase())
+            .collect::<Vec<_>>()
+            .join(": ");
+
+        if causes.contains("timed out") || causes.contains("elapsed") {
+            timed_out()
+        } else if causes.contains("dns") || causes.contains("lookup address") {
+            error.context(format!(
+                "Could not connect to the node {uri}: name resolution failed."
+            ))
+        } else if causes.contains("certificate")
+            || causes.contains("tls")
+            || causes.contains("handshake")
+        {
+            error.context(format!(
+                "Could not connect to the node {uri}: TLS handshake failed (check the `--ca-cert` \
+                 and `--tls-domain` flags)."
+            ))
+        } else {
+            error.context(format!("Could not connect to the node {uri}."))
+        }
+    })
+}
@@ -0,0 +1,125 @@
+use anyhow::{Context, Error};
+use concordium_rust_sdk::{
+    common::types::Amount,
+    id::types::AccountAddress,
+    types::{
+        smart_contracts::{InstanceInfo, ModuleReference, OwnedReceiveName},
+        ContractAddress,
+    },
+};
+use serde::{Deserialize, Serialize};
+use std::{io::Write, path::Path};
+
+/// The addresses and module references of a deployed umbrella oracle protocol.
+/// Written by the `deploy` command when the `--output-json` flag is provided. The contracts that were neither
+/// deployed nor given as existing contracts (see the `--only` flag) are omitted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployedAddresses {
+    /// Contract address of the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<ContractAddress>,
+    /// Contract address of the staking_bank.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staking_bank: Option<ContractAddress>,
+    /// Contract address of the umbrella_feeds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub umbrella_feeds: Option<ContractAddress>,
+    /// Module references of the deployed contracts.
+    pub module_references: DeployedModuleReferences,
+}
+
+/// The module references of a deployed umbrella oracle protocol.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeployedModuleReferences {
+    /// Module reference of the registry.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<ModuleReference>,
+    /// Module reference of the staking_bank.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub staking_bank: Option<ModuleReference>,
+    /// Module reference of the umbrella_feeds.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub umbrella_feeds: Option<ModuleReference>,
+}
+
+impl DeployedAddresses {
+    /// A function to write the addresses as JSON to the given file (atomically).
+    pub fn write_to_file(&self, output_file: &Path) -> Result<(), Error> {
+        write_json_atomically(self, output_file)
+    }
+}
+
+/// The information about a contract instance printed by the `contract-info` subcommand (the format of the
+/// `--json` output).
+#[derive(Debug, Clone, Serialize)]
+pub struct ContractInfo {
+    /// The address of the instance.
+    pub address: ContractAddress,
+    /// The name of the contract.
+    pub name: String,
+    /// The account that initialized the instance.
+    pub owner: AccountAddress,
+    /// The module reference of the instance.
+    pub source_module: ModuleReference,
+    /// The CCD balance of the instance.
+    pub amount: Amount,
+    /// The receive names of the entry points of the instance.
+    pub receive_names: Vec<OwnedReceiveName>,
+}
+
+impl ContractInfo {
+    /// A function to collect the information about the instance at the `address` from its queried instance info.
+    pub fn new(address: ContractAddress, info: &InstanceInfo) -> ContractInfo {
+        let owner = match info {
+            InstanceInfo::V0 { owner, .. } => *owner,
+            InstanceInfo::V1 { owner, .. } => *owner,
+        };
+
+        ContractInfo {
+            address,
+            name: info.name().as_contract_name().contract_name().to_string(),
+            owner,
+            source_module: info.source_module(),
+            amount: info.amount(),
+            receive_names: info.entrypoints().iter().cloned().collect(),
+        }
+    }
+}
+
+/// A function to write a value as JSON to the given file.
+/// The JSON is written to a temporary file in the same directory first which is then renamed,
+/// so that a partially written file is never observed at the given path.
+pub fn write_json_atomically<T: Serialize>(value: &T, output_file: &Path) -> Result<(), Error> {
+    let json = serde_json::to_string_pretty(value).context("Could not serialize the value.")?;
+
+    let dir = match output_file.parent() {
+        Some(dir) if !dir.as_os_str().is_empty() => dir,
+        _ => Path::new("."),
+    };
+
+    let file_name = output_file
+        .file_name()
+        .with_context(|| format!("Invalid output file {output_file:?}."))?;
+
+    let mut tmp_file_name = file_name.to_os_string();
+    tmp_file_name.push(format!(".{}.tmp", std::process::id()));
+    let tmp_file = dir.join(tmp_file_name);
+
+    let write_result = (|| -> Result<(), Error> {
+        let mut file = std::fs::File::create(&tmp_file)
+            .with_context(|| format!("Could not create the file {tmp_file:?}."))?;
+        file.write_all(json.as_bytes())
+            .with_context(|| format!("Could not write the file {tmp_file:?}."))?;
+        file.sync_all()
+            .with_context(|| format!("Could not sync the file {tmp_file:?}."))?;
+        std::fs::rename(&tmp_file, output_file)
+            .with_context(|| format!("Could not move the file {tmp_file:?} to {output_file:?}."))
+    })();
+
+    if write_result.is_err() {
+        // Cleaning up the temporary file. The original error is more relevant to the user.
+        let _ = std::fs::remove_file(&tmp_file);
+    }
+
+    write_result
+}
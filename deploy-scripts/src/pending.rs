@@ -0,0 +1,49 @@
+use crate::output::write_json_atomically;
+use anyhow::{Context, Error};
+use concordium_rust_sdk::types::{hashes::TransactionHash, transactions::EncodedPayload};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::{collections::BTreeMap, path::Path};
+
+/// The sent transactions whose finalization could not be awaited (e.g. because of the finalization timeout).
+/// The file is written by the `Deployer` if the `--retry-finalization` flag is given, so that the next run
+/// re-queries the outcome of a pending transaction instead of sending the same transaction again.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct PendingTransactions {
+    /// The transaction hashes of the pending transactions, keyed by the SHA2-256 digest (hex) of their payload.
+    /// The payload does not contain the nonce or the expiry, so that it is the same when the transaction is
+    /// built again by a later run.
+    pub transactions: BTreeMap<String, TransactionHash>,
+}
+
+impl PendingTransactions {
+    /// A function to load the pending transactions from a file.
+    /// If the file does not exist, no transactions are pending.
+    pub fn from_file(pending_file: &Path) -> Result<PendingTransactions, Error> {
+        if !pending_file.exists() {
+            return Ok(PendingTransactions::default());
+        }
+
+        let content = std::fs::read_to_string(pending_file).with_context(|| {
+            format!("Could not read the pending transactions file {pending_file:?}.")
+        })?;
+
+        serde_json::from_str(&content).with_context(|| {
+            format!("Could not parse the pending transactions file {pending_file:?}.")
+        })
+    }
+
+    /// A function to write the pending transactions to a file (atomically).
+    pub fn write_to_file(&self, pending_file: &Path) -> Result<(), Error> {
+        write_json_atomically(self, pending_file).with_context(|| {
+            format!("Could not write the pending transactions file {pending_file:?}.")
+        })
+    }
+
+    /// A function that returns the key of a transaction with the given payload.
+    pub fn key(payload: &EncodedPayload) -> String {
+        let payload: &Vec<u8> = payload.as_ref();
+
+        hex::encode(Sha256::digest(payload))
+    }
+}
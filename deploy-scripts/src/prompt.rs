@@ -0,0 +1,69 @@
+use anyhow::{bail, Context, Error};
+use std::io::{BufRead, IsTerminal, Write};
+
+/// A function that fails if no interactive terminal is available for the prompts of the `action`.
+pub fn ensure_interactive(action: &str) -> Result<(), Error> {
+    if !std::io::stdin().is_terminal() {
+        bail!("The {action} requires an interactive terminal.");
+    }
+
+    Ok(())
+}
+
+/// A function that prompts for one line of input. The input is trimmed. If the input is empty, the
+/// `default` is returned (an empty string if there is no default).
+pub fn prompt(question: &str, default: Option<&str>) -> Result<String, Error> {
+    match default {
+        Some(default) => print!("{question} [{default}]: "),
+        None => print!("{question}: "),
+    }
+    std::io::stdout().flush()?;
+
+    let mut input = String::new();
+    let read = std::io::stdin()
+        .lock()
+        .read_line(&mut input)
+        .context("Could not read the input.")?;
+
+    if read == 0 {
+        bail!("Aborted. No transactions were submitted.");
+    }
+
+    let input = input.trim();
+
+    if input.is_empty() {
+        Ok(default.unwrap_or_default().to_string())
+    } else {
+        Ok(input.to_string())
+    }
+}
+
+/// A function that prompts until the input is accepted by `parse`. The reason why an input is rejected
+/// is printed before the question is repeated.
+pub fn prompt_parsed<T>(
+    question: &str,
+    default: Option<&str>,
+    parse: impl Fn(&str) -> Result<T, Error>,
+) -> Result<T, Error> {
+    loop {
+        let input = prompt(question, default)?;
+
+        match parse(&input) {
+            Ok(value) => return Ok(value),
+            Err(error) => println!("Invalid input: {error:#}"),
+        }
+    }
+}
+
+/// A function that prompts for a yes/no answer.
+pub fn prompt_yes_no(question: &str, default: bool) -> Result<bool, Error> {
+    prompt_parsed(
+        question,
+        Some(if default { "y" } else { "n" }),
+        |input| match input.to_lowercase().as_str() {
+            "y" | "yes" => Ok(true),
+            "n" | "no" => Ok(false),
+            _ => bail!("Answer `y` or `n`."),
+        },
+    )
+}
@@ -0,0 +1,97 @@
+use anyhow::Error;
+use async_trait::async_trait;
+use concordium_rust_sdk::{
+    common::types::{CredentialIndex, KeyIndex, TransactionSignature},
+    id::types::AccountAddress,
+    types::{
+        hashes::TransactionSignHash,
+        transactions::{
+            self, construct::PreAccountTransaction, AccountTransaction, EncodedPayload,
+            ExactSizeTransactionSigner,
+        },
+        WalletAccount,
+    },
+};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+};
+
+/// A signer of the transactions sent by the `Deployer`.
+///
+/// The key material is kept behind this trait, so that the keys can be stored outside of the deployment
+/// scripts, e.g. in a hardware security module (HSM) or a remote signing service. The exported wallet file
+/// ([`WalletAccount`]) is the default implementation. Signing is async to accommodate network-backed signers.
+#[async_trait]
+pub trait TransactionSigner: Debug + Send + Sync {
+    /// The address of the account that sends the transactions.
+    fn address(&self) -> AccountAddress;
+
+    /// The number of signatures that the signer produces. It has to match the signatures returned by
+    /// `sign_transaction_hash`, since the transaction costs depend on it.
+    fn num_keys(&self) -> u32;
+
+    /// The credential and key indices of the keys that the signer signs with, if known. The `Deployer` checks
+    /// them against the signature thresholds of the account on chain before it sends the first transaction.
+    /// Signers that do not know their keys return `None` (the default), in which case the check is skipped.
+    fn key_indices(&self) -> Option<BTreeMap<CredentialIndex, BTreeSet<KeyIndex>>> {
+        None
+    }
+
+    /// Signs the given transaction hash and returns the signatures.
+    async fn sign_transaction_hash(
+        &self,
+        hash_to_sign: &TransactionSignHash,
+    ) -> Result<TransactionSignature, Error>;
+}
+
+#[async_trait]
+impl TransactionSigner for WalletAccount {
+    fn address(&self) -> AccountAddress {
+        self.address
+    }
+
+    fn num_keys(&self) -> u32 {
+        ExactSizeTransactionSigner::num_keys(self)
+    }
+
+    fn key_indices(&self) -> Option<BTreeMap<CredentialIndex, BTreeSet<KeyIndex>>> {
+        Some(
+            self.keys
+                .keys
+                .iter()
+                .map(|(credential_index, credential)| {
+                    (*credential_index, credential.keys.keys().copied().collect())
+                })
+                .collect(),
+        )
+    }
+
+    async fn sign_transaction_hash(
+        &self,
+        hash_to_sign: &TransactionSignHash,
+    ) -> Result<TransactionSignature, Error> {
+        Ok(transactions::TransactionSigner::sign_transaction_hash(
+            self,
+            hash_to_sign,
+        ))
+    }
+}
+
+/// A function that signs the prepared transaction with the given signer.
+pub async fn sign_transaction(
+    signer: &dyn TransactionSigner,
+    transaction: PreAccountTransaction,
+) -> Result<transactions::BlockItem<EncodedPayload>, Error> {
+    let signature = signer
+        .sign_transaction_hash(&transaction.hash_to_sign)
+        .await?;
+
+    Ok(transactions::BlockItem::AccountTransaction(
+        AccountTransaction {
+            signature,
+            header: transaction.header,
+            payload: transaction.encoded,
+        },
+    ))
+}
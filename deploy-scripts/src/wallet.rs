@@ -0,0 +1,242 @@
+use crate::checksum::{ensure_file_sha256, Sha256Digest};
+use anyhow::{bail, Context, Error};
+use concordium_rust_sdk::{
+    common::encryption::{decrypt, EncryptedData, Password},
+    id::types::CredentialData,
+    types::WalletAccount,
+};
+use std::{
+    io::IsTerminal,
+    path::{Path, PathBuf},
+};
+use tracing::debug;
+use zeroize::Zeroize;
+
+/// A function to read the account keys from a file exported from the wallet.
+///
+/// Both the plaintext export and the password-protected (encrypted) export are supported. The format is
+/// detected automatically. The password of an encrypted export is read from the `password_file` if
+/// provided, otherwise it is prompted for interactively. The keys are decrypted in memory only and are
+/// never written to disk.
+pub fn read_wallet_account(
+    wallet_account_file: &Path,
+    password_file: Option<&Path>,
+) -> Result<WalletAccount, Error> {
+    let content = std::fs::read_to_string(wallet_account_file)
+        .with_context(|| format!("Unable to read wallet file {wallet_account_file:?}."))?;
+
+    let encrypted_data = match serde_json::from_str::<EncryptedData>(&content) {
+        Ok(encrypted_data) => encrypted_data,
+        // Not an encrypted export. Reading the file as a plaintext export instead.
+        Err(_) => {
+            return WalletAccount::from_json_str(&content).context("Unable to read wallet file.")
+        }
+    };
+
+    let mut password = read_password(wallet_account_file, password_file)?;
+
+    let decrypted = decrypt(&Password::from(password.clone()), &encrypted_data);
+
+    password.zeroize();
+
+    let mut decrypted = decrypted.map_err(|_| {
+        anyhow::anyhow!(
+            "Unable to decrypt the wallet file {wallet_account_file:?}. Check the password."
+        )
+    })?;
+
+    let wallet_account = std::str::from_utf8(&decrypted)
+        .context("The decrypted wallet file is not valid UTF-8.")
+        .and_then(|decrypted| {
+            WalletAccount::from_json_str(decrypted)
+                .context("Unable to read the decrypted wallet file.")
+        });
+
+    decrypted.zeroize();
+
+    wallet_account
+}
+
+/// A function to read the account keys of a multi-signature account from several files exported from the wallet
+/// (e.g. one file per key holder) and to combine them into one account, so that the transactions are signed with
+/// all keys.
+///
+/// A path can also be a directory, in which case all `.export` and `.json` files in the directory are read. All
+/// files have to belong to the same account. Each file is read as in `read_wallet_account`. If digests are
+/// expected (the `--account-sha256` flag), the SHA2-256 digest of each file has to be one of them.
+pub fn read_wallet_accounts(
+    wallet_account_files: &[PathBuf],
+    password_file: Option<&Path>,
+    expected_sha256: &[Sha256Digest],
+) -> Result<WalletAccount, Error> {
+    let mut files = Vec::new();
+
+    for path in wallet_account_files {
+        if path.is_dir() {
+            let mut entries = std::fs::read_dir(path)
+                .with_context(|| format!("Unable to read the wallet directory {path:?}."))?
+                .map(|entry| entry.map(|entry| entry.path()))
+                .collect::<Result<Vec<_>, _>>()
+                .with_context(|| format!("Unable to read the wallet directory {path:?}."))?;
+
+            entries.retain(|entry| {
+                entry.is_file()
+                    && entry
+                        .extension()
+                        .is_some_and(|extension| extension == "export" || extension == "json")
+            });
+
+            if entries.is_empty() {
+                bail!("The wallet directory {path:?} contains no `.export` or `.json` files.");
+            }
+
+            entries.sort();
+            files.extend(entries);
+        } else {
+            files.push(path.clone());
+        }
+    }
+
+    let Some((first_file, other_files)) = files.split_first() else {
+        bail!("Missing the `--account` flag.");
+    };
+
+    for file in &files {
+        ensure_file_sha256(file, expected_sha256, "key file")?;
+    }
+
+    let mut wallet_account = read_wallet_account(first_file, password_file)?;
+
+    for file in other_files {
+        let other = read_wallet_account(file, password_file)?;
+
+        if other.address != wallet_account.address {
+            bail!(
+                "The wallet files {first_file:?} and {file:?} belong to different accounts ({} and {}).",
+                wallet_account.address,
+                other.address
+            );
+        }
+
+        for (credential_index, credential) in other.keys.keys {
+            wallet_account
+                .keys
+                .keys
+                .entry(credential_index)
+                .or_insert_with(|| CredentialData {
+                    keys: Default::default(),
+                    threshold: credential.threshold,
+                })
+                .keys
+                .extend(credential.keys);
+        }
+    }
+
+    Ok(wallet_account)
+}
+
+/// The credential and the account key of a wallet export that sign the transactions (the `--credential-index`
+/// and `--account-index` flags). If an index is `None`, all credentials (or all keys of the selected
+/// credentials) are used. The key files are checked against the pinned digests (the `--account-sha256` flag)
+/// before they are read.
+#[derive(Debug, Clone, Default)]
+pub struct KeySelection {
+    /// The index of the credential.
+    pub credential_index: Option<u8>,
+    /// The index of the account key within the selected credentials.
+    pub key_index: Option<u8>,
+    /// The SHA2-256 digests that the key files have to match. No check if empty.
+    pub account_sha256: Vec<Sha256Digest>,
+}
+
+impl KeySelection {
+    /// A function to remove all keys from the wallet account that are not selected. Fails if the wallet
+    /// account has no credential or key with the selected index. The selected keys are logged at the debug
+    /// level (`--verbose`).
+    pub fn apply(self, mut wallet_account: WalletAccount) -> Result<WalletAccount, Error> {
+        let credentials = &mut wallet_account.keys.keys;
+
+        if let Some(index) = self.credential_index {
+            let available = credentials
+                .keys()
+                .map(|credential_index| credential_index.index.to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            credentials.retain(|credential_index, _| credential_index.index == index);
+
+            if credentials.is_empty() {
+                bail!(
+                    "The `--credential-index` {index} is out of range. The wallet export of the account {} \
+                     has the credentials: {available}.",
+                    wallet_account.address
+                );
+            }
+        }
+
+        if let Some(index) = self.key_index {
+            for (credential_index, credential) in credentials.iter_mut() {
+                let available = credential
+                    .keys
+                    .keys()
+                    .map(|key_index| key_index.0.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                credential.keys.retain(|key_index, _| key_index.0 == index);
+
+                if credential.keys.is_empty() {
+                    bail!(
+                        "The `--account-index` {index} is out of range. The credential {} of the account {} \
+                         has the keys: {available}.",
+                        credential_index.index,
+                        wallet_account.address
+                    );
+                }
+            }
+        }
+
+        for (credential_index, credential) in credentials.iter() {
+            for (key_index, key) in &credential.keys {
+                debug!(
+                    "Signing with the key {} (public key {}) of the credential {} of the account {}.",
+                    key_index.0,
+                    hex::encode(key.public.as_bytes()),
+                    credential_index.index,
+                    wallet_account.address
+                );
+            }
+        }
+
+        Ok(wallet_account)
+    }
+}
+
+/// A function to read the password of an encrypted wallet file. The password is read from the
+/// `password_file` (trailing newlines are removed) if provided, otherwise it is prompted for interactively.
+fn read_password(
+    wallet_account_file: &Path,
+    password_file: Option<&Path>,
+) -> Result<String, Error> {
+    if let Some(password_file) = password_file {
+        let mut password = std::fs::read_to_string(password_file)
+            .with_context(|| format!("Unable to read the password file {password_file:?}."))?;
+
+        let len = password.trim_end_matches(['\r', '\n']).len();
+        password.truncate(len);
+
+        return Ok(password);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        bail!(
+            "The wallet file {wallet_account_file:?} is encrypted. Pass the `--password-file` flag to \
+             decrypt it without an interactive prompt."
+        );
+    }
+
+    rpassword::prompt_password(format!(
+        "The wallet file {wallet_account_file:?} is encrypted. Enter the password: "
+    ))
+    .context("Could not read the password.")
+}
@@ -3,8 +3,25 @@
 use concordium_std::*;
 use core::fmt::Debug;
 
+/// A call that the contract makes when its `verifyValidators` entry point is invoked.
+/// Used in the integration tests to simulate a staking bank that re-enters the calling contract.
+#[derive(Serial, Deserial, SchemaType)]
+struct ReentrantCall {
+    /// The contract to call.
+    contract: ContractAddress,
+    /// The entry point to call.
+    entrypoint: OwnedEntrypointName,
+    /// The serialized parameter of the call.
+    parameter: Vec<u8>,
+}
+
 #[derive(Serial, Deserial)]
-struct State {}
+struct State {
+    /// The call made by the `verifyValidators` entry point (if any).
+    reentrant_call: Option<ReentrantCall>,
+    /// The reject code of the last call made by the `verifyValidators` entry point (`None` if it succeeded).
+    last_reject_code: Option<i32>,
+}
 
 #[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
 enum CustomContractError {
@@ -18,7 +35,10 @@ fn init<S: HasStateApi>(
     _ctx: &impl HasInitContext,
     _state_builder: &mut StateBuilder<S>,
 ) -> InitResult<State> {
-    Ok(State {})
+    Ok(State {
+        reentrant_call: None,
+        last_reject_code: None,
+    })
 }
 
 #[receive(contract = "dummy_contract", name = "getName", return_value = "String")]
@@ -28,3 +48,68 @@ fn get_name<S: HasStateApi>(
 ) -> ReceiveResult<&'static str> {
     Ok("MyName")
 }
+
+/// Sets the call made by the `verifyValidators` entry point.
+#[receive(
+    contract = "dummy_contract",
+    name = "setReentrantCall",
+    parameter = "ReentrantCall",
+    error = "CustomContractError",
+    mutable
+)]
+fn set_reentrant_call<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    host.state_mut().reentrant_call = Some(ctx.parameter_cursor().get()?);
+
+    Ok(())
+}
+
+/// Makes the call set with the `setReentrantCall` entry point, records its reject code, and returns `true`
+/// (i.e. all keys are reported as validators).
+#[receive(
+    contract = "dummy_contract",
+    name = "verifyValidators",
+    return_value = "bool",
+    mutable
+)]
+fn verify_validators<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    // The call is removed from the state while it is made, so that a nested invoke of this entry point does not repeat it.
+    if let Some(call) = host.state_mut().reentrant_call.take() {
+        let result = host.invoke_contract_raw(
+            &call.contract,
+            Parameter::new_unchecked(&call.parameter),
+            call.entrypoint.as_entrypoint_name(),
+            Amount::zero(),
+        );
+
+        let last_reject_code = match result {
+            Ok(_) => None,
+            Err(CallContractError::LogicReject { reason, .. }) => Some(reason),
+            // The call failed without a reject by the contract (e.g. a missing contract or entry point).
+            Err(_) => Some(i32::MIN),
+        };
+
+        host.state_mut().reentrant_call = Some(call);
+        host.state_mut().last_reject_code = last_reject_code;
+    }
+
+    Ok(true)
+}
+
+/// View function that returns the reject code of the last call made by the `verifyValidators` entry point.
+#[receive(
+    contract = "dummy_contract",
+    name = "getLastRejectCode",
+    return_value = "Option<i32>"
+)]
+fn get_last_reject_code<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State, StateApiType = S>,
+) -> ReceiveResult<Option<i32>> {
+    Ok(host.state().last_reject_code)
+}
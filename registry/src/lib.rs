@@ -6,24 +6,90 @@
 //! This contract has an owner.
 //! The owner can:
 //! - Register contracts into this registry with the `importAddresses` and the `importContracts` entry points.
+//! - Register several contracts together in one all-or-nothing step with the `batchUpdate` entry point (e.g. when swapping the `StakingBank`).
 //! - Natively upgrade the `UmbrellaFeeds` contract via this registry contract by invoking the `atomicUpdate` entry point.
 //! - Override contract addresses registered (e.g. in case they don't have the entry points `upgradeNatively` implemented) by invoking the `importAddresses` and the `importContracts` entry points.
+//! - Remove contracts (e.g. deprecated contracts) from this registry with the `removeContract` entry point.
+//! - Upgrade this registry instance to a new module with the `upgrade` entry point (optionally calling a migration function).
+//! - Transfer the ownership in two steps. The owner proposes a new owner with the `transferOwnership` entry point and the proposed owner accepts the ownership with the `acceptOwnership` entry point.
+//!
 //! ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use the `atomicUpdate` function to natively upgrade the `UmbrellaFeeds` contract.
 use concordium_std::*;
 use core::fmt::Debug;
 
-#[derive(Serial, DeserialWithState)]
+#[derive(Serial)]
 #[concordium(state_parameter = "S")]
 struct State<S: HasStateApi> {
     // The owner of this contract. It can register/override/atomically upgrade contract addresses in this registry.
     owner: Option<Address>,
     // Mapping from key to contract address. The key/name of a contract is its string name.
     registry: StateMap<String, ContractAddress, S>,
+    // The proposed new owner of this contract. It can accept the ownership with the `acceptOwnership` entry point.
+    pending_owner: Option<AccountAddress>,
+    // The code version (e.g. the git commit) of the contracts this instance was built from, set at init.
+    code_version: String,
+}
+
+/// A reader that yields the byte read ahead by `get_or_default_at_end` before the remaining bytes of the source.
+struct ReadAhead<'a, R> {
+    byte: Option<u8>,
+    source: &'a mut R,
+}
+
+impl<R: Read> Read for ReadAhead<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> ParseResult<usize> {
+        match (self.byte, buf.first_mut()) {
+            (Some(byte), Some(first)) => {
+                *first = byte;
+                self.byte = None;
+                Ok(1)
+            }
+            _ => self.source.read(buf),
+        }
+    }
+}
+
+/// Helper function that reads a field that was added at the end of the state (or the init parameter) later. The `default` is only used if the
+/// source is at its end (i.e. the field is missing). Any other parse error (e.g. a truncated or malformed field) is
+/// returned, so that the following fields are not read from a wrong offset.
+fn get_or_default_at_end<T: Deserial, R: Read>(
+    source: &mut R,
+    default: impl FnOnce() -> T,
+) -> ParseResult<T> {
+    let mut byte = [0u8; 1];
+
+    if source.read(&mut byte)? == 0 {
+        return Ok(default());
+    }
+
+    T::deserial(&mut ReadAhead {
+        byte: Some(byte[0]),
+        source,
+    })
+}
+
+/// The state is deserialized manually so that the state of instances created before the
+/// `pending_owner`/`code_version` fields were added (the fields are missing at the end of the state) can still be read.
+/// In that case, no pending owner and an empty code version are set. The fields are written with the next state update.
+impl<S: HasStateApi> DeserialWithState<S> for State<S> {
+    fn deserial_with_state<R: Read>(state: &S, source: &mut R) -> ParseResult<Self> {
+        let owner = source.get()?;
+        let registry = StateMap::deserial_with_state(state, source)?;
+        let pending_owner = get_or_default_at_end(source, || None)?;
+        let code_version = get_or_default_at_end(source, Default::default)?;
+
+        Ok(State {
+            owner,
+            registry,
+            pending_owner,
+            code_version,
+        })
+    }
 }
 
 /// All smart contract errors.
-#[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
-enum CustomContractError {
+#[derive(Debug, PartialEq, Eq, Reject, Serial, Deserial, SchemaType)]
+pub enum CustomContractError {
     /// Failed to parse the parameter.
     #[from(ParseError)]
     ParseParams, // -1
@@ -39,6 +105,16 @@ enum CustomContractError {
     InvokeContractError, // -6
     /// Failed because this contract has no owner anymore (ownership was renounced).
     NoOwner, // -7
+    /// Failed because no ownership transfer is pending.
+    NoPendingOwner, // -8
+    /// Failed because a key/name appears more than once in the parameter.
+    DuplicateName, // -9
+    /// Failed to upgrade because the new module does not exist.
+    FailedUpgradeMissingModule, // -10
+    /// Failed to upgrade because the new module does not contain a contract with a matching name.
+    FailedUpgradeMissingContract, // -11
+    /// Failed to upgrade because the smart contract version of the new module is not supported.
+    FailedUpgradeUnsupportedModuleVersion, // -12
 }
 
 /// Mapping errors related to logging to CustomContractError.
@@ -58,16 +134,47 @@ impl<T> From<CallContractError<T>> for CustomContractError {
     }
 }
 
+/// Mapping errors related to contract upgrades to CustomContractError.
+impl From<UpgradeError> for CustomContractError {
+    #[inline(always)]
+    fn from(ue: UpgradeError) -> Self {
+        match ue {
+            UpgradeError::MissingModule => Self::FailedUpgradeMissingModule,
+            UpgradeError::MissingContract => Self::FailedUpgradeMissingContract,
+            UpgradeError::UnsupportedModuleVersion => Self::FailedUpgradeUnsupportedModuleVersion,
+        }
+    }
+}
+
 /// Tagged events to be serialized for the event log.
-#[derive(Debug, Serial, SchemaType)]
+/// The tag of each event is stable. New events are added with new tags, so that off-chain parsers
+/// (e.g. indexers) keep working.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
 #[concordium(repr(u8))]
-enum Event {
+pub enum Event {
     /// The event tracks whenever a new contract address gets registered/atomically upgraded in this registry (potentially replacing an old contract address).
     #[concordium(tag = 0)]
     LogRegistered(LogRegisteredEvent),
     /// The event tracks whenever the contract ownership gets transferred.
     #[concordium(tag = 1)]
     OwnershipTransferred(OwnershipTransferredEvent),
+    /// The event tracks whenever the owner proposes a new owner (the first step of the ownership transfer).
+    #[concordium(tag = 2)]
+    OwnershipTransferStarted(OwnershipTransferStartedEvent),
+    /// The event tracks whenever a contract address registered under a key/name gets replaced by a different contract address.
+    /// It is logged in addition to the `LogRegistered` event.
+    #[concordium(tag = 3)]
+    AddressUpdated(AddressUpdatedEvent),
+    /// The event tracks whenever a contract address gets removed from this registry.
+    #[concordium(tag = 4)]
+    ContractRemoved(ContractRemovedEvent),
+    /// The event tracks whenever several contract addresses get registered together with the `batchUpdate` entry point.
+    /// It lists all changes of the batch and is logged instead of the `LogRegistered` and `AddressUpdated` events.
+    #[concordium(tag = 5)]
+    BatchUpdated(BatchUpdatedEvent),
+    /// The event tracks whenever the owner cancels a pending ownership transfer.
+    #[concordium(tag = 6)]
+    OwnershipTransferCanceled(OwnershipTransferCanceledEvent),
 }
 
 /// The LogRegisteredEvent is logged when a new contract address gets registered/atomically upgraded in this registry (potentially replacing an old contract address).
@@ -79,6 +186,45 @@ pub struct LogRegisteredEvent {
     pub name: String,
 }
 
+/// The AddressUpdatedEvent is logged when a contract address registered under a key/name gets replaced by a different contract address.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct AddressUpdatedEvent {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The old contract address that got replaced.
+    pub old_address: ContractAddress,
+    /// The new contract address that got registered.
+    pub new_address: ContractAddress,
+}
+
+/// The ContractRemovedEvent is logged when a contract address gets removed from this registry.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ContractRemovedEvent {
+    /// The key/name of the removed contract.
+    pub name: String,
+    /// The contract address that got removed.
+    pub address: ContractAddress,
+}
+
+/// A single change of the `BatchUpdatedEvent`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct BatchUpdateChange {
+    /// The key/name of a contract.
+    pub name: String,
+    /// The contract address registered under the key/name before (`None` if the key/name was not registered).
+    pub old_address: Option<ContractAddress>,
+    /// The new contract address that got registered.
+    pub new_address: ContractAddress,
+}
+
+/// The BatchUpdatedEvent is logged when several contract addresses get registered together with the `batchUpdate` entry point.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct BatchUpdatedEvent {
+    /// All changes of the batch (in the order of the parameter).
+    #[concordium(size_length = 2)]
+    pub changes: Vec<BatchUpdateChange>,
+}
+
 /// The OwnershipTransferredEvent is logged when the contract ownership gets transferred.
 #[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
 pub struct OwnershipTransferredEvent {
@@ -88,13 +234,74 @@ pub struct OwnershipTransferredEvent {
     pub new_owner: Option<Address>,
 }
 
+/// The OwnershipTransferStartedEvent is logged when the owner proposes a new owner.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OwnershipTransferStartedEvent {
+    /// The current owner's address.
+    pub previous_owner: Option<Address>,
+    /// The proposed new owner's address.
+    pub new_owner: AccountAddress,
+}
+
+/// The OwnershipTransferCanceledEvent is logged when the owner cancels a pending ownership transfer.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct OwnershipTransferCanceledEvent {
+    /// The current owner's address.
+    pub owner: Option<Address>,
+    /// The proposed new owner's address whose proposal was canceled.
+    pub canceled_owner: AccountAddress,
+}
+
+/// Logs an `AddressUpdated` event if a different contract address was registered under the key/name before.
+fn log_address_updated(
+    logger: &mut impl HasLogger,
+    name: String,
+    old_address: Option<ContractAddress>,
+    new_address: ContractAddress,
+) -> Result<(), CustomContractError> {
+    if let Some(old_address) = old_address {
+        if old_address != new_address {
+            logger.log(&Event::AddressUpdated(AddressUpdatedEvent {
+                name,
+                old_address,
+                new_address,
+            }))?;
+        }
+    }
+    Ok(())
+}
+
+/// The parameter type for the contract init function.
+#[derive(Debug, Serial, SchemaType)]
+pub struct InitParamsRegistry {
+    /// The code version (e.g. the git commit) of the contracts. Defaults to an empty string if omitted (e.g. an
+    /// empty parameter).
+    pub code_version: String,
+}
+
+/// Custom deserialization of the init parameter, so that the `code_version` field can be omitted.
+impl Deserial for InitParamsRegistry {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        Ok(InitParamsRegistry {
+            code_version: get_or_default_at_end(source, Default::default)?,
+        })
+    }
+}
+
 /// The init function that creates a new registry smart contract.
-#[init(contract = "registry", event = "Event", enable_logger)]
+#[init(
+    contract = "registry",
+    parameter = "InitParamsRegistry",
+    event = "Event",
+    enable_logger
+)]
 fn init<S: HasStateApi>(
     ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
     logger: &mut impl HasLogger,
 ) -> InitResult<State<S>> {
+    let param: InitParamsRegistry = ctx.parameter_cursor().get()?;
+
     let owner = Address::from(ctx.init_origin());
 
     // Log OwnershipTransferred event
@@ -106,9 +313,25 @@ fn init<S: HasStateApi>(
     Ok(State {
         registry: state_builder.new_map(),
         owner: Some(owner),
+        pending_owner: None,
+        code_version: param.code_version,
     })
 }
 
+/// View function that returns the code version (e.g. the git commit) of the contracts this instance was built from.
+/// It is empty if the instance was initialized without a code version.
+#[receive(
+    contract = "registry",
+    name = "getCodeVersion",
+    return_value = "String"
+)]
+fn get_code_version<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<String> {
+    Ok(host.state().code_version.clone())
+}
+
 /// Part of the parameter type for the contract function `importAddresses`.
 #[derive(Serialize, SchemaType)]
 pub struct ImportAddressesParam {
@@ -152,20 +375,79 @@ fn import_addresses<S: HasStateApi>(
     let import_contracts: ImportAddressesParams = ctx.parameter_cursor().get()?;
 
     for entry in import_contracts.entries {
-        host.state_mut()
+        let old_address = host
+            .state_mut()
             .registry
             .insert(entry.name.clone(), entry.destination);
 
         // Log LogRegistered event
         logger.log(&Event::LogRegistered(LogRegisteredEvent {
-            name: entry.name,
+            name: entry.name.clone(),
             destination: entry.destination,
         }))?;
+
+        log_address_updated(logger, entry.name, old_address, entry.destination)?;
     }
 
     Ok(())
 }
 
+/// The owner can register several contract addresses (overriding old addresses) in one all-or-nothing step by providing the new contract addresses and their keys/names.
+/// Either all entries are registered or the transaction is rejected and the registry stays unchanged (e.g. if a key/name appears more than once in the parameter).
+/// A single `BatchUpdated` event listing all changes is logged. Since an event is limited to 512 bytes, a batch with too many (or too long) names is rejected with `LogMalformed`.
+/// The entry point is used when dependent contracts are swapped together (e.g. when the `StakingBank` is replaced), so that readers never observe a partially updated registry.
+/// ATTENTION: If you want to upgrade the `UmbrellaFeeds` contract, use the `atomicUpdate` function to natively upgrade the `UmbrellaFeeds` contract.
+#[receive(
+    contract = "registry",
+    name = "batchUpdate",
+    parameter = "ImportAddressesParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn batch_update<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let batch: ImportAddressesParams = ctx.parameter_cursor().get()?;
+
+    for (i, entry) in batch.entries.iter().enumerate() {
+        ensure!(
+            batch.entries[..i]
+                .iter()
+                .all(|other| other.name != entry.name),
+            CustomContractError::DuplicateName
+        );
+    }
+
+    let mut changes = Vec::with_capacity(batch.entries.len());
+
+    for entry in batch.entries {
+        let old_address = host
+            .state_mut()
+            .registry
+            .insert(entry.name.clone(), entry.destination);
+
+        changes.push(BatchUpdateChange {
+            name: entry.name,
+            old_address,
+            new_address: entry.destination,
+        });
+    }
+
+    // Log BatchUpdated event
+    logger.log(&Event::BatchUpdated(BatchUpdatedEvent { changes }))?;
+
+    Ok(())
+}
+
 /// The parameter type for the contract function `importContracts`.
 #[derive(Serialize, SchemaType)]
 #[concordium(transparent)]
@@ -210,15 +492,18 @@ fn import_contracts<S: HasStateApi>(
             .ok_or(CustomContractError::InvokeContractError)?
             .get()?;
 
-        host.state_mut()
+        let old_address = host
+            .state_mut()
             .registry
             .insert(name.clone(), contract_address);
 
         // Log LogRegistered event
         logger.log(&Event::LogRegistered(LogRegisteredEvent {
-            name,
+            name: name.clone(),
             destination: contract_address,
         }))?;
+
+        log_address_updated(logger, name, old_address, contract_address)?;
     }
 
     Ok(())
@@ -235,7 +520,7 @@ pub struct AtomicUpdateParam {
     pub contract_address: ContractAddress,
 }
 
-/// The parameter type for the contract function `upgradeNatively`.
+/// The parameter type for the contract functions `upgradeNatively` and `upgrade`.
 #[derive(Debug, Serialize, SchemaType)]
 pub struct UpgradeParams {
     /// The new module reference.
@@ -309,10 +594,102 @@ fn atomic_update<S: HasStateApi>(
 
     // Log LogRegistered event
     logger.log(&Event::LogRegistered(LogRegisteredEvent {
-        name,
+        name: name.clone(),
         destination: params.contract_address,
     }))?;
 
+    log_address_updated(logger, name, old_contract, params.contract_address)?;
+
+    Ok(())
+}
+
+/// The owner can upgrade this registry instance to a new module and optionally call a migration function
+/// in the new module after the upgrade (in the same transaction). The upgrade is reverted if the migration fails.
+/// The registered contract addresses and the owner are kept.
+///
+/// It rejects if:
+/// - Sender is not the owner of this contract instance.
+/// - It fails to parse the parameter.
+/// - If the upgrade fails.
+/// - If the migration invoke fails.
+///
+/// This function is marked as `low_level`, so that the state written by the migration function is not overwritten
+/// at the end of the execution.
+#[receive(
+    contract = "registry",
+    name = "upgrade",
+    parameter = "UpgradeParams",
+    error = "CustomContractError",
+    low_level
+)]
+fn upgrade<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<S>,
+) -> Result<(), CustomContractError> {
+    // Read the top-level contract state.
+    let state: State<S> = host.state().read_root()?;
+
+    // Only the owner can upgrade this contract.
+    ensure_eq!(
+        ctx.sender(),
+        state.owner.ok_or(CustomContractError::NoOwner)?,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    // Parse the parameter.
+    let param: UpgradeParams = ctx.parameter_cursor().get()?;
+
+    // Trigger the upgrade.
+    host.upgrade(param.module)?;
+
+    // Call the migration function if provided.
+    if let Some((func, parameters)) = param.migrate {
+        host.invoke_contract_raw(
+            &ctx.self_address(),
+            parameters.as_parameter(),
+            func.as_entrypoint_name(),
+            Amount::zero(),
+        )?;
+    }
+
+    Ok(())
+}
+
+/// The owner can remove a contract address (e.g. of a deprecated contract) from this registry by providing its key/name.
+/// Querying the key/name with the `getAddress` entry point fails with the `NameNotRegistered` error afterwards.
+#[receive(
+    contract = "registry",
+    name = "removeContract",
+    parameter = "String",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn remove_contract<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let name: String = ctx.parameter_cursor().get()?;
+
+    let address = host
+        .state_mut()
+        .registry
+        .remove_and_get(&name)
+        .ok_or(CustomContractError::NameNotRegistered)?;
+
+    // Log ContractRemoved event
+    logger.log(&Event::ContractRemoved(ContractRemovedEvent {
+        name,
+        address,
+    }))?;
+
     Ok(())
 }
 
@@ -336,6 +713,65 @@ fn require_and_get_address<S: HasStateApi>(
         .ok_or_else(|| CustomContractError::NameNotRegistered.into())
 }
 
+/// Returns all registered key names and their contract addresses, sorted by the key name. The iteration order of the
+/// registry map depends on the serialization of the keys, so the entries are sorted to make the enumeration
+/// deterministic: two snapshots of an unchanged registry are identical.
+fn sorted_contracts<S: HasStateApi>(state: &State<S>) -> Vec<(String, ContractAddress)> {
+    let mut contracts: Vec<(String, ContractAddress)> = state
+        .registry
+        .iter()
+        .map(|(name, contract_address)| (name.clone(), *contract_address))
+        .collect();
+
+    contracts.sort_by(|(name_a, _), (name_b, _)| name_a.cmp(name_b));
+
+    contracts
+}
+
+/// View function that returns all registered key names and their contract addresses (sorted by the key name).
+/// For large registries, use the paginated `getContracts` entry point to avoid hitting the energy limit.
+#[receive(
+    contract = "registry",
+    name = "getAllContracts",
+    return_value = "Vec<(String, ContractAddress)>"
+)]
+fn get_all_contracts<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<(String, ContractAddress)>> {
+    Ok(sorted_contracts(host.state()))
+}
+
+/// The parameter type for the contract function `getContracts`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetContractsParams {
+    /// The position (in the entries sorted by the key name) of the first entry to return.
+    pub start: u32,
+    /// The maximal number of entries to return.
+    pub limit: u32,
+}
+
+/// View function that returns a page of the registered key names and their contract addresses (sorted by the key name,
+/// so that the pages are consistent). All entries are read to sort them, but only the page is returned.
+#[receive(
+    contract = "registry",
+    name = "getContracts",
+    parameter = "GetContractsParams",
+    return_value = "Vec<(String, ContractAddress)>"
+)]
+fn get_contracts<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<(String, ContractAddress)>> {
+    let params: GetContractsParams = ctx.parameter_cursor().get()?;
+
+    Ok(sorted_contracts(host.state())
+        .into_iter()
+        .skip(params.start as usize)
+        .take(params.limit as usize)
+        .collect())
+}
+
 /// View function that returns the owner address.
 #[receive(
     contract = "registry",
@@ -370,6 +806,7 @@ fn renounce_ownership<S: HasStateApi>(
 
     let previous_owner = host.state().owner;
     host.state_mut().owner = None;
+    host.state_mut().pending_owner = None;
 
     // Log OwnershipTransferred event
     logger.log(&Event::OwnershipTransferred(OwnershipTransferredEvent {
@@ -380,11 +817,26 @@ fn renounce_ownership<S: HasStateApi>(
     Ok(())
 }
 
-/// The owner can transfer the ownership to a new address.
+/// View function that returns the pending owner address (the proposed new owner that has not accepted the ownership yet).
+#[receive(
+    contract = "registry",
+    name = "pendingOwner",
+    return_value = "Option<AccountAddress>"
+)]
+fn pending_owner<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<AccountAddress>> {
+    Ok(host.state().pending_owner)
+}
+
+/// The owner can propose a new owner (the first step of the ownership transfer).
+/// The ownership is only transferred after the new owner accepts it with the `acceptOwnership` entry point.
+/// Proposing another new owner replaces the previous proposal.
 #[receive(
     contract = "registry",
     name = "transferOwnership",
-    parameter = "Address",
+    parameter = "AccountAddress",
     error = "CustomContractError",
     enable_logger,
     mutable
@@ -400,14 +852,89 @@ fn transfer_ownership<S: HasStateApi>(
         CustomContractError::UnauthorizedAccount
     );
 
-    let new_owner: Address = ctx.parameter_cursor().get()?;
+    let new_owner: AccountAddress = ctx.parameter_cursor().get()?;
+
+    host.state_mut().pending_owner = Some(new_owner);
+
+    // Log OwnershipTransferStarted event
+    logger.log(&Event::OwnershipTransferStarted(
+        OwnershipTransferStartedEvent {
+            previous_owner: host.state().owner,
+            new_owner,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// The owner can cancel a pending ownership transfer (e.g. if a wrong new owner was proposed), so that the proposed
+/// owner cannot accept the ownership anymore. It rejects with `NoPendingOwner` if no ownership transfer is pending.
+#[receive(
+    contract = "registry",
+    name = "cancelOwnershipTransfer",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn cancel_ownership_transfer<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner.ok_or(CustomContractError::NoOwner)?,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let canceled_owner = host
+        .state_mut()
+        .pending_owner
+        .take()
+        .ok_or(CustomContractError::NoPendingOwner)?;
+
+    // Log OwnershipTransferCanceled event
+    logger.log(&Event::OwnershipTransferCanceled(
+        OwnershipTransferCanceledEvent {
+            owner: host.state().owner,
+            canceled_owner,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// The pending owner can accept the ownership (the second step of the ownership transfer).
+#[receive(
+    contract = "registry",
+    name = "acceptOwnership",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn accept_ownership<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    let pending_owner = host
+        .state()
+        .pending_owner
+        .ok_or(CustomContractError::NoPendingOwner)?;
+
+    ensure_eq!(
+        ctx.sender(),
+        Address::Account(pending_owner),
+        CustomContractError::UnauthorizedAccount
+    );
 
     let previous_owner = host.state().owner;
-    host.state_mut().owner = Some(new_owner);
+    host.state_mut().owner = Some(Address::Account(pending_owner));
+    host.state_mut().pending_owner = None;
 
     // Log OwnershipTransferred event
     logger.log(&Event::OwnershipTransferred(OwnershipTransferredEvent {
-        new_owner: Some(new_owner),
+        new_owner: Some(Address::Account(pending_owner)),
         previous_owner,
     }))?;
 
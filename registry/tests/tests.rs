@@ -1,6 +1,13 @@
 use concordium_smart_contract_testing::*;
-use registry::{ImportAddressesParam, ImportAddressesParams, OwnershipTransferredEvent};
-use registry::{ImportContractsParam, LogRegisteredEvent};
+use registry::{
+    AddressUpdatedEvent, BatchUpdateChange, BatchUpdatedEvent, ContractRemovedEvent, Event,
+    GetContractsParams, ImportContractsParam, LogRegisteredEvent,
+};
+use registry::{
+    ImportAddressesParam, ImportAddressesParams, InitParamsRegistry,
+    OwnershipTransferCanceledEvent, OwnershipTransferStartedEvent, OwnershipTransferredEvent,
+    UpgradeParams,
+};
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([77u8; 32]);
 const OTHER_ACCOUNT: AccountAddress = AccountAddress([1u8; 32]);
@@ -42,6 +49,16 @@ fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
     (chain, initialization_registry)
 }
 
+/// Returns the error code of a contract update that was rejected by the contract.
+fn reject_code(error: &ContractInvokeError) -> i32 {
+    match error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => code,
+        _ => panic!("The contract update should be rejected by the contract"),
+    }
+}
+
 #[test]
 fn test_init() {
     let (_chain, initialization_registry) = setup_chain_and_contract();
@@ -57,124 +74,917 @@ fn test_init() {
     let event_struct: OwnershipTransferredEvent =
         from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
 
-    assert_eq!(
-        event_struct,
-        OwnershipTransferredEvent {
-            new_owner: Some(Address::from(ACC_ADDR_OWNER)),
-            previous_owner: None,
-        },
-        "OwnershipTransferredEvent event is wrong"
-    );
+    assert_eq!(
+        event_struct,
+        OwnershipTransferredEvent {
+            new_owner: Some(Address::from(ACC_ADDR_OWNER)),
+            previous_owner: None,
+        },
+        "OwnershipTransferredEvent event is wrong"
+    );
+}
+
+/// Test the `getCodeVersion` function with and without the code version in the init parameter.
+#[test]
+fn test_code_version() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let get_code_version = |chain: &Chain, contract_address: ContractAddress| -> String {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "registry.getCodeVersion".to_string(),
+                    ),
+                    message: OwnedParameter::empty(),
+                },
+            )
+            .expect("Should be able to query the code version");
+
+        from_bytes(&invoke.return_value).expect("Should return a valid result")
+    };
+
+    // Checking that an empty init parameter (as sent by older deploy scripts) results in an empty code version.
+
+    assert_eq!(
+        get_code_version(&chain, initialization_registry.contract_address),
+        ""
+    );
+
+    // Checking the code version set at init.
+
+    let module_reference = chain
+        .get_contract(initialization_registry.contract_address)
+        .expect("The registry contract should exist")
+        .module_reference;
+
+    let initialization_registry = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_registry".to_string()),
+                param: OwnedParameter::from_serial(&InitParamsRegistry {
+                    code_version: String::from("8f3c2a1"),
+                })
+                .expect("`InitParamsRegistry` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `registry` should always succeed");
+
+    assert_eq!(
+        get_code_version(&chain, initialization_registry.contract_address),
+        "8f3c2a1"
+    );
+}
+
+/// Test that the initialization rejects a truncated `code_version` instead of falling back to an empty code version.
+#[test]
+fn test_init_truncated_code_version() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let module_reference = chain
+        .get_contract(initialization_registry.contract_address)
+        .expect("The registry contract should exist")
+        .module_reference;
+
+    // A length of 10 bytes but only 3 bytes of the string.
+
+    chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_registry".to_string()),
+                param: OwnedParameter::try_from(vec![10, 0, 0, 0, b'a', b'b', b'c'])
+                    .expect("The parameter should not be too large"),
+            },
+        )
+        .expect_err("Initialization should fail with a truncated `code_version`");
+}
+
+/// Test `importAddresses` function.
+#[test]
+fn test_import_addresses() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let umbrella_feeds_contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let staking_bank_contract = ContractAddress {
+        index: 99,
+        subindex: 0,
+    };
+
+    let input_parameter = ImportAddressesParams {
+        entries: vec![
+            ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination: umbrella_feeds_contract,
+            },
+            ImportAddressesParam {
+                name: String::from("Contract2"),
+                destination: staking_bank_contract,
+            },
+        ],
+    };
+
+    // Invoking 'importAddresses'.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()), // The receive function to call.
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to importAddresses");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: LogRegisteredEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        LogRegisteredEvent {
+            name: String::from("Contract1"),
+            destination: umbrella_feeds_contract,
+        },
+        "LogRegistered event is wrong"
+    );
+
+    let event = &events[0].1[1];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: LogRegisteredEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        LogRegisteredEvent {
+            name: String::from("Contract2"),
+            destination: staking_bank_contract,
+        },
+        "LogRegistered event is wrong"
+    );
+
+    // Checking that contract address was registered correctly in registry.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contract address");
+
+    let contract_address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contract_address, umbrella_feeds_contract);
+}
+
+/// Test that replacing a registered contract address logs an `AddressUpdated` event.
+#[test]
+fn test_address_updated() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let old_contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let new_contract = ContractAddress {
+        index: 9,
+        subindex: 0,
+    };
+
+    for (destination, expected_events) in [
+        (
+            old_contract,
+            vec![Event::LogRegistered(LogRegisteredEvent {
+                name: String::from("Contract1"),
+                destination: old_contract,
+            })],
+        ),
+        (
+            new_contract,
+            vec![
+                Event::LogRegistered(LogRegisteredEvent {
+                    name: String::from("Contract1"),
+                    destination: new_contract,
+                }),
+                Event::AddressUpdated(AddressUpdatedEvent {
+                    name: String::from("Contract1"),
+                    old_address: old_contract,
+                    new_address: new_contract,
+                }),
+            ],
+        ),
+    ] {
+        let input_parameter = ImportAddressesParams {
+            entries: vec![ImportAddressesParam {
+                name: String::from("Contract1"),
+                destination,
+            }],
+        };
+
+        // Invoking 'importAddresses'.
+
+        let update = chain
+            .contract_update(
+                Signer::with_one_key(), // Used for specifying the number of signatures.
+                ACC_ADDR_OWNER,         // Invoker account.
+                Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+                Energy::from(10000),    // Maximum energy allowed for the update.
+                UpdateContractPayload {
+                    address: initialization_registry.contract_address, // The contract to update.
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "registry.importAddresses".into(),
+                    ), // The receive function to call.
+                    message: OwnedParameter::from_serial(&input_parameter)
+                        .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
+                    amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+                },
+            )
+            .expect("Should be able to importAddresses");
+
+        // Checking logged events.
+        let events: Vec<Event> = update
+            .events()
+            .flat_map(|(_, events)| events)
+            .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+            .collect();
+
+        assert_eq!(events, expected_events, "Events are wrong");
+    }
+}
+
+/// Test `getAllContracts` and `getContracts` functions.
+#[test]
+fn test_get_contracts() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let entries = vec![
+        (String::from("Contract1"), ContractAddress::new(8, 0)),
+        (String::from("Contract2"), ContractAddress::new(9, 0)),
+        (String::from("Contract3"), ContractAddress::new(10, 0)),
+    ];
+
+    let input_parameter = ImportAddressesParams {
+        entries: entries
+            .iter()
+            .map(|(name, destination)| ImportAddressesParam {
+                name: name.clone(),
+                destination: *destination,
+            })
+            .collect(),
+    };
+
+    // Invoking 'importAddresses'.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()), // The receive function to call.
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to importAddresses");
+
+    // Checking `getAllContracts`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.getAllContracts".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query all contracts");
+
+    let contracts: Vec<(String, ContractAddress)> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contracts, entries);
+
+    // Checking `getContracts`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getContracts".to_string()),
+                message: OwnedParameter::from_serial(&GetContractsParams { start: 1, limit: 5 })
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contracts");
+
+    let contracts: Vec<(String, ContractAddress)> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contracts, entries[1..]);
+}
+
+/// Test that `getAllContracts` and `getContracts` return the entries sorted by the key name, independent of the
+/// order in which they were registered.
+#[test]
+fn test_get_contracts_sorted() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let input_parameter = ImportAddressesParams {
+        entries: vec![
+            ImportAddressesParam {
+                name: String::from("UmbrellaFeeds"),
+                destination: ContractAddress::new(10, 0),
+            },
+            ImportAddressesParam {
+                name: String::from("Zeta"),
+                destination: ContractAddress::new(8, 0),
+            },
+            ImportAddressesParam {
+                name: String::from("StakingBank"),
+                destination: ContractAddress::new(9, 0),
+            },
+        ],
+    };
+
+    // Invoking 'importAddresses'.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()),
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"),
+                amount: Amount::from_ccd(0),
+            },
+        )
+        .expect("Should be able to importAddresses");
+
+    // The map iterates the keys by their serialization (length first), i.e. `Zeta` would come first.
+    let sorted = vec![
+        (String::from("StakingBank"), ContractAddress::new(9, 0)),
+        (String::from("UmbrellaFeeds"), ContractAddress::new(10, 0)),
+        (String::from("Zeta"), ContractAddress::new(8, 0)),
+    ];
+
+    // Checking `getAllContracts`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.getAllContracts".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query all contracts");
+
+    let contracts: Vec<(String, ContractAddress)> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(contracts, sorted);
+
+    // Checking that the pages of `getContracts` follow the same order.
+
+    for start in 0..3 {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_registry.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "registry.getContracts".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&GetContractsParams { start, limit: 1 })
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to query contracts");
+
+        let contracts: Vec<(String, ContractAddress)> =
+            from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        assert_eq!(contracts, sorted[start as usize..start as usize + 1]);
+    }
+}
+
+/// Test `removeContract` function.
+#[test]
+fn test_remove_contract() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let contract = ContractAddress {
+        index: 8,
+        subindex: 0,
+    };
+
+    let input_parameter = ImportAddressesParams {
+        entries: vec![ImportAddressesParam {
+            name: String::from("Contract1"),
+            destination: contract,
+        }],
+    };
+
+    // Invoking 'importAddresses'.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()), // The receive function to call.
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to importAddresses");
+
+    let remove_payload = UpdateContractPayload {
+        address: initialization_registry.contract_address,
+        receive_name: OwnedReceiveName::new_unchecked("registry.removeContract".into()),
+        message: OwnedParameter::from_serial(&String::from("Contract1"))
+            .expect("`input_parameter` should be a valid inut parameter"),
+        amount: Amount::from_ccd(0),
+    };
+
+    // Invoking 'removeContract' from an account that is not the owner.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            remove_payload.clone(),
+        )
+        .expect_err("Should not be able to removeContract");
+
+    // Checking that the error is `UnauthorizedAccount`.
+    assert_eq!(reject_code(&update), -5, "Error is wrong");
+
+    // Invoking 'removeContract'.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            remove_payload.clone(),
+        )
+        .expect("Should be able to removeContract");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::ContractRemoved(ContractRemovedEvent {
+            name: String::from("Contract1"),
+            address: contract,
+        })],
+        "Events are wrong"
+    );
+
+    // Checking that the contract address was removed from the registry.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should not be able to query a removed contract address");
+
+    // Checking that the error is `NameNotRegistered`.
+    assert_eq!(reject_code(&invoke), -4, "Error is wrong");
+
+    // Invoking 'removeContract' again.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            remove_payload,
+        )
+        .expect_err("Should not be able to remove a contract twice");
+
+    // Checking that the error is `NameNotRegistered`.
+    assert_eq!(reject_code(&update), -4, "Error is wrong");
+}
+
+/// Test the `upgrade` function.
+#[test]
+fn test_upgrade() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    let contract = ContractAddress {
+        index: 5,
+        subindex: 0,
+    };
+
+    // Registering a contract address.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "registry.importAddresses".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&ImportAddressesParams {
+                    entries: vec![ImportAddressesParam {
+                        name: String::from("Contract1"),
+                        destination: contract,
+                    }],
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to importAddresses");
+
+    let registry_module = module_load_v1("./registry.wasm.v1")
+        .expect("`registry.wasm.v1` module should be loaded")
+        .get_module_ref();
+
+    let upgrade_payload =
+        |module: ModuleReference, migrate: Option<(OwnedEntrypointName, OwnedParameter)>| {
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.upgrade".to_string()),
+                message: OwnedParameter::from_serial(&UpgradeParams { module, migrate })
+                    .expect("`UpgradeParams` should be a valid inut parameter"),
+            }
+        };
+
+    // Invoking 'upgrade' from an account that is not the owner.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            OTHER_ACCOUNT,
+            Address::Account(OTHER_ACCOUNT),
+            Energy::from(10000),
+            upgrade_payload(registry_module, None),
+        )
+        .expect_err("Should not be able to upgrade from a non-owner account");
+
+    // Checking that the error is `UnauthorizedAccount`.
+    assert_eq!(reject_code(&update), -5, "Error is wrong");
+
+    // Invoking 'upgrade' with a module that does not contain a `registry` contract.
+
+    let dummy_module = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../dummy-contract/dummy-contract.wasm.v1")
+                .expect("`dummy-contract.wasm.v1` module should be loaded"),
+        )
+        .expect("`dummy-contract.wasm.v1` deployment should always succeed")
+        .module_reference;
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            upgrade_payload(dummy_module, None),
+        )
+        .expect_err("Should not be able to upgrade to a module without a `registry` contract");
+
+    // Checking that the error is `FailedUpgradeMissingContract`.
+    assert_eq!(reject_code(&update), -11, "Error is wrong");
+
+    // Invoking 'upgrade' with a migration function that does not exist.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            upgrade_payload(
+                registry_module,
+                Some((
+                    OwnedEntrypointName::new_unchecked("missingMigration".to_string()),
+                    OwnedParameter::empty(),
+                )),
+            ),
+        )
+        .expect_err("Should not be able to upgrade with a failing migration");
+
+    // Checking that the error is `InvokeContractError`.
+    assert_eq!(reject_code(&update), -6, "Error is wrong");
+
+    // Invoking 'upgrade' from the owner.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            upgrade_payload(registry_module, None),
+        )
+        .expect("Should be able to upgrade");
+
+    assert_eq!(
+        chain
+            .get_contract(initialization_registry.contract_address)
+            .expect("The registry instance should exist")
+            .module_reference,
+        registry_module,
+        "The module reference is wrong"
+    );
+
+    // Checking that the registered contract address was kept.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("Contract1"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the contract address");
+
+    let address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(address, contract);
 }
 
-/// Test `importAddresses` function.
+/// Test `importContracts` function.
+/// Test the `batchUpdate` function.
 #[test]
-fn test_import_addresses() {
+fn test_batch_update() {
     let (mut chain, initialization_registry) = setup_chain_and_contract();
 
-    let umbrella_feeds_contract = ContractAddress {
+    let old_contract = ContractAddress {
         index: 8,
         subindex: 0,
     };
 
-    let staking_bank_contract = ContractAddress {
-        index: 99,
+    let new_contract = ContractAddress {
+        index: 9,
         subindex: 0,
     };
 
-    let input_parameter = ImportAddressesParams {
-        entries: vec![
-            ImportAddressesParam {
-                name: String::from("Contract1"),
-                destination: umbrella_feeds_contract,
-            },
-            ImportAddressesParam {
-                name: String::from("Contract2"),
-                destination: staking_bank_contract,
-            },
-        ],
+    let other_contract = ContractAddress {
+        index: 10,
+        subindex: 0,
     };
 
-    // Invoking 'importAddresses'.
-
-    let update = chain
-        .contract_update(
-            Signer::with_one_key(), // Used for specifying the number of signatures.
-            ACC_ADDR_OWNER,         // Invoker account.
-            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
-            Energy::from(10000),    // Maximum energy allowed for the update.
-            UpdateContractPayload {
-                address: initialization_registry.contract_address, // The contract to update.
-                receive_name: OwnedReceiveName::new_unchecked("registry.importAddresses".into()), // The receive function to call.
-                message: OwnedParameter::from_serial(&input_parameter)
-                    .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
-                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
-            },
-        )
-        .expect("Should be able to importAddresses");
+    let batch_update =
+        |chain: &mut Chain, sender: AccountAddress, entries: Vec<(&str, ContractAddress)>| {
+            let input_parameter = ImportAddressesParams {
+                entries: entries
+                    .into_iter()
+                    .map(|(name, destination)| ImportAddressesParam {
+                        name: String::from(name),
+                        destination,
+                    })
+                    .collect(),
+            };
+
+            chain.contract_update(
+                Signer::with_one_key(),
+                sender,
+                Address::Account(sender),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    address: initialization_registry.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked("registry.batchUpdate".into()),
+                    message: OwnedParameter::from_serial(&input_parameter)
+                        .expect("`input_parameter` should be a valid inut parameter"),
+                    amount: Amount::from_ccd(0),
+                },
+            )
+        };
+
+    let get_address = |chain: &Chain, name: &str| {
+        chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_registry.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "registry.getAddress".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&String::from(name))
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .ok()
+            .map(|invoke| {
+                from_bytes::<ContractAddress>(&invoke.return_value)
+                    .expect("Should return a valid result")
+            })
+    };
 
-    // Checking logged event.
-    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
-    let event = &events[0].1[0];
+    // Invoking 'batchUpdate' with the first batch.
 
-    // Checking event tag.
-    assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
+    let update = batch_update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        vec![("Contract1", old_contract)],
+    )
+    .expect("Should be able to batchUpdate");
 
-    // Removing the tag byte at the beginning of the event.
-    let event_struct: LogRegisteredEvent =
-        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+    // Checking logged events.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
 
     assert_eq!(
-        event_struct,
-        LogRegisteredEvent {
-            name: String::from("Contract1"),
-            destination: umbrella_feeds_contract,
-        },
-        "LogRegistered event is wrong"
+        events,
+        [Event::BatchUpdated(BatchUpdatedEvent {
+            changes: vec![BatchUpdateChange {
+                name: String::from("Contract1"),
+                old_address: None,
+                new_address: old_contract,
+            }],
+        })],
+        "Events are wrong"
     );
 
-    let event = &events[0].1[1];
+    // Invoking 'batchUpdate' with a second batch that overrides `Contract1` and registers `Contract2`.
 
-    // Checking event tag.
-    assert_eq!(event.as_ref()[0], 0, "Event tag is wrong");
+    let update = batch_update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        vec![("Contract1", new_contract), ("Contract2", other_contract)],
+    )
+    .expect("Should be able to batchUpdate");
 
-    // Removing the tag byte at the beginning of the event.
-    let event_struct: LogRegisteredEvent =
-        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+    // Checking that a single event lists all changes.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
 
     assert_eq!(
-        event_struct,
-        LogRegisteredEvent {
-            name: String::from("Contract2"),
-            destination: staking_bank_contract,
-        },
-        "LogRegistered event is wrong"
+        events,
+        [Event::BatchUpdated(BatchUpdatedEvent {
+            changes: vec![
+                BatchUpdateChange {
+                    name: String::from("Contract1"),
+                    old_address: Some(old_contract),
+                    new_address: new_contract,
+                },
+                BatchUpdateChange {
+                    name: String::from("Contract2"),
+                    old_address: None,
+                    new_address: other_contract,
+                },
+            ],
+        })],
+        "Events are wrong"
     );
 
-    // Checking that contract address was registered correctly in registry.
+    assert_eq!(get_address(&chain, "Contract1"), Some(new_contract));
+    assert_eq!(get_address(&chain, "Contract2"), Some(other_contract));
 
-    let invoke = chain
-        .contract_invoke(
-            ACC_ADDR_OWNER,
-            Address::Account(ACC_ADDR_OWNER),
-            Energy::from(10000),
-            UpdateContractPayload {
-                amount: Amount::zero(),
-                address: initialization_registry.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
-                message: OwnedParameter::from_serial(&String::from("Contract1"))
-                    .expect("Should be a valid inut parameter"),
-            },
-        )
-        .expect("Should be able to query contract address");
+    // Invoking 'batchUpdate' with a duplicate name. The whole batch should be rejected.
 
-    let contract_address: ContractAddress =
-        from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let update = batch_update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        vec![
+            ("Contract3", other_contract),
+            ("Contract1", old_contract),
+            ("Contract1", other_contract),
+        ],
+    )
+    .expect_err("Should not be able to batchUpdate with a duplicate name");
 
-    assert_eq!(contract_address, umbrella_feeds_contract);
+    // Checking that the error is `DuplicateName`.
+    assert_eq!(reject_code(&update), -9, "Error is wrong");
+
+    // Checking that the registry is unchanged.
+    assert_eq!(get_address(&chain, "Contract1"), Some(new_contract));
+    assert_eq!(get_address(&chain, "Contract3"), None);
+
+    // Invoking 'batchUpdate' from an account that is not the owner.
+
+    let update = batch_update(&mut chain, OTHER_ACCOUNT, vec![("Contract1", old_contract)])
+        .expect_err("Only the owner should be able to batchUpdate");
+
+    // Checking that the error is `UnauthorizedAccount`.
+    assert_eq!(reject_code(&update), -5, "Error is wrong");
+
+    assert_eq!(get_address(&chain, "Contract1"), Some(new_contract));
 }
 
-/// Test `importContracts` function.
 #[test]
 fn test_import_contracts() {
     let (mut chain, initialization_registry) = setup_chain_and_contract();
@@ -309,7 +1119,7 @@ fn test_owner_functionalities() {
             UpdateContractPayload {
                 address: initialization_registry.contract_address, // The contract to update.
                 receive_name: OwnedReceiveName::new_unchecked("registry.transferOwnership".into()), // The receive function to call.
-                message: OwnedParameter::from_serial(&Address::from(OTHER_ACCOUNT))
+                message: OwnedParameter::from_serial(&OTHER_ACCOUNT)
                     .expect("`input_parameter` should be a valid inut parameter"), // The parameter sent to the contract.
                 amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
             },
@@ -320,6 +1130,107 @@ fn test_owner_functionalities() {
     let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
     let event = &events[0].1[0];
 
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 2, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: OwnershipTransferStartedEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OwnershipTransferStartedEvent {
+            new_owner: OTHER_ACCOUNT,
+            previous_owner: Some(Address::from(ACC_ADDR_OWNER)),
+        },
+        "OwnershipTransferStartedEvent event is wrong"
+    );
+
+    // Checking `owner` (the ownership is not transferred before it is accepted).
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.owner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query owner address");
+
+    let owner: Option<Address> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(owner, Some(Address::from(ACC_ADDR_OWNER)));
+
+    // Checking `pendingOwner`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.pendingOwner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query pending owner address");
+
+    let pending_owner: Option<AccountAddress> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(pending_owner, Some(OTHER_ACCOUNT));
+
+    // Invoking 'acceptOwnership' from an account that is not the pending owner.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()), // The receive function to call.
+                message: OwnedParameter::empty(), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0),      // Sending the contract 0 CCD.
+            },
+        )
+        .expect_err("Should not be able to acceptOwnership");
+
+    // Checking that the error is `UnauthorizedAccount`.
+    let rv: i32 = reject_code(&update);
+
+    assert_eq!(rv, -5, "Error is wrong");
+
+    // Invoking 'acceptOwnership'.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            OTHER_ACCOUNT,          // Invoker account.
+            Address::Account(OTHER_ACCOUNT), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()), // The receive function to call.
+                message: OwnedParameter::empty(), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0),      // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to acceptOwnership");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = update.events().collect();
+    let event = &events[0].1[0];
+
     // Checking event tag.
     assert_eq!(event.as_ref()[0], 1, "Event tag is wrong");
 
@@ -357,6 +1268,49 @@ fn test_owner_functionalities() {
 
     assert_eq!(owner, Some(Address::from(OTHER_ACCOUNT)));
 
+    // Checking `pendingOwner`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.pendingOwner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query pending owner address");
+
+    let pending_owner: Option<AccountAddress> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(pending_owner, None);
+
+    // Invoking 'acceptOwnership' again (no ownership transfer is pending).
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            OTHER_ACCOUNT,          // Invoker account.
+            Address::Account(OTHER_ACCOUNT), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.acceptOwnership".into()), // The receive function to call.
+                message: OwnedParameter::empty(), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0),      // Sending the contract 0 CCD.
+            },
+        )
+        .expect_err("Should not be able to acceptOwnership");
+
+    // Checking that the error is `NoPendingOwner`.
+    let rv: i32 = reject_code(&update);
+
+    assert_eq!(rv, -8, "Error is wrong");
+
     // Invoking 'renounceOwnership'.
 
     let update = chain
@@ -416,3 +1370,126 @@ fn test_owner_functionalities() {
 
     assert_eq!(owner, None);
 }
+
+#[test]
+fn test_cancel_ownership_transfer() {
+    let (mut chain, initialization_registry) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let update = |chain: &mut Chain, sender: AccountAddress, receive_name: &str, parameter| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(10000),
+            UpdateContractPayload {
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(receive_name.to_string()),
+                message: parameter,
+                amount: Amount::from_ccd(0),
+            },
+        )
+    };
+
+    // Invoking 'cancelOwnershipTransfer' without a pending ownership transfer.
+
+    let update_error = update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "registry.cancelOwnershipTransfer",
+        OwnedParameter::empty(),
+    )
+    .expect_err("Should not be able to cancel the ownership transfer");
+
+    // Checking that the error is `NoPendingOwner`.
+    assert_eq!(reject_code(&update_error), -8, "Error is wrong");
+
+    // Invoking 'transferOwnership'.
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "registry.transferOwnership",
+        OwnedParameter::from_serial(&OTHER_ACCOUNT)
+            .expect("`input_parameter` should be a valid inut parameter"),
+    )
+    .expect("Should be able to transferOwnership");
+
+    // Invoking 'cancelOwnershipTransfer' from an account that is not the owner (the pending owner).
+
+    let update_error = update(
+        &mut chain,
+        OTHER_ACCOUNT,
+        "registry.cancelOwnershipTransfer",
+        OwnedParameter::empty(),
+    )
+    .expect_err("Should not be able to cancel the ownership transfer");
+
+    // Checking that the error is `UnauthorizedAccount`.
+    assert_eq!(reject_code(&update_error), -5, "Error is wrong");
+
+    // Invoking 'cancelOwnershipTransfer'.
+
+    let cancel = update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "registry.cancelOwnershipTransfer",
+        OwnedParameter::empty(),
+    )
+    .expect("Should be able to cancel the ownership transfer");
+
+    // Checking logged event.
+    let events: Vec<(ContractAddress, &[ContractEvent])> = cancel.events().collect();
+    let event = &events[0].1[0];
+
+    // Checking event tag.
+    assert_eq!(event.as_ref()[0], 6, "Event tag is wrong");
+
+    // Removing the tag byte at the beginning of the event.
+    let event_struct: OwnershipTransferCanceledEvent =
+        from_bytes(&event.as_ref()[1..]).expect("Tag removal should work");
+
+    assert_eq!(
+        event_struct,
+        OwnershipTransferCanceledEvent {
+            owner: Some(Address::from(ACC_ADDR_OWNER)),
+            canceled_owner: OTHER_ACCOUNT,
+        },
+        "OwnershipTransferCanceledEvent event is wrong"
+    );
+
+    // Checking `pendingOwner`.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.pendingOwner".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query pending owner address");
+
+    let pending_owner: Option<AccountAddress> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(pending_owner, None);
+
+    // Invoking 'acceptOwnership' from the previously proposed owner.
+
+    let update_error = update(
+        &mut chain,
+        OTHER_ACCOUNT,
+        "registry.acceptOwnership",
+        OwnedParameter::empty(),
+    )
+    .expect_err("Should not be able to acceptOwnership");
+
+    // Checking that the error is `NoPendingOwner`.
+    assert_eq!(reject_code(&update_error), -8, "Error is wrong");
+}
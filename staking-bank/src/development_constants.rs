@@ -1,6 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::{CustomContractError, State};
 use concordium_std::*;
 
 // Development constants and functions
@@ -19,52 +18,37 @@ pub(crate) const VALIDATOR_1: PublicKeyEd25519 = PublicKeyEd25519([
     243, 162, 209, 78, 136, 94, 127, 247, 21, 222, 221,
 ]);
 
-type StakingBalanceAmount = u8;
+// ECDSA (secp256k1) public keys of the validators (same order as above). They are used if the
+// `umbrella_feeds` contract is initialized with the `EcdsaSecp256k1` signature scheme.
 
-/// The number of validators.
-pub(crate) const NUMBER_OF_VALIDATORS: u8 = 2;
-/// total supply = number_of_validators * ONE.
-pub(crate) const TOTAL_SUPPLY: StakingBalanceAmount = 2 * 1u8;
-
-/// Internal function that returns a boolean if the given public key is a validator.
-pub(crate) fn is_validator(validator: PublicKeyEd25519) -> bool {
-    public_keys().contains(&validator)
-}
-
-/// Internal function that returns all validators.
-pub(crate) fn public_keys() -> [PublicKeyEd25519; 2] {
-    [VALIDATOR_0, VALIDATOR_1]
-}
-
-/// View function that returns validator's URL (as well as the inputted public key). The function throws an error if the public key is not a validator.
-#[receive(
-    contract = "staking_bank",
-    name = "validators",
-    parameter = "PublicKeyEd25519",
-    return_value = "(PublicKeyEd25519,String)"
-)]
-pub(crate) fn validators<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<(PublicKeyEd25519, String)> {
-    let id: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+// ATTENTION: Use a different key in production. The private key is exposed and used for testing here.
+// Private key: 1F2A8E3C6D5B4A79880716253443526170E8D9C6B5A4F3E2D1C0B9A897867564
+pub(crate) const ECDSA_VALIDATOR_0: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([
+    2, 122, 114, 246, 115, 219, 127, 146, 184, 35, 59, 96, 60, 177, 237, 120, 241, 72, 252, 51,
+    106, 192, 49, 204, 84, 255, 146, 218, 148, 9, 105, 124, 224,
+]);
 
-    match id {
-        VALIDATOR_0 => Ok((id, "https://validator.umb.network".to_string())),
-        VALIDATOR_1 => Ok((id, "https://validator2.umb.network".to_string())),
-        _ => bail!(CustomContractError::NotValidator.into()),
-    }
-}
+// ATTENTION: Use a different key in production. The private key is exposed and used for testing here.
+// Private key: 7B6A594837261504F3E2D1C0B9A8978675645342312F1E0D0C0B0A0908070605
+pub(crate) const ECDSA_VALIDATOR_1: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([
+    2, 231, 38, 120, 58, 92, 141, 19, 123, 165, 152, 190, 30, 234, 22, 28, 98, 136, 124, 106, 12,
+    33, 234, 80, 46, 230, 4, 117, 46, 81, 219, 7, 143,
+]);
 
-/// View function that returns all validators' public keys.
-#[receive(
-    contract = "staking_bank",
-    name = "getPublicKeys",
-    return_value = "[PublicKeyEd25519;2]"
-)]
-pub(crate) fn get_public_keys<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<[PublicKeyEd25519; 2]> {
-    Ok(public_keys())
+/// Internal function that returns the initial validators (signing public key, URL, and ECDSA public key).
+/// They are registered in the `validators` map at initialization.
+pub(crate) fn initial_validators() -> [(PublicKeyEd25519, &'static str, PublicKeyEcdsaSecp256k1); 2]
+{
+    [
+        (
+            VALIDATOR_0,
+            "https://validator.umb.network",
+            ECDSA_VALIDATOR_0,
+        ),
+        (
+            VALIDATOR_1,
+            "https://validator2.umb.network",
+            ECDSA_VALIDATOR_1,
+        ),
+    ]
 }
@@ -1,6 +1,15 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 //! # Staking Bank
+//!
+//! A registry of the validators. The validators are stored in a map keyed by their (Ed25519) signing public key.
+//! The initial validators (compiled into the contract via the `production`, `development`, or `sandbox` feature) are
+//! registered at initialization. Afterwards, the owner can:
+//! - Register a validator with the `registerValidator` entry point.
+//! - Remove a validator with the `removeValidator` entry point.
+//!
+//! The location (URL) of a validator can be updated with the `setValidatorLocation` entry point by the owner or by
+//! the validator itself (with a signature of its signing key).
 use concordium_std::*;
 use core::fmt::Debug;
 
@@ -20,17 +29,89 @@ mod sandbox_constants;
 use sandbox_constants::*;
 
 #[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
-const ONE: StakingBalanceAmount = 1u8;
+const ONE: StakingBalanceAmount = 1u16;
 
 #[allow(dead_code)]
-type StakingBalanceAmount = u8;
+type StakingBalanceAmount = u16;
+
+/// The maximum number of validators (the number of validators is returned as `u16`).
+const MAX_NUMBER_OF_VALIDATORS: u16 = u16::MAX;
+
+/// The information stored for each validator.
+#[derive(Serialize, SchemaType, Clone, Debug, PartialEq, Eq)]
+pub struct ValidatorInfo {
+    /// The URL of the validator.
+    pub location: String,
+    /// The ECDSA (secp256k1) public key of the validator. It is used if the `umbrella_feeds` contract is
+    /// initialized with the `EcdsaSecp256k1` signature scheme.
+    pub ecdsa_public_key: Option<PublicKeyEcdsaSecp256k1>,
+    /// The nonce that the next `LocationMessage` of the validator has to be signed with. It is incremented with every
+    /// location update, so that a signed location update cannot be replayed.
+    pub nonce: u64,
+}
+
+#[derive(Serial, DeserialWithState)]
+#[concordium(state_parameter = "S")]
+pub struct State<S> {
+    /// The owner of this contract. The owner can register and remove validators.
+    owner: Address,
+    /// Map of all validators. It maps from the signing public key of a validator to its information.
+    validators: StateMap<PublicKeyEd25519, ValidatorInfo, S>,
+    /// Map from the ECDSA public key of a validator to its signing public key.
+    ecdsa_validators: StateMap<PublicKeyEcdsaSecp256k1, PublicKeyEd25519, S>,
+    /// The number of validators in the `validators` map.
+    number_of_validators: u16,
+}
+
+/// Tagged events to be serialized for the event log.
+/// The tag of each event is stable. New events are added with new tags, so that off-chain parsers
+/// (e.g. indexers) keep working.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+#[concordium(repr(u8))]
+pub enum Event {
+    /// The event tracks whenever a validator gets registered (including the initial validators at initialization).
+    #[concordium(tag = 0)]
+    ValidatorRegistered(ValidatorRegisteredEvent),
+    /// The event tracks whenever a validator gets removed.
+    #[concordium(tag = 1)]
+    ValidatorRemoved(ValidatorRemovedEvent),
+    /// The event tracks whenever the location of a validator gets updated.
+    #[concordium(tag = 2)]
+    ValidatorLocationChanged(ValidatorLocationChangedEvent),
+}
 
-#[derive(Serial, Deserial)]
-pub struct State {}
+/// The ValidatorRegisteredEvent is logged when a validator gets registered.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ValidatorRegisteredEvent {
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The URL of the validator.
+    pub location: String,
+    /// The ECDSA public key of the validator.
+    pub ecdsa_public_key: Option<PublicKeyEcdsaSecp256k1>,
+}
+
+/// The ValidatorRemovedEvent is logged when a validator gets removed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ValidatorRemovedEvent {
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+}
+
+/// The ValidatorLocationChangedEvent is logged when the location of a validator gets updated.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ValidatorLocationChangedEvent {
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The previous URL of the validator.
+    pub previous_location: String,
+    /// The new URL of the validator.
+    pub new_location: String,
+}
 
 /// All smart contract errors.
-#[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
-enum CustomContractError {
+#[derive(Debug, PartialEq, Eq, Reject, Serial, Deserial, SchemaType)]
+pub enum CustomContractError {
     /// Failed to parse the parameter.
     #[from(ParseError)]
     ParseParams, // -1
@@ -41,8 +122,19 @@ enum CustomContractError {
     /// Failed to invoke a contract.
     InvokeContractError, // -4
     /// Failed because the address is not a validator.
-    #[allow(dead_code)]
     NotValidator, // -5
+    /// Failed because of unauthorized invoke of the entry point.
+    UnauthorizedAccount, // -6
+    /// Failed because the validator (or its ECDSA public key) is already registered.
+    ValidatorAlreadyRegistered, // -7
+    /// Failed because the maximum number of validators is reached.
+    TooManyValidators, // -8
+    /// Failed because the signature of the validator is invalid.
+    WrongSignature, // -9
+    /// Failed because the signature of the validator is expired.
+    Expired, // -10
+    /// Failed because the signature is not signed with the current nonce of the validator.
+    WrongNonce, // -11
 }
 
 /// Mapping errors related to logging to CustomContractError.
@@ -62,13 +154,333 @@ impl<T> From<CallContractError<T>> for CustomContractError {
     }
 }
 
-/// Init function that creates a new smart contract.
-#[init(contract = "staking_bank")]
+/// Internal function that registers a validator and logs the `ValidatorRegistered` event.
+fn register<S: HasStateApi>(
+    state: &mut State<S>,
+    logger: &mut impl HasLogger,
+    public_key: PublicKeyEd25519,
+    validator_info: ValidatorInfo,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        state.number_of_validators < MAX_NUMBER_OF_VALIDATORS,
+        CustomContractError::TooManyValidators
+    );
+
+    if let Some(ecdsa_public_key) = validator_info.ecdsa_public_key {
+        ensure!(
+            state
+                .ecdsa_validators
+                .insert(ecdsa_public_key, public_key)
+                .is_none(),
+            CustomContractError::ValidatorAlreadyRegistered
+        );
+    }
+
+    ensure!(
+        state
+            .validators
+            .insert(public_key, validator_info.clone())
+            .is_none(),
+        CustomContractError::ValidatorAlreadyRegistered
+    );
+
+    state.number_of_validators += 1;
+
+    logger.log(&Event::ValidatorRegistered(ValidatorRegisteredEvent {
+        public_key,
+        location: validator_info.location,
+        ecdsa_public_key: validator_info.ecdsa_public_key,
+    }))?;
+
+    Ok(())
+}
+
+/// Init function that creates a new smart contract. The initial validators are registered.
+#[init(contract = "staking_bank", event = "Event", enable_logger)]
 fn init<S: HasStateApi>(
-    _ctx: &impl HasInitContext,
-    _state_builder: &mut StateBuilder<S>,
-) -> InitResult<State> {
-    Ok(State {})
+    ctx: &impl HasInitContext,
+    state_builder: &mut StateBuilder<S>,
+    logger: &mut impl HasLogger,
+) -> InitResult<State<S>> {
+    #[allow(unused_mut)]
+    let mut state = State {
+        owner: Address::from(ctx.init_origin()),
+        validators: state_builder.new_map(),
+        ecdsa_validators: state_builder.new_map(),
+        number_of_validators: 0,
+    };
+
+    #[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+    for (public_key, location, ecdsa_public_key) in initial_validators() {
+        register(
+            &mut state,
+            logger,
+            public_key,
+            ValidatorInfo {
+                location: location.to_string(),
+                ecdsa_public_key: Some(ecdsa_public_key),
+                nonce: 0,
+            },
+        )?;
+    }
+
+    #[cfg(not(any(feature = "production", feature = "development", feature = "sandbox")))]
+    let _ = logger;
+
+    Ok(state)
+}
+
+/// The parameter type for the contract function `registerValidator`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct RegisterValidatorParams {
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The URL of the validator.
+    pub location: String,
+    /// The ECDSA public key of the validator (optional).
+    pub ecdsa_public_key: Option<PublicKeyEcdsaSecp256k1>,
+}
+
+/// The owner can register a new validator.
+///
+/// It rejects if:
+/// - Sender is not the owner of this contract.
+/// - It fails to parse the parameter.
+/// - The validator (or its ECDSA public key) is already registered.
+/// - The maximum number of validators is reached.
+#[receive(
+    contract = "staking_bank",
+    name = "registerValidator",
+    parameter = "RegisterValidatorParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn register_validator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let param: RegisterValidatorParams = ctx.parameter_cursor().get()?;
+
+    register(
+        host.state_mut(),
+        logger,
+        param.public_key,
+        ValidatorInfo {
+            location: param.location,
+            ecdsa_public_key: param.ecdsa_public_key,
+            nonce: 0,
+        },
+    )
+}
+
+/// The owner can remove a validator.
+///
+/// It rejects if:
+/// - Sender is not the owner of this contract.
+/// - It fails to parse the parameter.
+/// - The public key is not a validator.
+#[receive(
+    contract = "staking_bank",
+    name = "removeValidator",
+    parameter = "PublicKeyEd25519",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn remove_validator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_eq!(
+        ctx.sender(),
+        host.state().owner,
+        CustomContractError::UnauthorizedAccount
+    );
+
+    let public_key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let state = host.state_mut();
+
+    let validator_info = state
+        .validators
+        .remove_and_get(&public_key)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    if let Some(ecdsa_public_key) = validator_info.ecdsa_public_key {
+        state.ecdsa_validators.remove(&ecdsa_public_key);
+    }
+
+    state.number_of_validators -= 1;
+
+    logger.log(&Event::ValidatorRemoved(ValidatorRemovedEvent {
+        public_key,
+    }))?;
+
+    Ok(())
+}
+
+/// The message that a validator signs to update its own location with the `setValidatorLocation` entry point.
+/// The signature is over the SHA2-256 digest of the serialized message.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct LocationMessage {
+    /// The contract_address that the signature is intended for.
+    pub contract_address: ContractAddress,
+    /// A timestamp to make the signature expire.
+    pub timestamp: Timestamp,
+    /// The nonce of the validator (see `ValidatorInfo`) to prevent replaying the signature.
+    pub nonce: u64,
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The new URL of the validator.
+    pub location: String,
+}
+
+/// The signature of a validator over a `LocationMessage`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct LocationSignature {
+    /// The timestamp of the signed message.
+    pub timestamp: Timestamp,
+    /// The nonce of the signed message.
+    pub nonce: u64,
+    /// The signature of the validator.
+    pub signature: SignatureEd25519,
+}
+
+/// The parameter type for the contract function `setValidatorLocation`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetValidatorLocationParams {
+    /// The signing public key of the validator.
+    pub public_key: PublicKeyEd25519,
+    /// The new URL of the validator.
+    pub location: String,
+    /// The signature of the validator if the validator updates its own location. If `None`, the sender has to be
+    /// the owner of this contract.
+    pub signature: Option<LocationSignature>,
+}
+
+/// The owner or the validator itself can update the location (URL) of a validator, e.g. when the validator moves
+/// its API endpoint. The validator authorizes the update by signing the `LocationMessage` with its signing key; the
+/// sender of the transaction can then be any account. Every update (also by the owner) increments the nonce of the
+/// validator, so that each signature is accepted at most once.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The public key is not a validator.
+/// - No signature is given and the sender is not the owner of this contract.
+/// - The signature is expired, is not signed with the current nonce of the validator, or is invalid.
+#[receive(
+    contract = "staking_bank",
+    name = "setValidatorLocation",
+    parameter = "SetValidatorLocationParams",
+    error = "CustomContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn set_validator_location<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> Result<(), CustomContractError> {
+    let param: SetValidatorLocationParams = ctx.parameter_cursor().get()?;
+
+    match param.signature {
+        None => ensure_eq!(
+            ctx.sender(),
+            host.state().owner,
+            CustomContractError::UnauthorizedAccount
+        ),
+        Some(LocationSignature {
+            timestamp,
+            nonce,
+            signature,
+        }) => {
+            ensure!(
+                timestamp > ctx.metadata().slot_time(),
+                CustomContractError::Expired
+            );
+
+            let validator_info = host
+                .state()
+                .validators
+                .get(&param.public_key)
+                .ok_or(CustomContractError::NotValidator)?;
+
+            ensure_eq!(nonce, validator_info.nonce, CustomContractError::WrongNonce);
+
+            let message_bytes = to_bytes(&LocationMessage {
+                contract_address: ctx.self_address(),
+                timestamp,
+                nonce,
+                public_key: param.public_key,
+                location: param.location.clone(),
+            });
+
+            let message_hash = crypto_primitives.hash_sha2_256(&message_bytes).0;
+
+            ensure!(
+                crypto_primitives.verify_ed25519_signature(
+                    param.public_key,
+                    signature,
+                    &message_hash
+                ),
+                CustomContractError::WrongSignature
+            );
+        }
+    }
+
+    let mut validator_info = host
+        .state_mut()
+        .validators
+        .get_mut(&param.public_key)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    let previous_location = core::mem::replace(&mut validator_info.location, param.location);
+    let new_location = validator_info.location.clone();
+    validator_info.nonce += 1;
+
+    drop(validator_info);
+
+    logger.log(&Event::ValidatorLocationChanged(
+        ValidatorLocationChangedEvent {
+            public_key: param.public_key,
+            previous_location,
+            new_location,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// View function that returns the owner address.
+#[receive(contract = "staking_bank", name = "owner", return_value = "Address")]
+fn owner<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Address> {
+    Ok(host.state().owner)
+}
+
+/// Internal function that returns a boolean if the given public key is a validator.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+fn is_validator<S: HasStateApi>(state: &State<S>, validator: &PublicKeyEd25519) -> bool {
+    state.validators.get(validator).is_some()
+}
+
+/// Internal function that returns the total supply (number of validators * ONE).
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+fn total_supply<S: HasStateApi>(state: &State<S>) -> StakingBalanceAmount {
+    state.number_of_validators * ONE
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `NUMBER_OF_VALIDATORS`.
@@ -76,13 +488,13 @@ fn init<S: HasStateApi>(
 #[receive(
     contract = "staking_bank",
     name = "NUMBER_OF_VALIDATORS",
-    return_value = "u8"
+    return_value = "u16"
 )]
 fn number_of_validators<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<u8> {
-    Ok(NUMBER_OF_VALIDATORS)
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<u16> {
+    Ok(host.state().number_of_validators)
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `TOTAL_SUPPLY`.
@@ -94,9 +506,9 @@ fn number_of_validators<S: HasStateApi>(
 )]
 fn total_supply_1<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
-    Ok(TOTAL_SUPPLY)
+    Ok(total_supply(host.state()))
 }
 
 /// Equivalent to solidity's getter function which is automatically created from the public storage variable `ONE`.
@@ -108,7 +520,7 @@ fn total_supply_1<S: HasStateApi>(
 )]
 fn one<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
     Ok(ONE)
 }
@@ -123,14 +535,14 @@ fn one<S: HasStateApi>(
 )]
 fn balances<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
     let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
 
-    if is_validator(key) {
+    if is_validator(host.state(), &key) {
         Ok(ONE)
     } else {
-        Ok(0u8)
+        Ok(0u16)
     }
 }
 
@@ -144,12 +556,36 @@ fn balances<S: HasStateApi>(
 )]
 fn verify_validators<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<bool> {
     let keys: Vec<PublicKeyEd25519> = ctx.parameter_cursor().get()?;
 
     for validator in keys {
-        if !is_validator(validator) {
+        if !is_validator(host.state(), &validator) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+/// View function that returns a true, if all of the provided ECDSA public keys are validators, otherwise a false.
+/// This function is used if the `umbrella_feeds` contract is initialized with the `EcdsaSecp256k1` signature scheme.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "verifyValidatorsEcdsa",
+    parameter = "Vec<PublicKeyEcdsaSecp256k1>",
+    return_value = "bool"
+)]
+fn verify_validators_ecdsa<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let keys: Vec<PublicKeyEcdsaSecp256k1> = ctx.parameter_cursor().get()?;
+
+    for validator in keys {
+        if host.state().ecdsa_validators.get(&validator).is_none() {
             return Ok(false);
         }
     }
@@ -162,13 +598,13 @@ fn verify_validators<S: HasStateApi>(
 #[receive(
     contract = "staking_bank",
     name = "getNumberOfValidators",
-    return_value = "u8"
+    return_value = "u16"
 )]
 fn get_number_of_validators<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<u8> {
-    Ok(NUMBER_OF_VALIDATORS)
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<u16> {
+    Ok(host.state().number_of_validators)
 }
 
 /// View function that returns the balances of validators.
@@ -180,10 +616,12 @@ fn get_number_of_validators<S: HasStateApi>(
 )]
 fn get_balances<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<Vec<StakingBalanceAmount>> {
-    let mut balances = Vec::with_capacity(NUMBER_OF_VALIDATORS as usize);
-    for _i in 0..NUMBER_OF_VALIDATORS {
+    let number_of_validators = host.state().number_of_validators;
+
+    let mut balances = Vec::with_capacity(number_of_validators as usize);
+    for _i in 0..number_of_validators {
         balances.push(ONE)
     }
 
@@ -195,15 +633,24 @@ fn get_balances<S: HasStateApi>(
 #[receive(
     contract = "staking_bank",
     name = "publicKey",
-    parameter = "u8",
+    parameter = "u16",
     return_value = "PublicKeyEd25519"
 )]
 fn public_key<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<PublicKeyEd25519> {
-    let index: u8 = ctx.parameter_cursor().get()?;
-    Ok(public_keys()[usize::from(index)])
+    let index: u16 = ctx.parameter_cursor().get()?;
+
+    let public_key = host
+        .state()
+        .validators
+        .iter()
+        .nth(usize::from(index))
+        .map(|(public_key, _)| *public_key)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    Ok(public_key)
 }
 
 /// View function that returns the balance of an validator. This is to follow ERC20 interface.
@@ -216,14 +663,14 @@ fn public_key<S: HasStateApi>(
 )]
 fn balance_of<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
     let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
 
-    if is_validator(key) {
+    if is_validator(host.state(), &key) {
         Ok(ONE)
     } else {
-        Ok(0u8)
+        Ok(0u16)
     }
 }
 
@@ -236,16 +683,136 @@ fn balance_of<S: HasStateApi>(
 )]
 fn total_supply_2<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<StakingBalanceAmount> {
-    Ok(TOTAL_SUPPLY)
+    Ok(total_supply(host.state()))
+}
+
+/// View function that returns validator's URL (as well as the inputted public key). The function throws an error if the public key is not a validator.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "validators",
+    parameter = "PublicKeyEd25519",
+    return_value = "(PublicKeyEd25519,String)"
+)]
+fn validators<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<(PublicKeyEd25519, String)> {
+    let id: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    let validator_info = host
+        .state()
+        .validators
+        .get(&id)
+        .ok_or(CustomContractError::NotValidator)?;
+
+    Ok((id, validator_info.location.clone()))
+}
+
+/// View function that returns a true, if the provided public key is a validator, otherwise a false.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "verifyValidator",
+    parameter = "PublicKeyEd25519",
+    return_value = "bool"
+)]
+fn verify_validator<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    let key: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
+
+    Ok(is_validator(host.state(), &key))
+}
+
+/// View function that returns all validators' public keys and URLs (ordered by public key).
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "getValidators",
+    return_value = "Vec<(PublicKeyEd25519,String)>"
+)]
+fn get_validators<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<(PublicKeyEd25519, String)>> {
+    Ok(validator_locations(host.state()))
+}
+
+/// View function that returns all validators' public keys and locations (ordered by public key), so that off-chain
+/// components (e.g. the aggregator of the signatures) can discover the API endpoints of the signers on chain. It
+/// returns the same list as `getValidators` under the name used by the signer discovery.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "getValidatorLocations",
+    return_value = "Vec<(PublicKeyEd25519,String)>"
+)]
+fn get_validator_locations<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<(PublicKeyEd25519, String)>> {
+    Ok(validator_locations(host.state()))
+}
+
+/// Internal function that returns all validators' public keys and locations (ordered by public key).
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+fn validator_locations<S: HasStateApi>(state: &State<S>) -> Vec<(PublicKeyEd25519, String)> {
+    state
+        .validators
+        .iter()
+        .map(|(public_key, validator_info)| (*public_key, validator_info.location.clone()))
+        .collect()
+}
+
+/// View function that returns all validators' public keys and information (URL, ECDSA public key, and nonce, ordered
+/// by public key), e.g. to carry the validators over to a new staking bank when the staking bank is upgraded or to
+/// look up the nonce that a validator has to sign its next location update with.
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "getValidatorInfos",
+    return_value = "Vec<(PublicKeyEd25519,ValidatorInfo)>"
+)]
+fn get_validator_infos<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<(PublicKeyEd25519, ValidatorInfo)>> {
+    Ok(host
+        .state()
+        .validators
+        .iter()
+        .map(|(public_key, validator_info)| (*public_key, validator_info.clone()))
+        .collect())
+}
+
+/// View function that returns all validators' public keys (ordered by public key).
+#[cfg(any(feature = "production", feature = "development", feature = "sandbox"))]
+#[receive(
+    contract = "staking_bank",
+    name = "getPublicKeys",
+    return_value = "Vec<PublicKeyEd25519>"
+)]
+fn get_public_keys<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<PublicKeyEd25519>> {
+    Ok(host
+        .state()
+        .validators
+        .iter()
+        .map(|(public_key, _)| *public_key)
+        .collect())
 }
 
 /// View function that returns the key/name of this contract.
 #[receive(contract = "staking_bank", name = "getName", return_value = "String")]
 fn get_name<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<&'static str> {
     Ok("StakingBank")
 }
@@ -280,7 +847,7 @@ fn upgrade_natively<S: HasStateApi>(
 #[receive(contract = "staking_bank", name = "unregister")]
 fn unregister<S: HasStateApi>(
     _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
 ) -> ReceiveResult<()> {
     // There are no requirements atm
 
@@ -1,6 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
-use crate::{CustomContractError, State};
 use concordium_std::*;
 
 // Production constants and functions
@@ -21,81 +20,91 @@ pub(crate) const VALIDATOR_12: PublicKeyEd25519 = PublicKeyEd25519([12u8; 32]);
 pub(crate) const VALIDATOR_13: PublicKeyEd25519 = PublicKeyEd25519([13u8; 32]);
 pub(crate) const VALIDATOR_14: PublicKeyEd25519 = PublicKeyEd25519([14u8; 32]);
 
-type StakingBalanceAmount = u8;
+// ECDSA (secp256k1) public keys of the validators (same order as above). They are used if the
+// `umbrella_feeds` contract is initialized with the `EcdsaSecp256k1` signature scheme.
+pub(crate) const ECDSA_VALIDATOR_0: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([0u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_1: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([1u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_2: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([2u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_3: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([3u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_4: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([4u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_5: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([5u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_6: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([6u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_7: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([7u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_8: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([8u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_9: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([9u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_10: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([10u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_11: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([11u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_12: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([12u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_13: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([13u8; 33]);
+pub(crate) const ECDSA_VALIDATOR_14: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([14u8; 33]);
 
-/// The number of validators.
-pub(crate) const NUMBER_OF_VALIDATORS: u8 = 15;
-/// total supply = number_of_validators * ONE.
-pub(crate) const TOTAL_SUPPLY: StakingBalanceAmount = 15 * 1u8;
-
-/// Internal function that returns a boolean if the given public key is a validator.
-pub(crate) fn is_validator(validator: PublicKeyEd25519) -> bool {
-    public_keys().contains(&validator)
-}
-
-/// Internal function that returns all validators.
-pub(crate) fn public_keys() -> [PublicKeyEd25519; 15] {
+/// Internal function that returns the initial validators (signing public key, URL, and ECDSA public key).
+/// They are registered in the `validators` map at initialization.
+pub(crate) fn initial_validators() -> [(PublicKeyEd25519, &'static str, PublicKeyEcdsaSecp256k1); 15]
+{
     [
-        VALIDATOR_0,
-        VALIDATOR_1,
-        VALIDATOR_2,
-        VALIDATOR_3,
-        VALIDATOR_4,
-        VALIDATOR_5,
-        VALIDATOR_6,
-        VALIDATOR_7,
-        VALIDATOR_8,
-        VALIDATOR_9,
-        VALIDATOR_10,
-        VALIDATOR_11,
-        VALIDATOR_12,
-        VALIDATOR_13,
-        VALIDATOR_14,
+        (
+            VALIDATOR_0,
+            "https://validator.umb.network",
+            ECDSA_VALIDATOR_0,
+        ),
+        (
+            VALIDATOR_1,
+            "https://validator2.umb.network",
+            ECDSA_VALIDATOR_1,
+        ),
+        (
+            VALIDATOR_2,
+            "https://umbrella.artemahr.tech",
+            ECDSA_VALIDATOR_2,
+        ),
+        (
+            VALIDATOR_3,
+            "https://umb.vtabsolutions.com:3030",
+            ECDSA_VALIDATOR_3,
+        ),
+        (VALIDATOR_4, "https://umb.stakers.world", ECDSA_VALIDATOR_4),
+        (
+            VALIDATOR_5,
+            "https://umbrella.crazywhale.es",
+            ECDSA_VALIDATOR_5,
+        ),
+        (
+            VALIDATOR_6,
+            "https://umbrella-node.gateomega.com",
+            ECDSA_VALIDATOR_6,
+        ),
+        (
+            VALIDATOR_7,
+            "https://umb.anorak.technology",
+            ECDSA_VALIDATOR_7,
+        ),
+        (
+            VALIDATOR_8,
+            "https://umbrella.infstones.io",
+            ECDSA_VALIDATOR_8,
+        ),
+        (VALIDATOR_9, "https://umb.hashquark.io", ECDSA_VALIDATOR_9),
+        (
+            VALIDATOR_10,
+            "http://umbrella.staking4all.org:3000",
+            ECDSA_VALIDATOR_10,
+        ),
+        (
+            VALIDATOR_11,
+            "https://umbrella-api.validatrium.club",
+            ECDSA_VALIDATOR_11,
+        ),
+        (VALIDATOR_12, "http://5.161.78.230:3000", ECDSA_VALIDATOR_12),
+        (
+            VALIDATOR_13,
+            "https://umbnode.blockchainliverpool.com",
+            ECDSA_VALIDATOR_13,
+        ),
+        (
+            VALIDATOR_14,
+            "https://umb-api.staking.rocks",
+            ECDSA_VALIDATOR_14,
+        ),
     ]
 }
-
-/// View function that returns validator's URL (as well as the inputted public key). The function throws an error if the public key is not a validator.
-#[receive(
-    contract = "staking_bank",
-    name = "validators",
-    parameter = "PublicKeyEd25519",
-    return_value = "(PublicKeyEd25519,String)"
-)]
-pub(crate) fn validators<S: HasStateApi>(
-    ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<(PublicKeyEd25519, String)> {
-    let id: PublicKeyEd25519 = ctx.parameter_cursor().get()?;
-
-    match id {
-        VALIDATOR_0 => Ok((id, "https://validator.umb.network".to_string())),
-        VALIDATOR_1 => Ok((id, "https://validator2.umb.network".to_string())),
-        VALIDATOR_2 => Ok((id, "https://umbrella.artemahr.tech".to_string())),
-        VALIDATOR_3 => Ok((id, "https://umb.vtabsolutions.com:3030".to_string())),
-        VALIDATOR_4 => Ok((id, "https://umb.stakers.world".to_string())),
-        VALIDATOR_5 => Ok((id, "https://umbrella.crazywhale.es".to_string())),
-        VALIDATOR_6 => Ok((id, "https://umbrella-node.gateomega.com".to_string())),
-        VALIDATOR_7 => Ok((id, "https://umb.anorak.technology".to_string())),
-        VALIDATOR_8 => Ok((id, "https://umbrella.infstones.io".to_string())),
-        VALIDATOR_9 => Ok((id, "https://umb.hashquark.io".to_string())),
-        VALIDATOR_10 => Ok((id, "http://umbrella.staking4all.org:3000".to_string())),
-        VALIDATOR_11 => Ok((id, "https://umbrella-api.validatrium.club".to_string())),
-        VALIDATOR_12 => Ok((id, "http://5.161.78.230:3000".to_string())),
-        VALIDATOR_13 => Ok((id, "https://umbnode.blockchainliverpool.com".to_string())),
-        VALIDATOR_14 => Ok((id, "https://umb-api.staking.rocks".to_string())),
-        _ => bail!(CustomContractError::NotValidator.into()),
-    }
-}
-
-/// View function that returns all validators' public keys.
-#[receive(
-    contract = "staking_bank",
-    name = "getPublicKeys",
-    return_value = "[PublicKeyEd25519;15]"
-)]
-pub(crate) fn get_public_keys<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State, StateApiType = S>,
-) -> ReceiveResult<[PublicKeyEd25519; 15]> {
-    Ok(public_keys())
-}
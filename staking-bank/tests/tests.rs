@@ -1,7 +1,14 @@
 use concordium_smart_contract_testing::*;
-use concordium_std::{HashSha2256, PublicKeyEd25519};
+use concordium_std::{
+    HashSha2256, PublicKeyEcdsaSecp256k1, PublicKeyEd25519, SignatureEd25519, Timestamp,
+};
+use staking_bank::{
+    Event, LocationSignature, RegisterValidatorParams, SetValidatorLocationParams, ValidatorInfo,
+    ValidatorLocationChangedEvent, ValidatorRegisteredEvent, ValidatorRemovedEvent,
+};
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([77u8; 32]);
+const ACC_ADDR_OTHER: AccountAddress = AccountAddress([1u8; 32]);
 
 // ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
 // Private key: 8ECA45107A878FB879B84401084B55AD4919FC0F7D14E8915D8A5989B1AE1C01
@@ -22,6 +29,22 @@ const VALIDATOR_DOES_NOT_EXIST: PublicKeyEd25519 = PublicKeyEd25519([
     243, 162, 209, 78, 136, 94, 127, 247, 21, 222, 221,
 ]);
 
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 1F2A8E3C6D5B4A79880716253443526170E8D9C6B5A4F3E2D1C0B9A897867564
+const ECDSA_VALIDATOR_0: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([
+    2, 122, 114, 246, 115, 219, 127, 146, 184, 35, 59, 96, 60, 177, 237, 120, 241, 72, 252, 51,
+    106, 192, 49, 204, 84, 255, 146, 218, 148, 9, 105, 124, 224,
+]);
+
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 7B6A594837261504F3E2D1C0B9A8978675645342312F1E0D0C0B0A0908070605
+const ECDSA_VALIDATOR_1: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([
+    2, 231, 38, 120, 58, 92, 141, 19, 123, 165, 152, 190, 30, 234, 22, 28, 98, 136, 124, 106, 12,
+    33, 234, 80, 46, 230, 4, 117, 46, 81, 219, 7, 143,
+]);
+
+const ECDSA_VALIDATOR_DOES_NOT_EXIST: PublicKeyEcdsaSecp256k1 = PublicKeyEcdsaSecp256k1([3u8; 33]);
+
 const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(1000);
 
 const KEY_HASH_1: HashSha2256 = HashSha2256([
@@ -34,6 +57,7 @@ fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
 
     // Creating accounts.
     chain.create_account(Account::new(ACC_ADDR_OWNER, ACC_INITIAL_BALANCE));
+    chain.create_account(Account::new(ACC_ADDR_OTHER, ACC_INITIAL_BALANCE));
 
     // Deploying 'staking bank' contract
 
@@ -63,6 +87,55 @@ fn setup_chain_and_contract() -> (Chain, ContractInitSuccess) {
     (chain, initialization_staking_bank)
 }
 
+fn reject_code(error: &ContractInvokeError) -> i32 {
+    match error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => code,
+        _ => panic!("The contract update should be rejected by the contract"),
+    }
+}
+
+fn get_number_of_validators(chain: &Chain, staking_bank: ContractAddress) -> u16 {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getNumberOfValidators".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query value");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+fn get_total_supply(chain: &Chain, staking_bank: ContractAddress) -> u16 {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.totalSupply".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query value");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
 #[test]
 fn test_verify_validators() {
     let (chain, initialization_staking_bank) = setup_chain_and_contract();
@@ -88,7 +161,7 @@ fn test_verify_validators() {
 
     let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, false);
+    assert!(!value);
 
     let invoke = chain
         .contract_invoke(
@@ -109,7 +182,59 @@ fn test_verify_validators() {
 
     let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, true);
+    assert!(value);
+}
+
+#[test]
+fn test_verify_validators_ecdsa() {
+    let (chain, initialization_staking_bank) = setup_chain_and_contract();
+
+    // Checking verifyValidatorsEcdsa.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsEcdsa".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![
+                    ECDSA_VALIDATOR_DOES_NOT_EXIST,
+                    ECDSA_VALIDATOR_0,
+                ])
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(!value);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsEcdsa".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![ECDSA_VALIDATOR_0, ECDSA_VALIDATOR_1])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(value);
 }
 
 #[test]
@@ -133,7 +258,7 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 1);
 
@@ -152,7 +277,7 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 0);
 
@@ -174,9 +299,9 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: Vec<u8> = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: Vec<u16> = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    let one = 1u8;
+    let one = 1u16;
 
     assert_eq!(value, vec![one, one]);
 
@@ -197,9 +322,9 @@ fn test_balances() {
         )
         .expect("Should be able to query the balance");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, 1u8);
+    assert_eq!(value, 1u16);
 }
 
 #[test]
@@ -244,7 +369,7 @@ fn test_validators() {
                 amount: Amount::zero(),
                 address: initialization_staking_bank.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked("staking_bank.publicKey".to_string()),
-                message: OwnedParameter::from_serial(&0u8)
+                message: OwnedParameter::from_serial(&0u16)
                     .expect("Should be a valid inut parameter"),
             },
         )
@@ -254,6 +379,103 @@ fn test_validators() {
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, VALIDATOR_0);
+
+    // Checking verifyValidator.
+
+    for (key, expected) in [(VALIDATOR_0, true), (VALIDATOR_DOES_NOT_EXIST, false)] {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_staking_bank.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "staking_bank.verifyValidator".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&key)
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to query if address is validator");
+
+        let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        assert_eq!(value, expected);
+    }
+
+    // Checking getValidators.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getValidators".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query validators");
+
+    let value: Vec<(PublicKeyEd25519, String)> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![
+            (VALIDATOR_0, String::from("https://validator.umb.network")),
+            (VALIDATOR_1, String::from("https://validator2.umb.network")),
+        ]
+    );
+
+    // Checking getValidatorInfos.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getValidatorInfos".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the validator infos");
+
+    let value: Vec<(PublicKeyEd25519, ValidatorInfo)> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        value,
+        vec![
+            (
+                VALIDATOR_0,
+                ValidatorInfo {
+                    location: String::from("https://validator.umb.network"),
+                    ecdsa_public_key: Some(ECDSA_VALIDATOR_0),
+                    nonce: 0,
+                }
+            ),
+            (
+                VALIDATOR_1,
+                ValidatorInfo {
+                    location: String::from("https://validator2.umb.network"),
+                    ecdsa_public_key: Some(ECDSA_VALIDATOR_1),
+                    nonce: 0,
+                }
+            ),
+        ]
+    );
 }
 
 #[test]
@@ -304,7 +526,7 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 2);
 
@@ -327,7 +549,7 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 2);
 
@@ -350,7 +572,7 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 2);
 
@@ -373,7 +595,7 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 2);
 
@@ -394,7 +616,7 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(value, 1);
 
@@ -417,8 +639,556 @@ fn test_init() {
         )
         .expect("Should be able to query value");
 
-    let value: [PublicKeyEd25519; 2] =
+    let value: Vec<PublicKeyEd25519> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, vec![VALIDATOR_0, VALIDATOR_1]);
+}
+
+#[test]
+fn test_register_and_remove_validator() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    // Checking the events logged at initialization.
+
+    let events: Vec<Event> = initialization_staking_bank
+        .events
+        .iter()
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![
+            Event::ValidatorRegistered(ValidatorRegisteredEvent {
+                public_key: VALIDATOR_0,
+                location: String::from("https://validator.umb.network"),
+                ecdsa_public_key: Some(ECDSA_VALIDATOR_0),
+            }),
+            Event::ValidatorRegistered(ValidatorRegisteredEvent {
+                public_key: VALIDATOR_1,
+                location: String::from("https://validator2.umb.network"),
+                ecdsa_public_key: Some(ECDSA_VALIDATOR_1),
+            }),
+        ],
+        "Events are wrong"
+    );
+
+    let register_payload = UpdateContractPayload {
+        amount: Amount::zero(),
+        address: staking_bank,
+        receive_name: OwnedReceiveName::new_unchecked("staking_bank.registerValidator".to_string()),
+        message: OwnedParameter::from_serial(&RegisterValidatorParams {
+            public_key: VALIDATOR_DOES_NOT_EXIST,
+            location: String::from("https://validator3.umb.network"),
+            ecdsa_public_key: Some(ECDSA_VALIDATOR_DOES_NOT_EXIST),
+        })
+        .expect("Should be a valid inut parameter"),
+    };
+
+    // Checking that only the owner can register a validator.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            register_payload.clone(),
+        )
+        .expect_err("Should fail to registerValidator");
+
+    assert_eq!(reject_code(&update), -6);
+
+    // Registering a validator.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            register_payload.clone(),
+        )
+        .expect("Should be able to registerValidator");
+
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::ValidatorRegistered(ValidatorRegisteredEvent {
+            public_key: VALIDATOR_DOES_NOT_EXIST,
+            location: String::from("https://validator3.umb.network"),
+            ecdsa_public_key: Some(ECDSA_VALIDATOR_DOES_NOT_EXIST),
+        })],
+        "Events are wrong"
+    );
+
+    assert_eq!(get_number_of_validators(&chain, staking_bank), 3);
+    assert_eq!(get_total_supply(&chain, staking_bank), 3);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsEcdsa".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![ECDSA_VALIDATOR_DOES_NOT_EXIST])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(value);
+
+    // Checking that a validator can not be registered twice.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            register_payload,
+        )
+        .expect_err("Should fail to registerValidator");
+
+    assert_eq!(reject_code(&update), -7);
+
+    let remove_payload = UpdateContractPayload {
+        amount: Amount::zero(),
+        address: staking_bank,
+        receive_name: OwnedReceiveName::new_unchecked("staking_bank.removeValidator".to_string()),
+        message: OwnedParameter::from_serial(&VALIDATOR_0)
+            .expect("Should be a valid inut parameter"),
+    };
+
+    // Checking that only the owner can remove a validator.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            remove_payload.clone(),
+        )
+        .expect_err("Should fail to removeValidator");
+
+    assert_eq!(reject_code(&update), -6);
+
+    // Removing a validator.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            remove_payload.clone(),
+        )
+        .expect("Should be able to removeValidator");
+
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::ValidatorRemoved(ValidatorRemovedEvent {
+            public_key: VALIDATOR_0,
+        })],
+        "Events are wrong"
+    );
+
+    assert_eq!(get_number_of_validators(&chain, staking_bank), 2);
+    assert_eq!(get_total_supply(&chain, staking_bank), 2);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidators".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![VALIDATOR_0])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(!value);
+
+    // Checking that the ECDSA public key of the removed validator is removed as well.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.verifyValidatorsEcdsa".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![ECDSA_VALIDATOR_0])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query if address is validator");
+
+    let value: bool = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert!(!value);
+
+    // Checking that a validator that is not registered can not be removed.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            remove_payload,
+        )
+        .expect_err("Should fail to removeValidator");
+
+    assert_eq!(reject_code(&update), -5);
+}
+
+fn get_validator_locations(
+    chain: &Chain,
+    staking_bank: ContractAddress,
+    view: &str,
+) -> Vec<(PublicKeyEd25519, String)> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(format!("staking_bank.{view}")),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the validator locations");
+
+    from_bytes(&invoke.return_value).expect("Should return a valid result")
+}
+
+fn get_validator_nonces(
+    chain: &Chain,
+    staking_bank: ContractAddress,
+) -> Vec<(PublicKeyEd25519, u64)> {
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: staking_bank,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getValidatorInfos".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the validator infos");
+
+    let validator_infos: Vec<(PublicKeyEd25519, ValidatorInfo)> =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(value, [VALIDATOR_0, VALIDATOR_1]);
+    validator_infos
+        .into_iter()
+        .map(|(public_key, validator_info)| (public_key, validator_info.nonce))
+        .collect()
+}
+
+#[test]
+fn test_set_validator_location() {
+    let (mut chain, initialization_staking_bank) = setup_chain_and_contract();
+    let staking_bank = initialization_staking_bank.contract_address;
+
+    assert_eq!(
+        get_validator_locations(&chain, staking_bank, "getValidatorLocations"),
+        vec![
+            (VALIDATOR_0, String::from("https://validator.umb.network")),
+            (VALIDATOR_1, String::from("https://validator2.umb.network")),
+        ]
+    );
+    assert_eq!(
+        get_validator_nonces(&chain, staking_bank),
+        vec![(VALIDATOR_0, 0), (VALIDATOR_1, 0)]
+    );
+
+    let set_location_payload = |params: &SetValidatorLocationParams| UpdateContractPayload {
+        amount: Amount::zero(),
+        address: staking_bank,
+        receive_name: OwnedReceiveName::new_unchecked(
+            "staking_bank.setValidatorLocation".to_string(),
+        ),
+        message: OwnedParameter::from_serial(params).expect("Should be a valid inut parameter"),
+    };
+
+    let set_location = |chain: &mut Chain, params: &SetValidatorLocationParams| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OTHER,
+            Address::Account(ACC_ADDR_OTHER),
+            Energy::from(10000),
+            set_location_payload(params),
+        )
+    };
+
+    let owner_params = SetValidatorLocationParams {
+        public_key: VALIDATOR_1,
+        location: String::from("https://validator2.umb.network/v2"),
+        signature: None,
+    };
+
+    // Checking that only the owner can set the location without a signature.
+
+    let update =
+        set_location(&mut chain, &owner_params).expect_err("Should fail to setValidatorLocation");
+
+    assert_eq!(reject_code(&update), -6);
+
+    // Setting the location as the owner (which increments the nonce as well).
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            set_location_payload(&owner_params),
+        )
+        .expect("Should be able to setValidatorLocation");
+
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::ValidatorLocationChanged(
+            ValidatorLocationChangedEvent {
+                public_key: VALIDATOR_1,
+                previous_location: String::from("https://validator2.umb.network"),
+                new_location: String::from("https://validator2.umb.network/v2"),
+            }
+        )],
+        "Events are wrong"
+    );
+
+    // Checking that the location of a validator that is not registered can not be set.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            set_location_payload(&SetValidatorLocationParams {
+                public_key: VALIDATOR_DOES_NOT_EXIST,
+                location: String::from("https://validator3.umb.network"),
+                signature: None,
+            }),
+        )
+        .expect_err("Should fail to setValidatorLocation");
+
+    assert_eq!(reject_code(&update), -5);
+
+    // Setting the location as the validator itself (sent by any account). The signature of `VALIDATOR_0` is over
+    // the SHA2-256 digest of the serialized `LocationMessage` for the contract `<0,0>`, the timestamp 10000, and the
+    // nonce 0.
+
+    let signature = SignatureEd25519([
+        89, 7, 2, 134, 145, 66, 210, 192, 76, 232, 237, 148, 133, 196, 94, 83, 231, 71, 30, 244,
+        62, 189, 11, 227, 89, 243, 236, 169, 28, 178, 251, 58, 27, 129, 126, 59, 97, 74, 228, 114,
+        173, 129, 238, 28, 67, 248, 45, 66, 13, 47, 50, 164, 194, 95, 116, 57, 192, 58, 90, 135,
+        145, 224, 19, 11,
+    ]);
+
+    let validator_params = SetValidatorLocationParams {
+        public_key: VALIDATOR_0,
+        location: String::from("https://validator.umb.network/v2"),
+        signature: Some(LocationSignature {
+            timestamp: Timestamp::from_timestamp_millis(10000),
+            nonce: 0,
+            signature,
+        }),
+    };
+
+    let update = set_location(&mut chain, &validator_params)
+        .expect("Should be able to setValidatorLocation");
+
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::ValidatorLocationChanged(
+            ValidatorLocationChangedEvent {
+                public_key: VALIDATOR_0,
+                previous_location: String::from("https://validator.umb.network"),
+                new_location: String::from("https://validator.umb.network/v2"),
+            }
+        )],
+        "Events are wrong"
+    );
+
+    let locations = get_validator_locations(&chain, staking_bank, "getValidatorLocations");
+
+    assert_eq!(
+        locations,
+        vec![
+            (
+                VALIDATOR_0,
+                String::from("https://validator.umb.network/v2")
+            ),
+            (
+                VALIDATOR_1,
+                String::from("https://validator2.umb.network/v2")
+            ),
+        ]
+    );
+    assert_eq!(
+        locations,
+        get_validator_locations(&chain, staking_bank, "getValidators")
+    );
+    assert_eq!(
+        get_validator_nonces(&chain, staking_bank),
+        vec![(VALIDATOR_0, 1), (VALIDATOR_1, 1)]
+    );
+
+    // Checking that the signature can not be replayed: the nonce 0 is used up, and the signature is not valid for
+    // the current nonce 1.
+
+    let update = set_location(&mut chain, &validator_params)
+        .expect_err("Should fail to replay setValidatorLocation");
+
+    // WrongNonce
+    assert_eq!(reject_code(&update), -11);
+
+    let update = set_location(
+        &mut chain,
+        &SetValidatorLocationParams {
+            signature: Some(LocationSignature {
+                timestamp: Timestamp::from_timestamp_millis(10000),
+                nonce: 1,
+                signature,
+            }),
+            ..validator_params
+        },
+    )
+    .expect_err("Should fail to replay setValidatorLocation");
+
+    // WrongSignature
+    assert_eq!(reject_code(&update), -9);
+
+    // Checking that the signature is not valid for another location or another validator.
+
+    for (public_key, location) in [
+        (VALIDATOR_0, "https://attacker.example"),
+        (VALIDATOR_1, "https://validator.umb.network/v2"),
+    ] {
+        let update = set_location(
+            &mut chain,
+            &SetValidatorLocationParams {
+                public_key,
+                location: String::from(location),
+                signature: Some(LocationSignature {
+                    timestamp: Timestamp::from_timestamp_millis(10000),
+                    nonce: 1,
+                    signature,
+                }),
+            },
+        )
+        .expect_err("Should fail to setValidatorLocation");
+
+        assert_eq!(reject_code(&update), -9);
+    }
+
+    // Checking that an expired signature is rejected.
+
+    let update = set_location(
+        &mut chain,
+        &SetValidatorLocationParams {
+            public_key: VALIDATOR_0,
+            location: String::from("https://validator.umb.network/v3"),
+            signature: Some(LocationSignature {
+                timestamp: Timestamp::from_timestamp_millis(0),
+                nonce: 1,
+                signature,
+            }),
+        },
+    )
+    .expect_err("Should fail to setValidatorLocation");
+
+    assert_eq!(reject_code(&update), -10);
+
+    // Setting the location again with a signature over the nonce 1.
+
+    let signature = SignatureEd25519([
+        22, 179, 104, 167, 155, 42, 235, 175, 85, 41, 49, 181, 75, 203, 108, 214, 95, 240, 107, 0,
+        234, 106, 239, 40, 147, 18, 246, 236, 164, 168, 91, 113, 233, 75, 86, 108, 63, 201, 129,
+        10, 212, 197, 138, 219, 111, 158, 199, 189, 53, 59, 60, 99, 161, 89, 78, 159, 224, 140, 85,
+        250, 15, 229, 102, 13,
+    ]);
+
+    let _update = set_location(
+        &mut chain,
+        &SetValidatorLocationParams {
+            public_key: VALIDATOR_0,
+            location: String::from("https://validator.umb.network/v3"),
+            signature: Some(LocationSignature {
+                timestamp: Timestamp::from_timestamp_millis(10000),
+                nonce: 1,
+                signature,
+            }),
+        },
+    )
+    .expect("Should be able to setValidatorLocation");
+
+    assert_eq!(
+        get_validator_locations(&chain, staking_bank, "getValidatorLocations")[0],
+        (
+            VALIDATOR_0,
+            String::from("https://validator.umb.network/v3")
+        )
+    );
+    assert_eq!(
+        get_validator_nonces(&chain, staking_bank)[0],
+        (VALIDATOR_0, 2)
+    );
 }
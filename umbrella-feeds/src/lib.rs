@@ -10,11 +10,37 @@
 use concordium_std::*;
 use core::fmt::Debug;
 
+/// Helpers for the integration tests to sign price feed updates (see the `dev` feature).
+#[cfg(feature = "dev")]
+pub mod test_harness;
+
+/// The maximum number of keys that can be queried in one invoke of the `getManyPriceData` and
+/// `getManyPriceDataRaw` entry points (to avoid energy exhaustion).
+pub const MAX_KEYS: usize = 100;
+
+/// The maximum number of decimals of the prices stored in the contract (more decimals are meaningless for any real price).
+pub const MAX_DECIMALS: u8 = 30;
+
+/// The human-readable name of an instance initialized without the `name` field (returned by the `getDeploymentName` entry point).
+pub const DEFAULT_DEPLOYMENT_NAME: &str = "UmbrellaFeeds";
+
+/// The chain tag of an instance initialized without the `chain_id` field (returned by the `getChainId` entry point).
+pub const DEFAULT_CHAIN_ID: &str = "concordium";
+
+/// The maximum number of seconds that the timestamp of submitted price data can be ahead of the block time for an
+/// instance initialized without the `max_future_skew` field (returned by the `maxFutureSkew` entry point).
+pub const DEFAULT_MAX_FUTURE_SKEW: u64 = 300;
+
+/// The maximum heartbeat in seconds (7 days) that can be configured for a price feed with the `setHeartbeat` entry point.
+pub const MAX_HEARTBEAT: u64 = 7 * 24 * 60 * 60;
+
 #[derive(Serialize, SchemaType, Copy, Clone, Debug, PartialOrd, Ord, PartialEq, Eq)]
 pub struct PriceData {
     /// This is a placeholder, that can be used for some additional data.
     pub data: u8,
     /// The heartbeat specifies the interval in seconds that the price data will be refreshed in case the price stays flat.
+    /// The `getPrice` entry point rejects price data that is older than its heartbeat. The `update` entry point rejects
+    /// price data with a zero heartbeat.
     /// ATTENTION: u64 is used here instead of u24 (different from the original solidity smart contracts).
     pub heartbeat: u64,
     /// It is the time the validators run consensus to decide on the price data.
@@ -23,7 +49,41 @@ pub struct PriceData {
     pub price: u128,
 }
 
-#[derive(Serial, DeserialWithState)]
+impl PriceData {
+    /// The zeroed price data returned by the `getPriceDataRaw` and `getManyPriceDataRawWithBlockTime` entry points for a
+    /// price feed that does not exist.
+    pub fn zero() -> PriceData {
+        PriceData {
+            data: 0,
+            heartbeat: 0,
+            timestamp: Timestamp::from_timestamp_millis(0),
+            price: 0,
+        }
+    }
+}
+
+/// The signature scheme used by the validators to sign the `Message` of a price feed update.
+/// The message digest that is signed differs per scheme:
+/// - `Ed25519`: Ed25519 signatures over the SHA2-256 digest of the serialized `Message`.
+/// - `EcdsaSecp256k1`: ECDSA (secp256k1) signatures over the Keccak-256 digest of the serialized `Message`.
+#[derive(Serialize, SchemaType, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum SignatureScheme {
+    #[default]
+    Ed25519,
+    EcdsaSecp256k1,
+}
+
+/// The behavior of the `update` entry point if a submitted price data is not strictly newer than the stored price data:
+/// - `Reject`: The whole update is rejected with the `OldData` error.
+/// - `Skip`: The price data is skipped, while the other (newer) price data of the update are stored.
+#[derive(Serialize, SchemaType, Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum OldDataPolicy {
+    #[default]
+    Reject,
+    Skip,
+}
+
+#[derive(Serial)]
 #[concordium(state_parameter = "S")]
 struct State<S> {
     /// Contract deployment time.
@@ -33,17 +93,301 @@ struct State<S> {
     /// StakingBank contract where list of validators is stored.
     staking_bank: ContractAddress,
     /// Minimal number of signatures required for accepting price submission (Proof-of-Authority = PoA).
+    /// It is at most the (`u16`) number of validators of the staking bank.
     required_signatures: u16,
     /// Decimals for prices stored in this contract.
     decimals: u8,
     /// Map of all prices stored in this contract. It maps from the key to PriceData. The key for the map is the string of the feed name.
     /// E.g. for the "ETH-USDC" feed, the key will be "ETH-USDC".
     prices: StateMap<String, PriceData, S>,
+    /// The signature scheme used by the validators to sign the price feed updates.
+    signature_scheme: SignatureScheme,
+    /// The behavior of the `update` entry point for price data that is not strictly newer than the stored price data.
+    old_data_policy: OldDataPolicy,
+    /// If `true`, the `update` entry point rejects all price submissions (e.g. during an incident). Reads are not affected.
+    paused: bool,
+    /// Per-feed overrides of the `decimals`, set with the `setFeedDecimals` entry point. It maps from the key of the
+    /// price feed to its decimals (stored alongside the `prices`, so that only the looked up override is loaded).
+    /// Price feeds without an override use the `decimals` of the contract.
+    feed_decimals: StateMap<String, u8, S>,
+    /// Reentrancy guard. It is `true` while the `update` entry point is executed, so that a nested invoke of the
+    /// `update` entry point (e.g. by the staking bank while the signers are verified) is rejected.
+    updating: bool,
+    /// The human-readable name of this instance (e.g. "UmbrellaFeeds Mainnet"), set at init. Off-chain aggregators
+    /// use it to label the instance. It is not the key/name under which the registry stores this contract (see `getName`).
+    name: String,
+    /// The configured chain tag (e.g. "concordium-mainnet"), set at init, that distinguishes deployments on different networks.
+    chain_id: String,
+    /// Per-feed maximum deviations in basis points (1/100 of a percent), set with the `setMaxDeviation` entry point. It maps
+    /// from the key of the price feed to the maximum deviation of a new price from the stored price (stored alongside the
+    /// `prices`, so that the `update` entry point only loads the entries of the updated price feeds). Price feeds without
+    /// an entry are not limited.
+    max_deviations: StateMap<String, u16, S>,
+    /// The code version (e.g. the git commit) of the contracts this instance was built from, set at init.
+    code_version: String,
+    /// If `true`, the sender of an `update` has to be a validator, i.e. an account with an (Ed25519) account key
+    /// that is registered as a validator in the `staking_bank` contract. Set at init.
+    restrict_submitter: bool,
+    /// The maximum number of seconds that the timestamp of submitted price data can be ahead of the block time, set at init.
+    max_future_skew: u64,
+    /// Per-feed heartbeats in seconds, set with the `setHeartbeat` entry point. It maps from the key of the price feed to
+    /// its heartbeat, which takes precedence over the heartbeat of the stored price data (see `effective_heartbeat`).
+    /// Stored alongside the `prices`, so that only the heartbeats of the queried price feeds are loaded.
+    feed_heartbeats: StateMap<String, u64, S>,
+}
+
+impl<S: HasStateApi> State<S> {
+    /// Returns the effective decimals of the price feed: its override if set, otherwise the `decimals` of the contract.
+    fn effective_decimals(&self, key: &String) -> u8 {
+        self.feed_decimals
+            .get(key)
+            .map_or(self.decimals, |decimals| *decimals)
+    }
+
+    /// Returns the effective heartbeat of the price feed: its configured heartbeat if set, otherwise the heartbeat of
+    /// the stored price data.
+    fn effective_heartbeat(&self, key: &String, price_data: &PriceData) -> u64 {
+        self.feed_heartbeats
+            .get(key)
+            .map_or(price_data.heartbeat, |heartbeat| *heartbeat)
+    }
+}
+
+/// Returns `true` if the `new_price` differs from the `stored_price` by more than `max_deviation_bps` basis points of the
+/// `stored_price`, i.e. if `|new_price - stored_price| * 10000 > stored_price * max_deviation_bps`.
+/// The right-hand side is divided by 10000 before the comparison (exactly, without the overflow of the multiplication).
+pub fn exceeds_max_deviation(stored_price: u128, new_price: u128, max_deviation_bps: u16) -> bool {
+    let bps = u128::from(max_deviation_bps);
+
+    let max_difference = (stored_price / 10000)
+        .saturating_mul(bps)
+        .saturating_add(stored_price % 10000 * bps / 10000);
+
+    stored_price.abs_diff(new_price) > max_difference
+}
+
+/// A reader that yields the byte read ahead by `get_or_default_at_end` before the remaining bytes of the source.
+struct ReadAhead<'a, R> {
+    byte: Option<u8>,
+    source: &'a mut R,
+}
+
+impl<R: Read> Read for ReadAhead<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> ParseResult<usize> {
+        match (self.byte, buf.first_mut()) {
+            (Some(byte), Some(first)) => {
+                *first = byte;
+                self.byte = None;
+                Ok(1)
+            }
+            _ => self.source.read(buf),
+        }
+    }
+}
+
+/// Helper function that reads a field that was added at the end of the state (or the init parameter) later. The `default` is only used if the
+/// source is at its end (i.e. the field is missing). Any other parse error (e.g. a truncated or malformed field) is
+/// returned, so that the following fields are not read from a wrong offset.
+fn get_or_default_at_end<T: Deserial, R: Read>(
+    source: &mut R,
+    default: impl FnOnce() -> T,
+) -> ParseResult<T> {
+    let mut byte = [0u8; 1];
+
+    if source.read(&mut byte)? == 0 {
+        return Ok(default());
+    }
+
+    T::deserial(&mut ReadAhead {
+        byte: Some(byte[0]),
+        source,
+    })
+}
+
+/// Helper function that reads a `StateMap` that was added at the end of the state later. If the source is at its end
+/// (i.e. the field is missing), a new empty map is created in the state. Any other parse error is returned.
+fn get_map_or_new_at_end<K, V, S: HasStateApi, R: Read>(
+    state: &S,
+    source: &mut R,
+) -> ParseResult<StateMap<K, V, S>> {
+    let mut byte = [0u8; 1];
+
+    if source.read(&mut byte)? == 0 {
+        return Ok(StateBuilder::open(state.clone()).new_map());
+    }
+
+    StateMap::deserial_with_state(
+        state,
+        &mut ReadAhead {
+            byte: Some(byte[0]),
+            source,
+        },
+    )
+}
+
+/// Custom deserialization of the state, so that the state of a contract that was initialized before the
+/// `signature_scheme`/`old_data_policy`/`paused`/`feed_decimals`/`updating`/`name`/`chain_id`/`max_deviations`/`code_version`/`restrict_submitter`/`max_future_skew`/`feed_heartbeats` fields were added (the fields are missing at the end of the state) can still be read.
+impl<S: HasStateApi> DeserialWithState<S> for State<S> {
+    fn deserial_with_state<R: Read>(state: &S, source: &mut R) -> ParseResult<Self> {
+        let deployed_at = source.get()?;
+        let registry = source.get()?;
+        let staking_bank = source.get()?;
+        let required_signatures = source.get()?;
+        let decimals = source.get()?;
+        let prices = StateMap::deserial_with_state(state, source)?;
+        let signature_scheme = get_or_default_at_end(source, Default::default)?;
+        let old_data_policy = get_or_default_at_end(source, Default::default)?;
+        let paused = get_or_default_at_end(source, Default::default)?;
+        let feed_decimals = get_map_or_new_at_end(state, source)?;
+        let updating = get_or_default_at_end(source, Default::default)?;
+        let name = get_or_default_at_end(source, || DEFAULT_DEPLOYMENT_NAME.to_string())?;
+        let chain_id = get_or_default_at_end(source, || DEFAULT_CHAIN_ID.to_string())?;
+        let max_deviations = get_map_or_new_at_end(state, source)?;
+        let code_version = get_or_default_at_end(source, Default::default)?;
+        let restrict_submitter = get_or_default_at_end(source, Default::default)?;
+        let max_future_skew = get_or_default_at_end(source, || DEFAULT_MAX_FUTURE_SKEW)?;
+        let feed_heartbeats = get_map_or_new_at_end(state, source)?;
+
+        Ok(State {
+            deployed_at,
+            registry,
+            staking_bank,
+            required_signatures,
+            decimals,
+            prices,
+            signature_scheme,
+            old_data_policy,
+            paused,
+            feed_decimals,
+            updating,
+            name,
+            chain_id,
+            max_deviations,
+            code_version,
+            restrict_submitter,
+            max_future_skew,
+            feed_heartbeats,
+        })
+    }
+}
+
+/// Tagged events to be serialized for the event log.
+/// The tag of each event is stable. New events are added with new tags, so that off-chain parsers
+/// (e.g. indexers) keep working.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+#[concordium(repr(u8))]
+pub enum Event {
+    /// The event tracks whenever the required signatures get changed.
+    #[concordium(tag = 0)]
+    RequiredSignaturesChanged(RequiredSignaturesChangedEvent),
+    /// The event tracks whenever the contract gets paused.
+    #[concordium(tag = 1)]
+    Paused(PausedEvent),
+    /// The event tracks whenever the contract gets unpaused.
+    #[concordium(tag = 2)]
+    Unpaused(UnpausedEvent),
+    /// The event tracks whenever the decimals of a price feed get changed.
+    #[concordium(tag = 3)]
+    FeedDecimalsChanged(FeedDecimalsChangedEvent),
+    /// The event tracks whenever the maximum deviation of a price feed gets changed.
+    #[concordium(tag = 4)]
+    MaxDeviationChanged(MaxDeviationChangedEvent),
+    /// The event tracks whenever a new price is not stored because it exceeds the maximum deviation of its price feed.
+    #[concordium(tag = 5)]
+    DeviationRejected(DeviationRejectedEvent),
+    /// The event tracks whenever the price of a price feed gets stored by an update.
+    #[concordium(tag = 6)]
+    PriceUpdated(PriceUpdatedEvent),
+    /// The event tracks whenever the heartbeat of a price feed gets changed.
+    #[concordium(tag = 7)]
+    HeartbeatChanged(HeartbeatChangedEvent),
+}
+
+/// The RequiredSignaturesChangedEvent is logged when the required signatures get changed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct RequiredSignaturesChangedEvent {
+    /// The previous number of required signatures.
+    pub previous_required_signatures: u16,
+    /// The new number of required signatures.
+    pub new_required_signatures: u16,
+}
+
+/// The PausedEvent is logged when the contract gets paused.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct PausedEvent {
+    /// The address that paused the contract.
+    pub sender: Address,
+}
+
+/// The UnpausedEvent is logged when the contract gets unpaused.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct UnpausedEvent {
+    /// The address that unpaused the contract.
+    pub sender: Address,
+}
+
+/// The FeedDecimalsChangedEvent is logged when the decimals of a price feed get changed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct FeedDecimalsChangedEvent {
+    /// The key of the price feed.
+    pub key: String,
+    /// The previous effective decimals of the price feed.
+    pub previous_decimals: u8,
+    /// The new decimals of the price feed.
+    pub new_decimals: u8,
+}
+
+/// The MaxDeviationChangedEvent is logged when the maximum deviation of a price feed gets changed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct MaxDeviationChangedEvent {
+    /// The key of the price feed.
+    pub key: String,
+    /// The previous maximum deviation in basis points (`None` if the price feed was not limited).
+    pub previous_max_deviation_bps: Option<u16>,
+    /// The new maximum deviation in basis points (`None` if the price feed is not limited anymore).
+    pub new_max_deviation_bps: Option<u16>,
+}
+
+/// The DeviationRejectedEvent is logged when a new price is not stored because it exceeds the maximum deviation.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct DeviationRejectedEvent {
+    /// The key of the price feed.
+    pub key: String,
+    /// The stored price.
+    pub stored_price: u128,
+    /// The new price that was not stored.
+    pub rejected_price: u128,
+    /// The maximum deviation of the price feed in basis points.
+    pub max_deviation_bps: u16,
+}
+
+/// The PriceUpdatedEvent is logged for each price feed whose price data gets stored by an update (not for price data
+/// that is skipped as old data or rejected because of the maximum deviation). Only the key, the price, and the
+/// timestamp are logged, to keep the events of large updates small.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct PriceUpdatedEvent {
+    /// The key of the price feed.
+    pub key: String,
+    /// The new price.
+    pub price: u128,
+    /// The timestamp of the new price data.
+    pub timestamp: Timestamp,
+}
+
+/// The HeartbeatChangedEvent is logged when the heartbeat of a price feed gets changed.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct HeartbeatChangedEvent {
+    /// The key of the price feed.
+    pub key: String,
+    /// The previous heartbeat in seconds (`None` if the heartbeat of the stored price data was used).
+    pub previous_heartbeat: Option<u64>,
+    /// The new heartbeat in seconds.
+    pub new_heartbeat: u64,
 }
 
 /// All smart contract errors.
-#[derive(Debug, PartialEq, Eq, Reject, Serial, SchemaType)]
-enum CustomContractError {
+#[derive(Debug, PartialEq, Eq, Reject, Serial, Deserial, SchemaType)]
+pub enum CustomContractError {
     /// Failed to parse the parameter.
     #[from(ParseError)]
     ParseParams, // -1
@@ -81,10 +425,27 @@ enum CustomContractError {
     MissingAccount, // -16
     /// Failed because not enough signatures were provided.
     NotEnoughSignatures, // -17
-    /// Failed because the signatures are not in order.
+    /// Failed because the signers are not sorted in strictly increasing order (e.g. a signer is listed twice).
     SignaturesOutOfOrder, // -18
     /// Failed because one of the given signers is not a validator.
     InvalidSigner, // -19
+    /// Failed because the heartbeat of the price data is zero, or the heartbeat given to `setHeartbeat` is zero or
+    /// exceeds `MAX_HEARTBEAT`.
+    InvalidHeartbeat, // -20
+    /// Failed because the price data is older than the allowed maximum age.
+    StaleData, // -21
+    /// Failed because more than `MAX_KEYS` keys were queried.
+    TooManyKeys, // -22
+    /// Failed because the contract is paused.
+    Paused, // -23
+    /// Failed because the decimals exceed `MAX_DECIMALS`.
+    InvalidDecimals, // -24
+    /// Failed because the `update` entry point was invoked by this contract itself or while an update is in progress.
+    Reentrancy, // -25
+    /// Failed because the maximum deviation is zero (use `None` to remove the limit of a price feed).
+    InvalidMaxDeviation, // -26
+    /// Failed because the timestamp of the price data is more than `max_future_skew` seconds ahead of the block time.
+    TimestampTooFar, // -27
 }
 
 /// Mapping errors related to logging to CustomContractError.
@@ -127,16 +488,62 @@ impl From<UpgradeError> for CustomContractError {
 }
 
 /// The parameter type for the contract init function.
-#[derive(Debug, Serialize, SchemaType)]
+#[derive(Debug, Serial, SchemaType)]
 pub struct InitParamsUmbrellaFeeds {
     pub registry: ContractAddress,
     pub required_signatures: u16,
     pub staking_bank: ContractAddress,
     pub decimals: u8,
+    /// The signature scheme used by the validators. Defaults to `Ed25519` if omitted at the end of the parameter.
+    pub signature_scheme: SignatureScheme,
+    /// The behavior for price data that is not strictly newer than the stored price data. Defaults to `Reject` if omitted
+    /// at the end of the parameter.
+    pub old_data_policy: OldDataPolicy,
+    /// The human-readable name of the instance. Defaults to `DEFAULT_DEPLOYMENT_NAME` if omitted at the end of the parameter.
+    pub name: String,
+    /// The chain tag of the instance. Defaults to `DEFAULT_CHAIN_ID` if omitted at the end of the parameter.
+    pub chain_id: String,
+    /// The code version (e.g. the git commit) of the contracts. Defaults to an empty string if omitted at the end of
+    /// the parameter.
+    pub code_version: String,
+    /// If `true`, only validators can submit price feed updates with the `update` entry point (see `restrictSubmitter`).
+    /// Defaults to `false` if omitted at the end of the parameter.
+    pub restrict_submitter: bool,
+    /// The maximum number of seconds that the timestamp of submitted price data can be ahead of the block time.
+    /// Defaults to `DEFAULT_MAX_FUTURE_SKEW` if omitted at the end of the parameter.
+    pub max_future_skew: u64,
+}
+
+/// Custom deserialization of the init parameter, so that the `signature_scheme`/`old_data_policy`/`name`/`chain_id`/`code_version`/`restrict_submitter`/`max_future_skew` fields can be omitted.
+impl Deserial for InitParamsUmbrellaFeeds {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        Ok(InitParamsUmbrellaFeeds {
+            registry: source.get()?,
+            required_signatures: source.get()?,
+            staking_bank: source.get()?,
+            decimals: source.get()?,
+            signature_scheme: get_or_default_at_end(source, Default::default)?,
+            old_data_policy: get_or_default_at_end(source, Default::default)?,
+            name: get_or_default_at_end(source, || DEFAULT_DEPLOYMENT_NAME.to_string())?,
+            chain_id: get_or_default_at_end(source, || DEFAULT_CHAIN_ID.to_string())?,
+            code_version: get_or_default_at_end(source, Default::default)?,
+            restrict_submitter: get_or_default_at_end(source, Default::default)?,
+            max_future_skew: get_or_default_at_end(source, || DEFAULT_MAX_FUTURE_SKEW)?,
+        })
+    }
 }
 
 /// Init function that creates a new smart contract.
-#[init(contract = "umbrella_feeds", parameter = "InitParamsUmbrellaFeeds")]
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The required signatures are zero.
+/// - The decimals exceed `MAX_DECIMALS`.
+#[init(
+    contract = "umbrella_feeds",
+    parameter = "InitParamsUmbrellaFeeds",
+    event = "Event"
+)]
 fn init<S: HasStateApi>(
     ctx: &impl HasInitContext,
     state_builder: &mut StateBuilder<S>,
@@ -148,6 +555,11 @@ fn init<S: HasStateApi>(
         CustomContractError::InvalidRequiredSignatures.into()
     );
 
+    ensure!(
+        param.decimals <= MAX_DECIMALS,
+        CustomContractError::InvalidDecimals.into()
+    );
+
     Ok(State {
         deployed_at: ctx.metadata().block_time(),
         registry: param.registry,
@@ -155,6 +567,18 @@ fn init<S: HasStateApi>(
         required_signatures: param.required_signatures,
         decimals: param.decimals,
         prices: state_builder.new_map(),
+        signature_scheme: param.signature_scheme,
+        old_data_policy: param.old_data_policy,
+        paused: false,
+        feed_decimals: state_builder.new_map(),
+        updating: false,
+        name: param.name,
+        chain_id: param.chain_id,
+        max_deviations: state_builder.new_map(),
+        code_version: param.code_version,
+        restrict_submitter: param.restrict_submitter,
+        max_future_skew: param.max_future_skew,
+        feed_heartbeats: state_builder.new_map(),
     })
 }
 
@@ -226,6 +650,142 @@ fn upgrade_natively<S: HasStateApi>(
     Ok(())
 }
 
+/// The parameter type for the contract function `migrate` and the return value of the contract function `exportPrices`.
+/// It contains the stored price data and the per-feed settings, so that they can be carried over to a new module during
+/// an upgrade.
+#[derive(Debug, Serial, SchemaType, Clone, PartialEq)]
+pub struct MigrationParams {
+    /// The price data keyed by the key of the price feed (e.g. "ETH-USDC").
+    pub prices: Vec<(String, PriceData)>,
+    /// The per-feed overrides of the decimals (see `setFeedDecimals`). Defaults to an empty list if omitted at the end
+    /// of the parameter (e.g. exported by an older module).
+    pub feed_decimals: Vec<(String, u8)>,
+    /// The per-feed maximum deviations in basis points (see `setMaxDeviation`). Defaults to an empty list if omitted at
+    /// the end of the parameter.
+    pub max_deviations: Vec<(String, u16)>,
+    /// The per-feed heartbeats in seconds (see `setHeartbeat`). Defaults to an empty list if omitted at the end of the
+    /// parameter.
+    pub feed_heartbeats: Vec<(String, u64)>,
+}
+
+/// Custom deserialization of the migration parameter, so that the per-feed settings can be omitted.
+impl Deserial for MigrationParams {
+    fn deserial<R: Read>(source: &mut R) -> ParseResult<Self> {
+        Ok(MigrationParams {
+            prices: source.get()?,
+            feed_decimals: get_or_default_at_end(source, Default::default)?,
+            max_deviations: get_or_default_at_end(source, Default::default)?,
+            feed_heartbeats: get_or_default_at_end(source, Default::default)?,
+        })
+    }
+}
+
+/// View function that returns all stored price data and per-feed settings in the format of the parameter of the
+/// `migrate` entry point.
+/// The `upgrade_umbrella_feeds_contract` command of the deploy scripts queries it before an upgrade and passes it to
+/// the `migrate` entry point of the new module. The price data are sorted by the key of the price feed (not in the
+/// iteration order of the state), so that two snapshots of unchanged price data are identical.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "exportPrices",
+    return_value = "MigrationParams"
+)]
+fn export_prices<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<MigrationParams> {
+    let mut prices: Vec<(String, PriceData)> = host
+        .state()
+        .prices
+        .iter()
+        .map(|(key, price_data)| (key.clone(), *price_data))
+        .collect();
+
+    prices.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+
+    let mut feed_decimals: Vec<(String, u8)> = host
+        .state()
+        .feed_decimals
+        .iter()
+        .map(|(key, decimals)| (key.clone(), *decimals))
+        .collect();
+
+    feed_decimals.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+
+    let mut max_deviations: Vec<(String, u16)> = host
+        .state()
+        .max_deviations
+        .iter()
+        .map(|(key, max_deviation_bps)| (key.clone(), *max_deviation_bps))
+        .collect();
+
+    max_deviations.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+
+    let mut feed_heartbeats: Vec<(String, u64)> = host
+        .state()
+        .feed_heartbeats
+        .iter()
+        .map(|(key, heartbeat)| (key.clone(), *heartbeat))
+        .collect();
+
+    feed_heartbeats.sort_by(|(key_a, _), (key_b, _)| key_a.cmp(key_b));
+
+    Ok(MigrationParams {
+        prices,
+        feed_decimals,
+        max_deviations,
+        feed_heartbeats,
+    })
+}
+
+/// Migration function that (re-)inserts the given price data and per-feed settings into the state, replacing stored
+/// price data and settings of the same price feeds. It is intended to be invoked by the `upgradeNatively` entry point right after the upgrade (in the same
+/// transaction), so that the stored price data carry over to the new module even if it changes how they are stored.
+///
+/// It rejects if:
+/// - Sender is not this contract instance (i.e. it is not invoked during an upgrade).
+/// - It fails to parse the parameter.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "migrate",
+    parameter = "MigrationParams",
+    error = "CustomContractError",
+    mutable
+)]
+fn migrate<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    // Only this contract (via the `upgradeNatively` entry point) can migrate its state.
+    ensure_eq!(
+        ctx.sender(),
+        Address::Contract(ctx.self_address()),
+        CustomContractError::Unauthorized
+    );
+
+    let param: MigrationParams = ctx.parameter_cursor().get()?;
+
+    for (key, price_data) in param.prices {
+        host.state_mut().prices.insert(key, price_data);
+    }
+
+    for (key, decimals) in param.feed_decimals {
+        host.state_mut().feed_decimals.insert(key, decimals);
+    }
+
+    for (key, max_deviation_bps) in param.max_deviations {
+        host.state_mut()
+            .max_deviations
+            .insert(key, max_deviation_bps);
+    }
+
+    for (key, heartbeat) in param.feed_heartbeats {
+        host.state_mut().feed_heartbeats.insert(key, heartbeat);
+    }
+
+    Ok(())
+}
+
 /// Part of the parameter type for the contract function `update`.
 /// Specifies the message that is signed.
 #[derive(SchemaType, Serialize, Clone)]
@@ -259,7 +819,37 @@ pub struct UpdateParamsPartial {
     pub signers_and_signatures: Vec<(PublicKeyEd25519, SignatureEd25519)>,
 }
 
-/// Helper function to calculate the `message_hash`.
+/// The parameter type for the contract function `update` and `viewMessageHashEcdsa` if the contract uses the
+/// `EcdsaSecp256k1` signature scheme. Takes a vector of signers and signatures, and the message that was signed.
+#[derive(Serialize, SchemaType)]
+pub struct UpdateParamsEcdsa {
+    /// Signers and signatures.
+    pub signers_and_signatures: Vec<(PublicKeyEcdsaSecp256k1, SignatureEcdsaSecp256k1)>,
+    /// Message that was signed.
+    pub message: Message,
+}
+
+/// The `UpdateParamsEcdsaPartial` parameter type is used in the `view_message_hash_ecdsa` function.
+/// Equivalent to the `UpdateParamsPartial` parameter type for the `EcdsaSecp256k1` signature scheme.
+#[derive(Serialize)]
+#[concordium(transparent)]
+pub struct UpdateParamsEcdsaPartial {
+    /// Signers and signatures.
+    pub signers_and_signatures: Vec<(PublicKeyEcdsaSecp256k1, SignatureEcdsaSecp256k1)>,
+}
+
+/// Helper function that reads the remaining bytes of the parameter (the `message`).
+fn read_message_bytes(cursor: &mut impl HasParameter) -> Result<Vec<u8>, CustomContractError> {
+    // `(cursor.size() - cursor.cursor_position())` is the length of the message in
+    // bytes.
+    let mut message_bytes = vec![0; (cursor.size() - cursor.cursor_position()) as usize];
+
+    cursor.read_exact(&mut message_bytes)?;
+
+    Ok(message_bytes)
+}
+
+/// Helper function to calculate the `message_hash` (SHA2-256 digest) for the `Ed25519` signature scheme.
 #[receive(
     contract = "umbrella_feeds",
     name = "viewMessageHash",
@@ -282,17 +872,40 @@ fn view_message_hash<S: HasStateApi>(
 
     // The input parameter is `UpdateParams` but we have only read the initial part
     // of it with `UpdateParamsPartial` so far. We read in the `message` now.
-    // `(cursor.size() - cursor.cursor_position())` is the length of the message in
-    // bytes.
-    let mut message_bytes = vec![0; (cursor.size() - cursor.cursor_position()) as usize];
-
-    cursor.read_exact(&mut message_bytes)?;
+    let message_bytes = read_message_bytes(&mut cursor)?;
 
     let message_hash = crypto_primitives.hash_sha2_256(&message_bytes).0;
 
     Ok(HashSha2256(message_hash))
 }
 
+/// Helper function to calculate the `message_hash` (Keccak-256 digest) for the `EcdsaSecp256k1` signature scheme.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "viewMessageHashEcdsa",
+    parameter = "UpdateParamsEcdsa",
+    return_value = "HashKeccak256",
+    crypto_primitives,
+    mutable
+)]
+fn view_message_hash_ecdsa<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    _host: &mut impl HasHost<State<S>, StateApiType = S>,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> Result<HashKeccak256, CustomContractError> {
+    // Parse the parameter.
+    let mut cursor = ctx.parameter_cursor();
+    // The input parameter is `UpdateParamsEcdsa` but we only read the initial part of it
+    // with `UpdateParamsEcdsaPartial`. I.e. we read the `signatures` and the
+    // `signers`, but not the `message` here.
+    let _param: UpdateParamsEcdsaPartial = cursor.get()?;
+
+    // We read in the `message` now.
+    let message_bytes = read_message_bytes(&mut cursor)?;
+
+    Ok(crypto_primitives.hash_keccak_256(&message_bytes))
+}
+
 /// Helper function to verify the signature.
 /// This function throws if the signatures are not valid.
 #[receive(
@@ -310,25 +923,34 @@ fn verify_signatures<S: HasStateApi>(
     let param: UpdateParams = ctx.parameter_cursor().get()?;
 
     ensure!(
-        param.signers_and_signatures.len() >= host.state().required_signatures as usize,
+        param.signers_and_signatures.len() >= usize::from(host.state().required_signatures),
         CustomContractError::NotEnoughSignatures
     );
 
-    let mut prev_signer: Option<PublicKeyEd25519> = None;
+    // All signers (also the signers beyond the `required_signatures` whose signatures are not checked) have to be
+    // sorted in strictly increasing order, so that a signer cannot be counted twice.
+    ensure!(
+        param
+            .signers_and_signatures
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0),
+        CustomContractError::SignaturesOutOfOrder
+    );
 
     let message_hash = view_message_hash(ctx, host, crypto_primitives)?;
 
     let required_signatures = host.state().required_signatures;
 
-    let mut validators: Vec<PublicKeyEd25519> = Vec::with_capacity(required_signatures as usize);
+    let mut validators: Vec<PublicKeyEd25519> =
+        Vec::with_capacity(usize::from(required_signatures));
 
     // To save gas we check only the required number of signatures.
     // The case, where you can have part of signatures invalid but still enough valid in total is not supported.
     // We want to record all validators who submit (valid) signatures in a trustless/transparent way
     // in the smart contract (to e.g. reward off-chain all validators for good behavior) that is why the smart contract allows submitting more signatures than the `required_signatures` here.
     for i in 0..required_signatures {
-        let signer = param.signers_and_signatures[i as usize].0;
-        let signature = param.signers_and_signatures[i as usize].1;
+        let signer = param.signers_and_signatures[usize::from(i)].0;
+        let signature = param.signers_and_signatures[usize::from(i)].1;
 
         //Check signature.
         let valid_signature =
@@ -336,14 +958,7 @@ fn verify_signatures<S: HasStateApi>(
 
         ensure!(valid_signature, CustomContractError::WrongSignature);
 
-        ensure!(
-            prev_signer < Some(signer),
-            CustomContractError::SignaturesOutOfOrder
-        );
-
         validators.push(signer);
-
-        prev_signer = Some(signer);
     }
 
     let are_valid_signers = host.invoke_contract_read_only::<Vec<PublicKeyEd25519>>(
@@ -362,92 +977,407 @@ fn verify_signatures<S: HasStateApi>(
     Ok(())
 }
 
+/// Helper function to verify the signatures for the `EcdsaSecp256k1` signature scheme.
+/// This function throws if the signatures are not valid.
 #[receive(
     contract = "umbrella_feeds",
-    name = "update",
-    parameter = "UpdateParams",
-    error = "CustomContractError",
+    name = "verifySignaturesEcdsa",
+    parameter = "UpdateParamsEcdsa",
     crypto_primitives,
     mutable
 )]
-fn update<S: HasStateApi>(
+fn verify_signatures_ecdsa<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &mut impl HasHost<State<S>, StateApiType = S>,
     crypto_primitives: &impl HasCryptoPrimitives,
 ) -> Result<(), CustomContractError> {
-    let param: UpdateParams = ctx.parameter_cursor().get()?;
-
-    let message = param.message;
+    let param: UpdateParamsEcdsa = ctx.parameter_cursor().get()?;
 
-    // Check that the signature was intended for this contract.
-    ensure_eq!(
-        message.contract_address,
-        ctx.self_address(),
-        CustomContractError::WrongContract
+    ensure!(
+        param.signers_and_signatures.len() >= usize::from(host.state().required_signatures),
+        CustomContractError::NotEnoughSignatures
     );
 
-    // Check signature is not expired.
+    // All signers (also the signers beyond the `required_signatures` whose signatures are not checked) have to be
+    // sorted in strictly increasing order, so that a signer cannot be counted twice.
     ensure!(
-        message.timestamp > ctx.metadata().slot_time(),
-        CustomContractError::Expired
+        param
+            .signers_and_signatures
+            .windows(2)
+            .all(|pair| pair[0].0 < pair[1].0),
+        CustomContractError::SignaturesOutOfOrder
     );
 
-    verify_signatures(ctx, host, crypto_primitives)?;
+    let message_hash = view_message_hash_ecdsa(ctx, host, crypto_primitives)?;
 
-    for element in message.price_feed {
-        let price_key: String = element.0;
-        let new_price_data: PriceData = element.1;
+    let required_signatures = host.state().required_signatures;
 
-        let stored_price_data = host.state_mut().prices.entry(price_key);
+    let mut validators: Vec<PublicKeyEcdsaSecp256k1> =
+        Vec::with_capacity(usize::from(required_signatures));
 
-        match stored_price_data {
-            Entry::Occupied(mut oe) => {
-                // We do not allow for older prices.
-                // This prevents replay attacks by preventing reusing of signatures at the same time.
-                ensure!(
-                    oe.timestamp < new_price_data.timestamp,
-                    CustomContractError::OldData
-                );
-                *oe = new_price_data;
-            }
-            Entry::Vacant(ve) => {
-                ve.insert(new_price_data);
-            }
-        }
+    // Same as for the `Ed25519` signature scheme, we check only the required number of signatures.
+    for i in 0..required_signatures {
+        let signer = param.signers_and_signatures[usize::from(i)].0;
+        let signature = param.signers_and_signatures[usize::from(i)].1;
+
+        //Check signature.
+        let valid_signature =
+            crypto_primitives.verify_ecdsa_secp256k1_signature(signer, signature, message_hash.0);
+
+        ensure!(valid_signature, CustomContractError::WrongSignature);
+
+        validators.push(signer);
     }
 
-    Ok(())
-}
+    let are_valid_signers = host.invoke_contract_read_only::<Vec<PublicKeyEcdsaSecp256k1>>(
+        &host.state().staking_bank,
+        &validators,
+        EntrypointName::new_unchecked("verifyValidatorsEcdsa"),
+        Amount::zero(),
+    )?;
 
-/// View function that returns the key/name of this contract.
-#[receive(contract = "umbrella_feeds", name = "getName", return_value = "String")]
-fn get_name<S: HasStateApi>(
-    _ctx: &impl HasReceiveContext,
-    _host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<&'static str> {
-    Ok("UmbrellaFeeds")
+    let are_valid_signers: bool = are_valid_signers
+        .ok_or(CustomContractError::InvokeContractError)?
+        .get()?;
+
+    ensure!(are_valid_signers, CustomContractError::InvalidSigner);
+
+    Ok(())
 }
 
-/// View function that returns many price data. It throws if price feed does not exist.
+/// Function to update the price feeds. The signatures are verified with the signature scheme of this contract.
+/// The parameter is `UpdateParams` for the `Ed25519` signature scheme and `UpdateParamsEcdsa`
+/// for the `EcdsaSecp256k1` signature scheme.
+/// It rejects with `Reentrancy` if it is invoked by this contract itself or while another update is in progress.
+///
+/// A new price of a price feed with a maximum deviation (see `setMaxDeviation`) that differs from the stored (non-zero)
+/// price by more than the maximum deviation is not stored, and a `DeviationRejected` event is logged. The other price
+/// data of the update are stored (the update is not rejected, since a rejected update cannot log the event).
+///
+/// If the contract was initialized with `restrict_submitter`, it rejects with `Unauthorized` if the sender is not a
+/// validator (see `ensure_validator_submitter`).
 #[receive(
     contract = "umbrella_feeds",
-    name = "getManyPriceData",
-    parameter = "Vec<String>",
-    return_value = "Vec<PriceData>"
+    name = "update",
+    parameter = "UpdateParams",
+    error = "CustomContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
 )]
-fn get_many_price_data<S: HasStateApi>(
+fn update<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
-    host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<Vec<PriceData>> {
-    let keys: Vec<String> = ctx.parameter_cursor().get()?;
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> Result<(), CustomContractError> {
+    if host.state().restrict_submitter {
+        ensure_validator_submitter(ctx, host)?;
+    }
 
-    let mut price_data = Vec::with_capacity(keys.len());
+    guarded_update(ctx, host, logger, crypto_primitives, true)
+}
 
-    for key in keys {
-        price_data.push(
-            *host
-                .state()
-                .prices
+/// Helper function of the `update` entry point that checks that the sender is a validator, i.e. an account with an
+/// (Ed25519) account key that is registered as a validator (signing public key) in the `staking_bank` contract.
+/// This function throws `Unauthorized` if the sender is not a validator.
+fn ensure_validator_submitter<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    let Address::Account(sender) = ctx.sender() else {
+        bail!(CustomContractError::Unauthorized);
+    };
+
+    let account_keys = host
+        .account_public_keys(sender)
+        .map_err(|_| CustomContractError::MissingAccount)?;
+
+    for credential_keys in account_keys.keys.values() {
+        for key in credential_keys.keys.values() {
+            let PublicKey::Ed25519(key) = key;
+
+            let is_validator = host.invoke_contract_read_only::<PublicKeyEd25519>(
+                &host.state().staking_bank,
+                key,
+                EntrypointName::new_unchecked("verifyValidator"),
+                Amount::zero(),
+            )?;
+
+            let is_validator: bool = is_validator
+                .ok_or(CustomContractError::InvokeContractError)?
+                .get()?;
+
+            if is_validator {
+                return Ok(());
+            }
+        }
+    }
+
+    bail!(CustomContractError::Unauthorized)
+}
+
+/// Function to update the price feeds without the maximum deviation check (e.g. for a legitimate large price move).
+/// The parameter and the signature verification are the same as for the `update` entry point. It can only be invoked
+/// by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+/// - Any of the checks of the `update` entry point fails.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "emergencyUpdate",
+    parameter = "UpdateParams",
+    error = "CustomContractError",
+    crypto_primitives,
+    enable_logger,
+    mutable
+)]
+fn emergency_update<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    guarded_update(ctx, host, logger, crypto_primitives, false)
+}
+
+/// Helper function of the `update` and `emergencyUpdate` entry points that guards against reentrancy.
+fn guarded_update<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    check_deviation: bool,
+) -> Result<(), CustomContractError> {
+    ensure!(
+        ctx.sender() != Address::Contract(ctx.self_address()),
+        CustomContractError::Reentrancy
+    );
+
+    ensure!(!host.state().updating, CustomContractError::Reentrancy);
+
+    host.state_mut().updating = true;
+
+    // The guard has to be committed before the staking bank is invoked, otherwise it is not seen by a nested invoke.
+    host.commit_state();
+
+    let result = update_price_feeds(ctx, host, logger, crypto_primitives, check_deviation);
+
+    host.state_mut().updating = false;
+
+    result
+}
+
+/// Helper function of the `update` entry point that verifies the signatures and stores the price data.
+/// The maximum deviations of the price feeds are only checked if `check_deviation` is `true`.
+fn update_price_feeds<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+    crypto_primitives: &impl HasCryptoPrimitives,
+    check_deviation: bool,
+) -> Result<(), CustomContractError> {
+    // Check that the contract is not paused.
+    ensure!(!host.state().paused, CustomContractError::Paused);
+
+    let signature_scheme = host.state().signature_scheme;
+
+    let message = match signature_scheme {
+        SignatureScheme::Ed25519 => {
+            let param: UpdateParams = ctx.parameter_cursor().get()?;
+            param.message
+        }
+        SignatureScheme::EcdsaSecp256k1 => {
+            let param: UpdateParamsEcdsa = ctx.parameter_cursor().get()?;
+            param.message
+        }
+    };
+
+    // Check that the signature was intended for this contract.
+    ensure_eq!(
+        message.contract_address,
+        ctx.self_address(),
+        CustomContractError::WrongContract
+    );
+
+    // Check signature is not expired.
+    ensure!(
+        message.timestamp > ctx.metadata().slot_time(),
+        CustomContractError::Expired
+    );
+
+    // Check that all price data have a heartbeat.
+    ensure!(
+        message
+            .price_feed
+            .iter()
+            .all(|(_, price_data)| price_data.heartbeat != 0),
+        CustomContractError::InvalidHeartbeat
+    );
+
+    // Check that no price data is stamped more than `max_future_skew` seconds ahead of the block time, since it would
+    // poison the staleness checks.
+    let max_timestamp = ctx
+        .metadata()
+        .slot_time()
+        .timestamp_millis()
+        .saturating_add(host.state().max_future_skew.saturating_mul(1000));
+
+    ensure!(
+        message
+            .price_feed
+            .iter()
+            .all(|(_, price_data)| price_data.timestamp.timestamp_millis() <= max_timestamp),
+        CustomContractError::TimestampTooFar
+    );
+
+    match signature_scheme {
+        SignatureScheme::Ed25519 => verify_signatures(ctx, host, crypto_primitives)?,
+        SignatureScheme::EcdsaSecp256k1 => verify_signatures_ecdsa(ctx, host, crypto_primitives)?,
+    }
+
+    let old_data_policy = host.state().old_data_policy;
+
+    for element in message.price_feed {
+        let price_key: String = element.0;
+        let new_price_data: PriceData = element.1;
+
+        let max_deviation_bps = if check_deviation {
+            host.state()
+                .max_deviations
+                .get(&price_key)
+                .map(|max_deviation_bps| *max_deviation_bps)
+        } else {
+            None
+        };
+
+        let price_updated = PriceUpdatedEvent {
+            key: price_key.clone(),
+            price: new_price_data.price,
+            timestamp: new_price_data.timestamp,
+        };
+
+        let stored_price_data = host.state_mut().prices.entry(price_key.clone());
+
+        match stored_price_data {
+            Entry::Occupied(mut oe) => {
+                // We do not allow for older prices.
+                // This prevents replay attacks by preventing reusing of signatures at the same time.
+                if oe.timestamp >= new_price_data.timestamp {
+                    match old_data_policy {
+                        OldDataPolicy::Reject => bail!(CustomContractError::OldData),
+                        OldDataPolicy::Skip => continue,
+                    }
+                }
+
+                // A stored price of zero is treated as uninitialized and not limited.
+                if let Some(max_deviation_bps) = max_deviation_bps {
+                    if oe.price != 0
+                        && exceeds_max_deviation(oe.price, new_price_data.price, max_deviation_bps)
+                    {
+                        logger.log(&Event::DeviationRejected(DeviationRejectedEvent {
+                            key: price_key,
+                            stored_price: oe.price,
+                            rejected_price: new_price_data.price,
+                            max_deviation_bps,
+                        }))?;
+                        continue;
+                    }
+                }
+
+                *oe = new_price_data;
+            }
+            Entry::Vacant(ve) => {
+                ve.insert(new_price_data);
+            }
+        }
+
+        logger.log(&Event::PriceUpdated(price_updated))?;
+    }
+
+    Ok(())
+}
+
+/// View function that returns the key/name of this contract.
+/// The registry stores this contract under this key/name (`importContracts`/`atomicUpdate`), so it is the same for all
+/// instances. Use `getDeploymentName` for the human-readable name of an instance.
+#[receive(contract = "umbrella_feeds", name = "getName", return_value = "String")]
+fn get_name<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    _host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<&'static str> {
+    Ok("UmbrellaFeeds")
+}
+
+/// View function that returns the human-readable name of this instance set at init.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getDeploymentName",
+    return_value = "String"
+)]
+fn get_deployment_name<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<String> {
+    Ok(host.state().name.clone())
+}
+
+/// View function that returns the chain tag of this instance set at init.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getChainId",
+    return_value = "String"
+)]
+fn get_chain_id<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<String> {
+    Ok(host.state().chain_id.clone())
+}
+
+/// View function that returns the code version (e.g. the git commit) of the contracts this instance was built from.
+/// It is empty if the instance was initialized without a code version.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getCodeVersion",
+    return_value = "String"
+)]
+fn get_code_version<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<String> {
+    Ok(host.state().code_version.clone())
+}
+
+/// View function that returns many price data. It throws if price feed does not exist or if more than `MAX_KEYS`
+/// keys are queried.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getManyPriceData",
+    parameter = "Vec<String>",
+    return_value = "Vec<PriceData>"
+)]
+fn get_many_price_data<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Vec<PriceData>> {
+    let keys: Vec<String> = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        keys.len() <= MAX_KEYS,
+        CustomContractError::TooManyKeys.into()
+    );
+
+    let mut price_data = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        price_data.push(
+            *host
+                .state()
+                .prices
                 .get(&key)
                 .ok_or(CustomContractError::FeedNotExist)?,
         );
@@ -459,7 +1389,7 @@ fn get_many_price_data<S: HasStateApi>(
 /// View function that returns many price data. In contrast to the `getManyPriceData`,
 /// this function returns `None` instead of an Error when a price feed
 /// does not exist. This function can be used if reverting the transaction
-/// on non-existing price-feed is not desired.
+/// on non-existing price-feed is not desired. It throws if more than `MAX_KEYS` keys are queried.
 #[receive(
     contract = "umbrella_feeds",
     name = "getManyPriceDataRaw",
@@ -472,6 +1402,11 @@ fn get_many_price_data_raw<S: HasStateApi>(
 ) -> ReceiveResult<Vec<Option<PriceData>>> {
     let keys: Vec<String> = ctx.parameter_cursor().get()?;
 
+    ensure!(
+        keys.len() <= MAX_KEYS,
+        CustomContractError::TooManyKeys.into()
+    );
+
     let mut price_data = Vec::with_capacity(keys.len());
 
     for key in keys {
@@ -481,17 +1416,78 @@ fn get_many_price_data_raw<S: HasStateApi>(
     Ok(price_data)
 }
 
-/// View function that returns the price data of one price feed. It throws if the price feed does not exist.
+/// The return value of the contract function `getManyPriceDataRawWithBlockTime`.
+/// The serialization is the serialization of the tuple `(Vec<PriceData>, Timestamp)`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct ManyPriceDataRawWithBlockTime {
+    /// The price data of the price feeds, aligned with the order of the input keys. The price data of a
+    /// non-existing price feed is zeroed (see `PriceData::zero()`), i.e. its `timestamp` is 0.
+    pub price_data: Vec<PriceData>,
+    /// The block time that a consumer can compare the timestamps of the price data against.
+    pub block_time: Timestamp,
+}
+
+/// View function that returns many price data together with the block time, so that a consumer can apply its own
+/// staleness check in a single invoke. In contrast to `getManyPriceDataRaw`, the price data of a non-existing price
+/// feed is zeroed (with a zero `timestamp`) instead of `None`, which keeps the return value a flat list.
+/// It throws if more than `MAX_KEYS` keys are queried.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getManyPriceDataRawWithBlockTime",
+    parameter = "Vec<String>",
+    return_value = "ManyPriceDataRawWithBlockTime"
+)]
+fn get_many_price_data_raw_with_block_time<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<ManyPriceDataRawWithBlockTime> {
+    let keys: Vec<String> = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        keys.len() <= MAX_KEYS,
+        CustomContractError::TooManyKeys.into()
+    );
+
+    let mut price_data = Vec::with_capacity(keys.len());
+
+    for key in keys {
+        price_data.push(
+            host.state()
+                .prices
+                .get(&key)
+                .map_or(PriceData::zero(), |price_data| *price_data),
+        );
+    }
+
+    Ok(ManyPriceDataRawWithBlockTime {
+        price_data,
+        block_time: ctx.metadata().block_time(),
+    })
+}
+
+/// The return value of the contract function `getPriceData`.
+/// The serialization is the serialization of the `PriceData` followed by the decimals, so that consumers that
+/// only read the `PriceData` keep working.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct PriceDataWithDecimals {
+    /// The price data of the price feed.
+    pub price_data: PriceData,
+    /// The effective decimals of the price of the price feed (see `setFeedDecimals`).
+    pub decimals: u8,
+}
+
+/// View function that returns the price data of one price feed together with its effective decimals.
+/// It throws if the price feed does not exist.
 #[receive(
     contract = "umbrella_feeds",
     name = "getPriceData",
     parameter = "String",
-    return_value = "PriceData"
+    return_value = "PriceDataWithDecimals"
 )]
 fn get_price_data<S: HasStateApi>(
     ctx: &impl HasReceiveContext,
     host: &impl HasHost<State<S>, StateApiType = S>,
-) -> ReceiveResult<PriceData> {
+) -> ReceiveResult<PriceDataWithDecimals> {
     let key: String = ctx.parameter_cursor().get()?;
 
     let price_data = *host
@@ -500,10 +1496,186 @@ fn get_price_data<S: HasStateApi>(
         .get(&key)
         .ok_or(CustomContractError::FeedNotExist)?;
 
+    Ok(PriceDataWithDecimals {
+        price_data,
+        decimals: host.state().effective_decimals(&key),
+    })
+}
+
+/// The return type of the contract function `getPriceDataRaw`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct PriceDataRaw {
+    /// `true` if the price feed exists. If `false`, the `price_data` is zeroed (see `PriceData::zero()`).
+    pub success: bool,
+    /// The price data of the price feed.
+    pub price_data: PriceData,
+}
+
+/// View function that returns the price data of one price feed. In contrast to the `getPriceData`,
+/// this function does not throw when the price feed does not exist. Instead, `success` is `false` and
+/// zeroed price data (with a zero `timestamp` and `heartbeat`, see `PriceData::zero()`) is returned. It cannot be
+/// mistaken for stored price data, since the `update` entry point rejects price data with a zero heartbeat. This function can be used if reverting the transaction on a
+/// non-existing price feed is not desired.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getPriceDataRaw",
+    parameter = "String",
+    return_value = "PriceDataRaw"
+)]
+fn get_price_data_raw<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<PriceDataRaw> {
+    let key: String = ctx.parameter_cursor().get()?;
+
+    Ok(match host.state().prices.get(&key) {
+        Some(price_data) => PriceDataRaw {
+            success: true,
+            price_data: *price_data,
+        },
+        None => PriceDataRaw {
+            success: false,
+            price_data: PriceData::zero(),
+        },
+    })
+}
+
+/// The parameter type for the contract function `getPriceDataFresh`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetPriceDataFreshParams {
+    /// The key of the price feed.
+    pub key: String,
+    /// The maximum age of the price data in seconds (compared to the block time).
+    pub max_age_seconds: u64,
+}
+
+/// Helper function that throws if the price data is older than `max_age_seconds` at the given block time.
+/// Price data with a timestamp in the future is considered fresh.
+fn ensure_fresh(
+    price_data: &PriceData,
+    block_time: Timestamp,
+    max_age_seconds: u64,
+) -> Result<(), CustomContractError> {
+    let age = block_time
+        .duration_since(price_data.timestamp)
+        .unwrap_or_else(|| Duration::from_millis(0));
+
+    ensure!(
+        age.millis() <= max_age_seconds.saturating_mul(1000),
+        CustomContractError::StaleData
+    );
+
+    Ok(())
+}
+
+/// View function that returns the price data of one price feed. It throws if the price feed does not exist or
+/// if the price data is older than `max_age_seconds` (i.e. `block_time - timestamp > max_age_seconds`).
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getPriceDataFresh",
+    parameter = "GetPriceDataFreshParams",
+    return_value = "PriceData"
+)]
+fn get_price_data_fresh<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<PriceData> {
+    let param: GetPriceDataFreshParams = ctx.parameter_cursor().get()?;
+
+    let price_data = *host
+        .state()
+        .prices
+        .get(&param.key)
+        .ok_or(CustomContractError::FeedNotExist)?;
+
+    ensure_fresh(
+        &price_data,
+        ctx.metadata().block_time(),
+        param.max_age_seconds,
+    )?;
+
     Ok(price_data)
 }
 
-/// View function that returns the price of one price feed.
+/// The parameter type for the contract function `getStaleFeeds`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct GetStaleFeedsParams {
+    /// The maximum age of the price data in seconds (compared to the block time).
+    pub max_age_seconds: u64,
+    /// The number of stored price feeds (in the iteration order of the state) to skip, i.e. `0` or the `next`
+    /// cursor of the previous page.
+    pub start: u32,
+    /// The maximum number of stored price feeds to check (at most `MAX_KEYS`).
+    pub limit: u32,
+}
+
+/// The return value of the contract function `getStaleFeeds`.
+#[derive(Debug, Serialize, SchemaType, PartialEq, Eq)]
+pub struct StaleFeeds {
+    /// The sorted keys of the stale price feeds among the checked price feeds.
+    pub keys: Vec<String>,
+    /// The `start` of the next page, or `None` if all stored price feeds were checked.
+    pub next: Option<u32>,
+}
+
+/// View function that returns the keys of the price feeds whose price data is older than `max_age_seconds`
+/// (i.e. `block_time - timestamp > max_age_seconds`), so that a watchdog can detect price feeds that stopped being
+/// updated without querying every price feed individually. Price data with a timestamp in the future is considered
+/// fresh.
+///
+/// The function only compares the timestamps of the stored price data and does not load the heartbeats set with
+/// `setHeartbeat`. It checks at most `limit` stored price feeds, starting after the first `start` price feeds in the
+/// iteration order of the state, and returns the cursor of the next page (`None` if there are no more price feeds), so
+/// that neither the work of one invoke nor the return value grows with the number of stored price feeds. The skipped
+/// price feeds are still stepped over, so the pages get slightly more expensive towards the end. The keys of a page
+/// are sorted, so that the result does not depend on the iteration order within the page.
+///
+/// It rejects if:
+/// - It fails to parse the parameter.
+/// - The `limit` exceeds `MAX_KEYS`.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getStaleFeeds",
+    parameter = "GetStaleFeedsParams",
+    return_value = "StaleFeeds",
+    error = "CustomContractError"
+)]
+fn get_stale_feeds<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> Result<StaleFeeds, CustomContractError> {
+    let param: GetStaleFeedsParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        param.limit as usize <= MAX_KEYS,
+        CustomContractError::TooManyKeys
+    );
+
+    let block_time = ctx.metadata().block_time();
+
+    let mut price_feeds = host.state().prices.iter().skip(param.start as usize);
+
+    let mut keys: Vec<String> = price_feeds
+        .by_ref()
+        .take(param.limit as usize)
+        .filter(|(_, price_data)| {
+            ensure_fresh(price_data, block_time, param.max_age_seconds).is_err()
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    keys.sort();
+
+    let next = price_feeds
+        .next()
+        .map(|_| param.start.saturating_add(param.limit));
+
+    Ok(StaleFeeds { keys, next })
+}
+
+/// View function that returns the price of one price feed. It throws if the price feed does not exist or if the
+/// price data is older than the effective heartbeat of the price feed (its heartbeat set with `setHeartbeat`, otherwise
+/// the heartbeat of the price data). Price data stored without a heartbeat (zero) are not checked.
 #[receive(
     contract = "umbrella_feeds",
     name = "getPrice",
@@ -522,6 +1694,12 @@ fn get_price<S: HasStateApi>(
         .get(&key)
         .ok_or(CustomContractError::FeedNotExist)?;
 
+    let heartbeat = host.state().effective_heartbeat(&key, &price_data);
+
+    if heartbeat != 0 {
+        ensure_fresh(&price_data, ctx.metadata().block_time(), heartbeat)?;
+    }
+
     Ok(price_data.price)
 }
 
@@ -559,7 +1737,7 @@ pub struct SchemTypeTripleWrapper {
     pub heartbeat: u64,
 }
 
-/// View function that returns the price, timestamp, and heartbeat of one price feed.
+/// View function that returns the price, timestamp, and effective heartbeat (see `getPrice`) of one price feed.
 #[receive(
     contract = "umbrella_feeds",
     name = "getPriceTimestampHeartbeat",
@@ -581,7 +1759,7 @@ fn get_price_timestamp_heartbeat<S: HasStateApi>(
     Ok(SchemTypeTripleWrapper {
         price: price_data.price,
         timestamp: price_data.timestamp,
-        heartbeat: price_data.heartbeat,
+        heartbeat: host.state().effective_heartbeat(&key, &price_data),
     })
 }
 
@@ -607,6 +1785,405 @@ fn required_signatures<S: HasStateApi>(
     Ok(host.state().required_signatures)
 }
 
+/// Internal function that checks that the sender is the owner of the registry contract
+/// (this contract has no owner of its own and is administered via the registry contract).
+fn ensure_registry_owner<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> Result<(), CustomContractError> {
+    let registry_owner = host.invoke_contract_read_only::<()>(
+        &host.state().registry,
+        &(),
+        EntrypointName::new_unchecked("owner"),
+        Amount::zero(),
+    )?;
+
+    let registry_owner: Option<Address> = registry_owner
+        .ok_or(CustomContractError::InvokeContractError)?
+        .get()?;
+
+    ensure!(
+        registry_owner == Some(ctx.sender()),
+        CustomContractError::Unauthorized
+    );
+
+    Ok(())
+}
+
+/// Function to update the required signatures. It can only be invoked by the owner of the registry contract
+/// (this contract has no owner of its own and is administered via the registry contract).
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+/// - It fails to parse the parameter.
+/// - The new required signatures are zero or exceed the number of validators in the staking bank contract.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "setRequiredSignatures",
+    parameter = "u16",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_required_signatures<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    let new_required_signatures: u16 = ctx.parameter_cursor().get()?;
+
+    let number_of_validators = host.invoke_contract_read_only::<()>(
+        &host.state().staking_bank,
+        &(),
+        EntrypointName::new_unchecked("getNumberOfValidators"),
+        Amount::zero(),
+    )?;
+
+    let number_of_validators: u16 = number_of_validators
+        .ok_or(CustomContractError::InvokeContractError)?
+        .get()?;
+
+    ensure!(
+        new_required_signatures != 0 && new_required_signatures <= number_of_validators,
+        CustomContractError::InvalidRequiredSignatures
+    );
+
+    let previous_required_signatures = host.state().required_signatures;
+
+    host.state_mut().required_signatures = new_required_signatures;
+
+    logger.log(&Event::RequiredSignaturesChanged(
+        RequiredSignaturesChangedEvent {
+            previous_required_signatures,
+            new_required_signatures,
+        },
+    ))?;
+
+    Ok(())
+}
+
+/// The parameter type for the contract function `setFeedDecimals`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetFeedDecimalsParams {
+    /// The key of the price feed.
+    pub key: String,
+    /// The new decimals of the price feed.
+    pub decimals: u8,
+}
+
+/// Function to override the decimals of one price feed (e.g. for FX rates with fewer decimals than crypto prices).
+/// The price feed does not have to exist yet. It can only be invoked by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+/// - It fails to parse the parameter.
+/// - The decimals exceed `MAX_DECIMALS`.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "setFeedDecimals",
+    parameter = "SetFeedDecimalsParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_feed_decimals<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    let param: SetFeedDecimalsParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        param.decimals <= MAX_DECIMALS,
+        CustomContractError::InvalidDecimals
+    );
+
+    let previous_decimals = host.state().effective_decimals(&param.key);
+
+    host.state_mut()
+        .feed_decimals
+        .insert(param.key.clone(), param.decimals);
+
+    logger.log(&Event::FeedDecimalsChanged(FeedDecimalsChangedEvent {
+        key: param.key,
+        previous_decimals,
+        new_decimals: param.decimals,
+    }))?;
+
+    Ok(())
+}
+
+/// The parameter type for the contract function `setMaxDeviation`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetMaxDeviationParams {
+    /// The key of the price feed.
+    pub key: String,
+    /// The new maximum deviation in basis points (e.g. 500 for 5%), or `None` to remove the limit of the price feed.
+    pub max_deviation_bps: Option<u16>,
+}
+
+/// Function to set the maximum deviation of one price feed. A new price submitted via the `update` entry point that
+/// differs from the stored price by more than the maximum deviation is not stored (see `update`). The price feed does
+/// not have to exist yet. It can only be invoked by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+/// - It fails to parse the parameter.
+/// - The maximum deviation is zero.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "setMaxDeviation",
+    parameter = "SetMaxDeviationParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_max_deviation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    let param: SetMaxDeviationParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        param.max_deviation_bps != Some(0),
+        CustomContractError::InvalidMaxDeviation
+    );
+
+    let max_deviations = &mut host.state_mut().max_deviations;
+
+    let previous_max_deviation_bps = match param.max_deviation_bps {
+        Some(max_deviation_bps) => max_deviations.insert(param.key.clone(), max_deviation_bps),
+        None => max_deviations.remove_and_get(&param.key),
+    };
+
+    logger.log(&Event::MaxDeviationChanged(MaxDeviationChangedEvent {
+        key: param.key,
+        previous_max_deviation_bps,
+        new_max_deviation_bps: param.max_deviation_bps,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the maximum deviation of the price feed in basis points (`None` if it is not limited).
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getMaxDeviation",
+    parameter = "String",
+    return_value = "Option<u16>"
+)]
+fn get_max_deviation<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<u16>> {
+    let key: String = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .max_deviations
+        .get(&key)
+        .map(|max_deviation_bps| *max_deviation_bps))
+}
+
+/// The parameter type for the contract function `setHeartbeat`.
+#[derive(Debug, Serialize, SchemaType)]
+pub struct SetHeartbeatParams {
+    /// The key of the price feed.
+    pub key: String,
+    /// The new heartbeat in seconds.
+    pub heartbeat: u64,
+}
+
+/// Function to set the heartbeat of one price feed. The heartbeat takes precedence over the heartbeat of the stored
+/// price data in the `getPrice` and `getPriceTimestampHeartbeat` entry points. The price feed does not have to exist
+/// yet. It can only be invoked by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+/// - It fails to parse the parameter.
+/// - The heartbeat is zero or exceeds `MAX_HEARTBEAT`.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "setHeartbeat",
+    parameter = "SetHeartbeatParams",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn set_heartbeat<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    let param: SetHeartbeatParams = ctx.parameter_cursor().get()?;
+
+    ensure!(
+        param.heartbeat != 0 && param.heartbeat <= MAX_HEARTBEAT,
+        CustomContractError::InvalidHeartbeat
+    );
+
+    let previous_heartbeat = host
+        .state_mut()
+        .feed_heartbeats
+        .insert(param.key.clone(), param.heartbeat);
+
+    logger.log(&Event::HeartbeatChanged(HeartbeatChangedEvent {
+        key: param.key,
+        previous_heartbeat,
+        new_heartbeat: param.heartbeat,
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns the heartbeat of the price feed set with `setHeartbeat` in seconds (`None` if the
+/// heartbeat of the stored price data is used).
+#[receive(
+    contract = "umbrella_feeds",
+    name = "getHeartbeat",
+    parameter = "String",
+    return_value = "Option<u64>"
+)]
+fn get_heartbeat<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<Option<u64>> {
+    let key: String = ctx.parameter_cursor().get()?;
+
+    Ok(host
+        .state()
+        .feed_heartbeats
+        .get(&key)
+        .map(|heartbeat| *heartbeat))
+}
+
+/// Function to pause the contract (e.g. in an incident such as a compromised validator key). While paused, the
+/// `update` entry point rejects all price submissions. Reads continue to work, so that dependent contracts keep working.
+/// It can only be invoked by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "pause",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn pause<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    host.state_mut().paused = true;
+
+    logger.log(&Event::Paused(PausedEvent {
+        sender: ctx.sender(),
+    }))?;
+
+    Ok(())
+}
+
+/// Function to unpause the contract. It can only be invoked by the owner of the registry contract.
+///
+/// It rejects if:
+/// - Sender is not the owner of the registry contract.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "unpause",
+    error = "CustomContractError",
+    enable_logger,
+    mutable
+)]
+fn unpause<S: HasStateApi>(
+    ctx: &impl HasReceiveContext,
+    host: &mut impl HasHost<State<S>, StateApiType = S>,
+    logger: &mut impl HasLogger,
+) -> Result<(), CustomContractError> {
+    ensure_registry_owner(ctx, host)?;
+
+    host.state_mut().paused = false;
+
+    logger.log(&Event::Unpaused(UnpausedEvent {
+        sender: ctx.sender(),
+    }))?;
+
+    Ok(())
+}
+
+/// View function that returns if the contract is paused.
+#[receive(contract = "umbrella_feeds", name = "paused", return_value = "bool")]
+fn paused<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    Ok(host.state().paused)
+}
+
+/// View function that returns if only validators can submit price feed updates with the `update` entry point.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "restrictSubmitter",
+    return_value = "bool"
+)]
+fn restrict_submitter<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<bool> {
+    Ok(host.state().restrict_submitter)
+}
+
+/// View function that returns the maximum number of seconds that the timestamp of submitted price data can be ahead
+/// of the block time.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "maxFutureSkew",
+    return_value = "u64"
+)]
+fn max_future_skew<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<u64> {
+    Ok(host.state().max_future_skew)
+}
+
+/// View function that returns the signature scheme used by the validators.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "signatureScheme",
+    return_value = "SignatureScheme"
+)]
+fn signature_scheme<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<SignatureScheme> {
+    Ok(host.state().signature_scheme)
+}
+
+/// View function that returns the behavior for price data that is not strictly newer than the stored price data.
+#[receive(
+    contract = "umbrella_feeds",
+    name = "oldDataPolicy",
+    return_value = "OldDataPolicy"
+)]
+fn old_data_policy<S: HasStateApi>(
+    _ctx: &impl HasReceiveContext,
+    host: &impl HasHost<State<S>, StateApiType = S>,
+) -> ReceiveResult<OldDataPolicy> {
+    Ok(host.state().old_data_policy)
+}
+
 /// Hook function to enable `atomicUpdate` via the registry contract.
 #[receive(contract = "umbrella_feeds", name = "unregister")]
 fn unregister<S: HasStateApi>(
@@ -0,0 +1,86 @@
+//! Helpers for the integration tests (enabled with the `dev` feature) that produce the signed parameters of the
+//! `update` entry point for the `Ed25519` signature scheme, so that the tests can exercise the signature
+//! verification without the off-chain signer service.
+//!
+//! The validators sign the SHA2-256 digest of the serialized `Message` (the remaining bytes of the `update`
+//! parameter after the signers and signatures), which is also returned by the `viewMessageHash` entry point.
+
+use crate::{Message, PriceData, UpdateParams};
+use concordium_std::*;
+use ed25519_dalek::{Keypair, PublicKey, SecretKey, Signer};
+use sha2::{Digest, Sha256};
+
+/// Generates `n` deterministic validator key pairs. The secret key of the `i`-th key pair is the little-endian
+/// `u16` value `i + 1` padded with zeros to 32 bytes, so that the keys are stable across test runs.
+pub fn generate_validator_keys(n: u16) -> Vec<Keypair> {
+    (1..=n)
+        .map(|i| {
+            let mut bytes = [0u8; 32];
+            bytes[..2].copy_from_slice(&i.to_le_bytes());
+
+            let secret =
+                SecretKey::from_bytes(&bytes).expect("32 bytes should be a valid secret key");
+            let public = PublicKey::from(&secret);
+
+            Keypair { secret, public }
+        })
+        .collect()
+}
+
+/// Returns the public key of the validator key pair in the format of the `update` parameter.
+pub fn public_key(key: &Keypair) -> PublicKeyEd25519 {
+    PublicKeyEd25519(key.public.to_bytes())
+}
+
+/// Builds the bytes of the message that the validators sign, i.e. the serialized `Message` with the given
+/// umbrella feeds contract address, expiry timestamp, and price data.
+pub fn build_update_message(
+    feeds_addr: ContractAddress,
+    timestamp: Timestamp,
+    prices: Vec<(String, PriceData)>,
+) -> Vec<u8> {
+    to_bytes(&Message {
+        contract_address: feeds_addr,
+        timestamp,
+        price_feed: prices,
+    })
+}
+
+/// Signs the SHA2-256 digest of the message with each of the given keys.
+/// The signers and signatures are sorted by the public key, as required by the `update` entry point.
+pub fn sign_update(keys: &[Keypair], message: &[u8]) -> Vec<(PublicKeyEd25519, SignatureEd25519)> {
+    let message_hash = Sha256::digest(message);
+
+    let mut signers_and_signatures: Vec<(PublicKeyEd25519, SignatureEd25519)> = keys
+        .iter()
+        .map(|key| {
+            (
+                public_key(key),
+                SignatureEd25519(key.sign(&message_hash).to_bytes()),
+            )
+        })
+        .collect();
+
+    signers_and_signatures.sort_by_key(|(signer, _)| *signer);
+
+    signers_and_signatures
+}
+
+/// Builds the `update` parameter with the message signed by all of the given keys.
+pub fn build_update_params(
+    keys: &[Keypair],
+    feeds_addr: ContractAddress,
+    timestamp: Timestamp,
+    prices: Vec<(String, PriceData)>,
+) -> UpdateParams {
+    let message = build_update_message(feeds_addr, timestamp, prices.clone());
+
+    UpdateParams {
+        signers_and_signatures: sign_update(keys, &message),
+        message: Message {
+            contract_address: feeds_addr,
+            timestamp,
+            price_feed: prices,
+        },
+    }
+}
@@ -2,13 +2,22 @@ use std::collections::BTreeMap;
 
 use concordium_smart_contract_testing::*;
 use concordium_std::HashSha2256;
-use concordium_std::{CredentialSignatures, PublicKeyEd25519, SignatureEd25519, Timestamp};
+use concordium_std::{
+    CredentialSignatures, PublicKeyEcdsaSecp256k1, PublicKeyEd25519, SignatureEcdsaSecp256k1,
+    SignatureEd25519, Timestamp,
+};
 use registry::{AtomicUpdateParam, ImportContractsParam};
 use umbrella_feeds::{
-    InitParamsUmbrellaFeeds, Message, PriceData, SchemTypeTripleWrapper, UpdateParams,
+    Event, FeedDecimalsChangedEvent, GetPriceDataFreshParams, HeartbeatChangedEvent,
+    InitParamsUmbrellaFeeds, Message, OldDataPolicy, PausedEvent, PriceData, PriceDataRaw,
+    PriceDataWithDecimals, RequiredSignaturesChangedEvent, SchemTypeTripleWrapper,
+    SetFeedDecimalsParams, SetHeartbeatParams, SignatureScheme, UnpausedEvent, UpdateParams,
+    UpdateParamsEcdsa, DEFAULT_CHAIN_ID, DEFAULT_DEPLOYMENT_NAME, DEFAULT_MAX_FUTURE_SKEW,
+    MAX_DECIMALS, MAX_HEARTBEAT, MAX_KEYS,
 };
 
 const ACC_ADDR_OWNER: AccountAddress = AccountAddress([0u8; 32]);
+const OTHER_ACCOUNT: AccountAddress = AccountAddress([1u8; 32]);
 const ACC_INITIAL_BALANCE: Amount = Amount::from_ccd(1000);
 
 const SIGNATURE_1: SignatureEd25519 = SignatureEd25519([
@@ -65,13 +74,63 @@ const PUBLIC_KEY_SIGNER_2: [u8; 32] = [
     243, 162, 209, 78, 136, 94, 127, 247, 21, 222, 221,
 ];
 
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 1F2A8E3C6D5B4A79880716253443526170E8D9C6B5A4F3E2D1C0B9A897867564
+const ECDSA_PUBLIC_KEY_SIGNER_1: [u8; 33] = [
+    2, 122, 114, 246, 115, 219, 127, 146, 184, 35, 59, 96, 60, 177, 237, 120, 241, 72, 252, 51,
+    106, 192, 49, 204, 84, 255, 146, 218, 148, 9, 105, 124, 224,
+];
+
+// ATTENTION: Use a different key in production. This key and its private key is exposed and used for testing here.
+// Private key: 7B6A594837261504F3E2D1C0B9A8978675645342312F1E0D0C0B0A0908070605
+const ECDSA_PUBLIC_KEY_SIGNER_2: [u8; 33] = [
+    2, 231, 38, 120, 58, 92, 141, 19, 123, 165, 152, 190, 30, 234, 22, 28, 98, 136, 124, 106, 12,
+    33, 234, 80, 46, 230, 4, 117, 46, 81, 219, 7, 143,
+];
+
+const ECDSA_SIGNATURE_1: SignatureEcdsaSecp256k1 = SignatureEcdsaSecp256k1([
+    5, 126, 179, 21, 99, 83, 68, 251, 119, 149, 252, 29, 136, 185, 88, 239, 172, 87, 146, 11, 79,
+    29, 113, 154, 150, 252, 236, 61, 11, 104, 118, 185, 116, 68, 122, 124, 91, 62, 172, 116, 186,
+    198, 54, 243, 98, 73, 153, 213, 73, 219, 54, 196, 42, 237, 12, 232, 153, 142, 57, 10, 210, 96,
+    189, 210,
+]);
+
+const ECDSA_SIGNATURE_2: SignatureEcdsaSecp256k1 = SignatureEcdsaSecp256k1([
+    212, 104, 5, 240, 153, 84, 0, 218, 209, 194, 66, 80, 164, 119, 51, 74, 83, 16, 182, 159, 174,
+    99, 151, 14, 218, 46, 58, 238, 186, 186, 170, 172, 69, 5, 234, 46, 89, 222, 10, 164, 1, 163,
+    97, 50, 244, 143, 44, 79, 63, 43, 247, 126, 8, 116, 140, 170, 125, 181, 238, 92, 196, 112, 220,
+    135,
+]);
+
+const ECDSA_MESSAGE_HASH: [u8; 32] = [
+    233, 191, 87, 35, 11, 52, 108, 131, 188, 17, 54, 210, 8, 206, 122, 213, 218, 119, 132, 114,
+    229, 42, 2, 25, 155, 10, 221, 69, 160, 104, 212, 59,
+];
+
 fn setup_chain_and_contract() -> (
     Chain,
     ContractInitSuccess,
     ContractInitSuccess,
     ContractInitSuccess,
 ) {
-    let mut chain = Chain::new();
+    setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(0),
+    )
+}
+
+fn setup_chain_and_contract_with(
+    signature_scheme: SignatureScheme,
+    old_data_policy: OldDataPolicy,
+    block_time: Timestamp,
+) -> (
+    Chain,
+    ContractInitSuccess,
+    ContractInitSuccess,
+    ContractInitSuccess,
+) {
+    let mut chain = Chain::new_with_time(block_time);
 
     // Creating contract owner's keys
 
@@ -143,6 +202,13 @@ fn setup_chain_and_contract() -> (
         required_signatures: 2,
         staking_bank: initialization_staking_bank.contract_address,
         decimals: 4,
+        signature_scheme,
+        old_data_policy,
+        name: String::from("UmbrellaFeeds Testnet"),
+        chain_id: String::from("concordium-testnet"),
+        code_version: String::from("8f3c2a1"),
+        restrict_submitter: false,
+        max_future_skew: DEFAULT_MAX_FUTURE_SKEW,
     };
 
     let initialization_umbrella_feeds = chain
@@ -168,6 +234,16 @@ fn setup_chain_and_contract() -> (
     )
 }
 
+/// Returns the error code of a contract update or invoke that was rejected by the contract.
+fn reject_code(error: &ContractInvokeError) -> i32 {
+    match error.kind {
+        ContractInvokeErrorKind::ExecutionError {
+            failure_kind: InvokeFailure::ContractReject { code, .. },
+        } => code,
+        _ => panic!("The contract update should be rejected by the contract"),
+    }
+}
+
 #[test]
 fn test_init() {
     let (
@@ -200,87 +276,295 @@ fn test_init() {
     assert_eq!(value, 4);
 }
 
-/// Test updating the price feed with two signers and two price feeds.
+/// Test the `getDeploymentName` and `getChainId` functions, including the defaults of instances initialized
+/// without the `name` and `chain_id` fields.
 #[test]
-fn test_update_two_price_feeds() {
+fn test_metadata() {
     let (
         mut chain,
         initialization_umbrella_feeds,
-        _initialization_registry,
-        _initialization_staking_bank,
+        initialization_registry,
+        initialization_staking_bank,
     ) = setup_chain_and_contract();
 
-    let key_1: String = String::from("Contract1");
-
-    let key_2: String = String::from("Contract2");
-
-    let price_data_1 = PriceData {
-        data: 7,
-        heartbeat: 12,
-        timestamp: Timestamp::from_timestamp_millis(9),
-        price: 4,
+    let view = |chain: &Chain, contract_address: ContractAddress, entrypoint: &str| -> String {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(format!(
+                        "umbrella_feeds.{entrypoint}"
+                    )),
+                    message: OwnedParameter::empty(),
+                },
+            )
+            .expect("Should be able to query");
+
+        from_bytes(&invoke.return_value).expect("Should return a valid result")
     };
 
-    let price_data_2 = PriceData {
-        data: 73,
-        heartbeat: 12342,
-        timestamp: Timestamp::from_timestamp_millis(239),
-        price: 44,
-    };
+    // Checking the name, chain tag, and code version set at init.
 
-    // Creating signer_1's signature map
+    let contract_address = initialization_umbrella_feeds.contract_address;
 
-    let mut inner_signature_map = BTreeMap::new();
-    inner_signature_map.insert(
-        0u8,
-        concordium_std::Signature::Ed25519(SIGNATURE_TWO_PRICE_FEEDS_1),
+    assert_eq!(
+        view(&chain, contract_address, "getDeploymentName"),
+        "UmbrellaFeeds Testnet"
+    );
+    assert_eq!(
+        view(&chain, contract_address, "getChainId"),
+        "concordium-testnet"
     );
+    assert_eq!(view(&chain, contract_address, "getCodeVersion"), "8f3c2a1");
 
-    let mut signature_map = BTreeMap::new();
-    signature_map.insert(
-        0u8,
-        CredentialSignatures {
-            sigs: inner_signature_map,
-        },
+    // Checking that the key/name under which the registry stores the contract is unchanged.
+
+    assert_eq!(view(&chain, contract_address, "getName"), "UmbrellaFeeds");
+
+    // Initializing an instance with a parameter that omits the `signature_scheme`, `old_data_policy`, `name`,
+    // `chain_id`, and `code_version` fields (as created by older deploy scripts).
+
+    let module_reference = chain
+        .get_contract(contract_address)
+        .expect("The umbrella feeds contract should exist")
+        .module_reference;
+
+    let legacy_parameter = (
+        initialization_registry.contract_address,
+        2u16,
+        initialization_staking_bank.contract_address,
+        4u8,
     );
 
-    // Creating signer_2's signature map
+    let initialization_legacy = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&legacy_parameter)
+                    .expect("`legacy_parameter` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `umbrella_feeds` should succeed without the optional fields");
 
-    let mut inner_signature_map_signer_2 = BTreeMap::new();
-    inner_signature_map_signer_2.insert(
-        0u8,
-        concordium_std::Signature::Ed25519(SIGNATURE_TWO_PRICE_FEEDS_2),
+    assert_eq!(
+        view(
+            &chain,
+            initialization_legacy.contract_address,
+            "getDeploymentName"
+        ),
+        DEFAULT_DEPLOYMENT_NAME
+    );
+    assert_eq!(
+        view(&chain, initialization_legacy.contract_address, "getChainId"),
+        DEFAULT_CHAIN_ID
+    );
+    assert_eq!(
+        view(
+            &chain,
+            initialization_legacy.contract_address,
+            "getCodeVersion"
+        ),
+        ""
     );
+}
 
-    let mut signature_map_signer_2 = BTreeMap::new();
-    signature_map_signer_2.insert(
-        0u8,
-        CredentialSignatures {
-            sigs: inner_signature_map_signer_2,
-        },
+/// Test that the initialization rejects zero required signatures and decimals above `MAX_DECIMALS`.
+#[test]
+fn test_init_invalid_parameters() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let module_reference = chain
+        .get_contract(initialization_umbrella_feeds.contract_address)
+        .expect("The umbrella feeds contract should exist")
+        .module_reference;
+
+    let mut init = |required_signatures: u16, decimals: u8| {
+        let input_parameter = InitParamsUmbrellaFeeds {
+            registry: initialization_registry.contract_address,
+            required_signatures,
+            staking_bank: initialization_staking_bank.contract_address,
+            decimals,
+            signature_scheme: SignatureScheme::Ed25519,
+            old_data_policy: OldDataPolicy::Reject,
+            name: DEFAULT_DEPLOYMENT_NAME.to_string(),
+            chain_id: DEFAULT_CHAIN_ID.to_string(),
+            code_version: String::new(),
+            restrict_submitter: false,
+            max_future_skew: DEFAULT_MAX_FUTURE_SKEW,
+        };
+
+        chain.contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitParamsUmbrellaFeeds` should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let init_reject_code = |error: ContractInitError| match error.kind {
+        ContractInitErrorKind::ExecutionError {
+            error: InitExecutionError::Reject { reason, .. },
+        } => reason,
+        _ => panic!("The initialization should be rejected by the contract"),
+    };
+
+    // Checking that zero required signatures are rejected with `InvalidRequiredSignatures`.
+
+    let error = init(0, 4).expect_err("Initialization should fail");
+
+    assert_eq!(init_reject_code(error), -5);
+
+    // Checking that decimals above `MAX_DECIMALS` are rejected with `InvalidDecimals`.
+
+    let error = init(2, MAX_DECIMALS + 1).expect_err("Initialization should fail");
+
+    assert_eq!(init_reject_code(error), -24);
+
+    // Checking that `MAX_DECIMALS` decimals are accepted.
+
+    init(2, MAX_DECIMALS).expect("Initialization with `MAX_DECIMALS` decimals should succeed");
+}
+
+/// Test that the initialization rejects a malformed or truncated optional field at the end of the parameter instead
+/// of falling back to its default (only a missing field falls back to its default).
+#[test]
+fn test_init_malformed_optional_fields() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let module_reference = chain
+        .get_contract(initialization_umbrella_feeds.contract_address)
+        .expect("The umbrella feeds contract should exist")
+        .module_reference;
+
+    let mut init = |trailing_bytes: &[u8]| {
+        let mut parameter = to_bytes(&(
+            initialization_registry.contract_address,
+            2u16,
+            initialization_staking_bank.contract_address,
+            4u8,
+        ));
+        parameter.extend_from_slice(trailing_bytes);
+
+        chain.contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::try_from(parameter)
+                    .expect("The parameter should not be too large"),
+            },
+        )
+    };
+
+    // Checking that an invalid tag of the `signature_scheme` field is rejected.
+
+    init(&[9]).expect_err("Initialization should fail with a malformed `signature_scheme`");
+
+    // Checking that a truncated `name` field (a length of 10 bytes but only 3 bytes) is rejected.
+
+    init(&[0, 0, 10, 0, 0, 0, b'a', b'b', b'c'])
+        .expect_err("Initialization should fail with a truncated `name`");
+
+    // Checking that the fields up to the end of the parameter are read and the missing fields are defaulted.
+
+    init(&[1, 1]).expect("Initialization should succeed without the remaining optional fields");
+}
+
+/// Test updating the price feed with two signers using the `EcdsaSecp256k1` signature scheme.
+#[test]
+fn test_update_price_feed_ecdsa() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::EcdsaSecp256k1,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(0),
     );
 
+    // Checking the signature scheme
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.signatureScheme".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query signatureScheme");
+
+    let signature_scheme: SignatureScheme =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(signature_scheme, SignatureScheme::EcdsaSecp256k1);
+
+    let key: String = String::from("Contract1");
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
     // Creating input parameter for pice data update
 
-    let update_param = UpdateParams {
+    let update_param = UpdateParamsEcdsa {
         signers_and_signatures: vec![
             (
-                PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
-                SIGNATURE_TWO_PRICE_FEEDS_1,
+                PublicKeyEcdsaSecp256k1(ECDSA_PUBLIC_KEY_SIGNER_1),
+                ECDSA_SIGNATURE_1,
             ),
             (
-                PublicKeyEd25519(PUBLIC_KEY_SIGNER_2),
-                SIGNATURE_TWO_PRICE_FEEDS_2,
+                PublicKeyEcdsaSecp256k1(ECDSA_PUBLIC_KEY_SIGNER_2),
+                ECDSA_SIGNATURE_2,
             ),
         ],
         message: Message {
             timestamp: Timestamp::from_timestamp_millis(10000000000),
             contract_address: initialization_umbrella_feeds.contract_address,
-            price_feed: vec![(key_1.clone(), price_data_1), (key_2.clone(), price_data_2)],
+            price_feed: vec![(key.clone(), price_data)],
         },
     };
 
-    // Checking message hash to be signed
+    // Checking message hash (Keccak-256) to be signed
 
     let invoke = chain
         .contract_invoke(
@@ -291,7 +575,7 @@ fn test_update_two_price_feeds() {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.viewMessageHash".to_string(),
+                    "umbrella_feeds.viewMessageHashEcdsa".to_string(),
                 ),
                 message: OwnedParameter::from_serial(&update_param)
                     .expect("Should be a valid inut parameter"),
@@ -302,19 +586,33 @@ fn test_update_two_price_feeds() {
     let message_hash: [u8; 32] =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    println!(
-        "Signer sign this message hash: {}",
-        HashSha2256(message_hash)
-    );
+    assert_eq!(message_hash, ECDSA_MESSAGE_HASH);
 
-    let signature:SignatureEd25519 = "B0A61D5E1B1E25A463F9F1BBF2F3C55BB571EE2CF405BCFB6CD4C22BB55817201C88320EFD70EB618487D5866BDCBAF56F6256EE771839C86F26246463DCA20A".parse().unwrap();
-    println!("Signature: {:?}", signature.0);
+    // Updating price data with `Ed25519` signatures fails
 
-    let public_key: PublicKeyEd25519 =
-        "D96C4B1218EA7EC20F4604D6C2F02FA3F36B518443F3A2D14E885E7FF715DEDD"
-            .parse()
-            .unwrap();
-    println!("Public key: {:?}", public_key.0);
+    let ed25519_update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: update_param.message.clone(),
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&ed25519_update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should not be able to update with `Ed25519` signatures");
 
     // Updating price data in contract
 
@@ -332,7 +630,7 @@ fn test_update_two_price_feeds() {
                     .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to update operator with permit");
+        .expect("Should be able to update price data with ECDSA signatures");
 
     // Checking price data was updated correctly in contract
 
@@ -345,45 +643,84 @@ fn test_update_two_price_feeds() {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.getManyPriceData".to_string(),
+                    "umbrella_feeds.getPriceData".to_string(),
                 ),
-                message: OwnedParameter::from_serial(&vec![key_1.clone(), key_2.clone()])
+                message: OwnedParameter::from_serial(&key)
                     .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query getManyPriceData");
+        .expect("Should be able to query getPriceData");
 
-    let stored_price_data: Vec<PriceData> =
+    let stored_price_data: PriceData =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(stored_price_data, vec![price_data_1, price_data_2]);
+    assert_eq!(stored_price_data, price_data);
+}
+
+/// Test that the batch read functions reject more than `MAX_KEYS` keys.
+#[test]
+fn test_get_many_price_data_too_many_keys() {
+    let (
+        chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let keys: Vec<String> = (0..=MAX_KEYS).map(|i| format!("Contract{i}")).collect();
+
+    for entrypoint in [
+        "umbrella_feeds.getManyPriceData",
+        "umbrella_feeds.getManyPriceDataRaw",
+        "umbrella_feeds.getManyPriceDataRawWithBlockTime",
+    ] {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(100000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_umbrella_feeds.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(entrypoint.to_string()),
+                    message: OwnedParameter::from_serial(&keys)
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect_err("Should not be able to query too many keys");
+
+        // Checking that the error is `TooManyKeys`.
+        assert_eq!(reject_code(&invoke), -22, "Error is wrong");
+    }
+
+    // Querying `MAX_KEYS` keys succeeds.
 
     let invoke = chain
         .contract_invoke(
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
-            Energy::from(10000),
+            Energy::from(100000),
             UpdateContractPayload {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.getManyPriceData".to_string(),
+                    "umbrella_feeds.getManyPriceDataRaw".to_string(),
                 ),
-                message: OwnedParameter::from_serial(&vec![key_1, key_2])
+                message: OwnedParameter::from_serial(&keys[..MAX_KEYS].to_vec())
                     .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query getManyPriceData");
+        .expect("Should be able to query getManyPriceDataRaw");
 
-    let stored_price_data: Vec<PriceData> =
+    let stored_price_data: Vec<Option<PriceData>> =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(stored_price_data, vec![price_data_1, price_data_2]);
+    assert_eq!(stored_price_data, vec![None; MAX_KEYS]);
 }
 
-/// Test updating the price feed with two signer and one price feed.
+/// Test updating the price feed with two signers and two price feeds.
 #[test]
-fn test_update_price_feed() {
+fn test_update_two_price_feeds() {
     let (
         mut chain,
         initialization_umbrella_feeds,
@@ -392,20 +729,201 @@ fn test_update_price_feed() {
     ) = setup_chain_and_contract();
 
     let key_1: String = String::from("Contract1");
-    let non_existing_key: String = String::from("blabla");
 
-    let price_data = PriceData {
+    let key_2: String = String::from("Contract2");
+
+    let price_data_1 = PriceData {
         data: 7,
         heartbeat: 12,
         timestamp: Timestamp::from_timestamp_millis(9),
         price: 4,
     };
 
+    let price_data_2 = PriceData {
+        data: 73,
+        heartbeat: 12342,
+        timestamp: Timestamp::from_timestamp_millis(239),
+        price: 44,
+    };
+
     // Creating signer_1's signature map
 
     let mut inner_signature_map = BTreeMap::new();
-    inner_signature_map.insert(0u8, concordium_std::Signature::Ed25519(SIGNATURE_1));
-
+    inner_signature_map.insert(
+        0u8,
+        concordium_std::Signature::Ed25519(SIGNATURE_TWO_PRICE_FEEDS_1),
+    );
+
+    let mut signature_map = BTreeMap::new();
+    signature_map.insert(
+        0u8,
+        CredentialSignatures {
+            sigs: inner_signature_map,
+        },
+    );
+
+    // Creating signer_2's signature map
+
+    let mut inner_signature_map_signer_2 = BTreeMap::new();
+    inner_signature_map_signer_2.insert(
+        0u8,
+        concordium_std::Signature::Ed25519(SIGNATURE_TWO_PRICE_FEEDS_2),
+    );
+
+    let mut signature_map_signer_2 = BTreeMap::new();
+    signature_map_signer_2.insert(
+        0u8,
+        CredentialSignatures {
+            sigs: inner_signature_map_signer_2,
+        },
+    );
+
+    // Creating input parameter for pice data update
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+                SIGNATURE_TWO_PRICE_FEEDS_1,
+            ),
+            (
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_2),
+                SIGNATURE_TWO_PRICE_FEEDS_2,
+            ),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(key_1.clone(), price_data_1), (key_2.clone(), price_data_2)],
+        },
+    };
+
+    // Checking message hash to be signed
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.viewMessageHash".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query messageHash");
+
+    let message_hash: [u8; 32] =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    println!(
+        "Signer sign this message hash: {}",
+        HashSha2256(message_hash)
+    );
+
+    let signature:SignatureEd25519 = "B0A61D5E1B1E25A463F9F1BBF2F3C55BB571EE2CF405BCFB6CD4C22BB55817201C88320EFD70EB618487D5866BDCBAF56F6256EE771839C86F26246463DCA20A".parse().unwrap();
+    println!("Signature: {:?}", signature.0);
+
+    let public_key: PublicKeyEd25519 =
+        "D96C4B1218EA7EC20F4604D6C2F02FA3F36B518443F3A2D14E885E7FF715DEDD"
+            .parse()
+            .unwrap();
+    println!("Public key: {:?}", public_key.0);
+
+    // Updating price data in contract
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update operator with permit");
+
+    // Checking price data was updated correctly in contract
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getManyPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![key_1.clone(), key_2.clone()])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query getManyPriceData");
+
+    let stored_price_data: Vec<PriceData> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, vec![price_data_1, price_data_2]);
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getManyPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![key_1, key_2])
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query getManyPriceData");
+
+    let stored_price_data: Vec<PriceData> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, vec![price_data_1, price_data_2]);
+}
+
+/// Test updating the price feed with two signer and one price feed.
+#[test]
+fn test_update_price_feed() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let key_1: String = String::from("Contract1");
+    let non_existing_key: String = String::from("blabla");
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    // Creating signer_1's signature map
+
+    let mut inner_signature_map = BTreeMap::new();
+    inner_signature_map.insert(0u8, concordium_std::Signature::Ed25519(SIGNATURE_1));
+
     let mut signature_map = BTreeMap::new();
     signature_map.insert(
         0u8,
@@ -514,10 +1032,17 @@ fn test_update_price_feed() {
         )
         .expect("Should be able to query prices");
 
-    let stored_price_data: PriceData =
+    let stored_price_data: PriceDataWithDecimals =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(stored_price_data, price_data);
+    // Without an override, the `decimals` of the contract are returned.
+    assert_eq!(
+        stored_price_data,
+        PriceDataWithDecimals {
+            price_data,
+            decimals: 4,
+        }
+    );
 
     let invoke = chain
         .contract_invoke(
@@ -563,6 +1088,34 @@ fn test_update_price_feed() {
 
     assert_eq!(no_price_data, [None]);
 
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceDataRaw".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query prices");
+
+    let stored_price_data: PriceDataRaw =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        stored_price_data,
+        PriceDataRaw {
+            success: true,
+            price_data,
+        }
+    );
+
     let invoke = chain
         .contract_invoke(
             ACC_ADDR_OWNER,
@@ -684,9 +1237,136 @@ fn test_update_price_feed() {
     );
 }
 
-/// Test updating the price feed with the `ETH-CCD` feed name.
+/// Test re-submitting price data that is not newer than the stored price data with both old data policies.
 #[test]
-fn test_update_price_feed_and_check_price_via_feed_name() {
+fn test_old_data_policy() {
+    for old_data_policy in [OldDataPolicy::Reject, OldDataPolicy::Skip] {
+        let (
+            mut chain,
+            initialization_umbrella_feeds,
+            _initialization_registry,
+            _initialization_staking_bank,
+        ) = setup_chain_and_contract_with(
+            SignatureScheme::Ed25519,
+            old_data_policy,
+            Timestamp::from_timestamp_millis(0),
+        );
+
+        // Checking the old data policy
+
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_umbrella_feeds.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.oldDataPolicy".to_string(),
+                    ),
+                    message: OwnedParameter::empty(),
+                },
+            )
+            .expect("Should be able to query oldDataPolicy");
+
+        let stored_old_data_policy: OldDataPolicy =
+            from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        assert_eq!(stored_old_data_policy, old_data_policy);
+
+        let key_1: String = String::from("Contract1");
+
+        let price_data = PriceData {
+            data: 7,
+            heartbeat: 12,
+            timestamp: Timestamp::from_timestamp_millis(9),
+            price: 4,
+        };
+
+        let update_param = UpdateParams {
+            signers_and_signatures: vec![
+                (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+                (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+            ],
+            message: Message {
+                timestamp: Timestamp::from_timestamp_millis(10000000000),
+                contract_address: initialization_umbrella_feeds.contract_address,
+                price_feed: vec![(key_1.clone(), price_data)],
+            },
+        };
+
+        let update_payload = UpdateContractPayload {
+            amount: Amount::zero(),
+            address: initialization_umbrella_feeds.contract_address,
+            receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+            message: OwnedParameter::from_serial(&update_param)
+                .expect("Should be a valid inut parameter"),
+        };
+
+        // Updating price data in contract
+
+        let _update = chain
+            .contract_update(
+                Signer::with_one_key(),
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                update_payload.clone(),
+            )
+            .expect("Should be able to update price data");
+
+        // Re-submitting the same price data (not strictly newer)
+
+        let update = chain.contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            update_payload,
+        );
+
+        match old_data_policy {
+            OldDataPolicy::Reject => {
+                let error = update.expect_err("Should not be able to update with old data");
+
+                // Checking that the error is `OldData`.
+                assert_eq!(reject_code(&error), -6, "Error is wrong");
+            }
+            OldDataPolicy::Skip => {
+                update.expect("Should be able to update with old data (skipped)");
+            }
+        }
+
+        // Checking that the stored price data is unchanged
+
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_umbrella_feeds.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.getPriceData".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&key_1)
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to query getPriceData");
+
+        let stored_price_data: PriceData =
+            from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        assert_eq!(stored_price_data, price_data);
+    }
+}
+
+/// Test that the `update` function is rejected while the contract is paused, while reads continue to work.
+#[test]
+fn test_pause() {
     let (
         mut chain,
         initialization_umbrella_feeds,
@@ -694,6 +1374,10 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
         _initialization_staking_bank,
     ) = setup_chain_and_contract();
 
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let key_1: String = String::from("Contract1");
+
     let price_data = PriceData {
         data: 7,
         heartbeat: 12,
@@ -701,62 +1385,2792 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
         price: 4,
     };
 
-    // Creating signer_1's signature map
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(key_1.clone(), price_data)],
+        },
+    };
 
-    let mut inner_signature_map = BTreeMap::new();
-    inner_signature_map.insert(
-        0u8,
-        concordium_std::Signature::Ed25519(SIGNATURE_ETH_CCD_FEEDS_1),
-    );
+    let update_payload = UpdateContractPayload {
+        amount: Amount::zero(),
+        address: initialization_umbrella_feeds.contract_address,
+        receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+        message: OwnedParameter::from_serial(&update_param)
+            .expect("Should be a valid inut parameter"),
+    };
 
-    let mut signature_map = BTreeMap::new();
-    signature_map.insert(
-        0u8,
-        CredentialSignatures {
-            sigs: inner_signature_map,
-        },
-    );
+    let invoke_entrypoint = |chain: &mut Chain, sender: AccountAddress, entrypoint: &str| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(format!(
+                    "umbrella_feeds.{entrypoint}"
+                )),
+                message: OwnedParameter::empty(),
+            },
+        )
+    };
 
-    // Creating signer_2's signature map
+    let paused = |chain: &Chain| -> bool {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_umbrella_feeds.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.paused".to_string(),
+                    ),
+                    message: OwnedParameter::empty(),
+                },
+            )
+            .expect("Should be able to query paused");
+
+        from_bytes(&invoke.return_value).expect("Should return a valid result")
+    };
 
-    let mut inner_signature_map_signer_2 = BTreeMap::new();
-    inner_signature_map_signer_2.insert(
-        0u8,
-        concordium_std::Signature::Ed25519(SIGNATURE_ETH_CCD_FEEDS_2),
+    assert!(!paused(&chain));
+
+    // Updating price data in contract
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            update_payload.clone(),
+        )
+        .expect("Should be able to update price data");
+
+    // Invoking 'pause' from an account that is not the owner of the registry.
+
+    let update = invoke_entrypoint(&mut chain, OTHER_ACCOUNT, "pause")
+        .expect_err("Should not be able to pause");
+
+    // Checking that the error is `Unauthorized`.
+    assert_eq!(reject_code(&update), -10, "Error is wrong");
+
+    // Invoking 'pause'.
+
+    let update =
+        invoke_entrypoint(&mut chain, ACC_ADDR_OWNER, "pause").expect("Should be able to pause");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::Paused(PausedEvent {
+            sender: Address::Account(ACC_ADDR_OWNER),
+        })],
+        "Events are wrong"
     );
 
-    let mut signature_map_signer_2 = BTreeMap::new();
-    signature_map_signer_2.insert(
-        0u8,
-        CredentialSignatures {
-            sigs: inner_signature_map_signer_2,
+    assert!(paused(&chain));
+
+    // Checking that updates are rejected while paused.
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            update_payload,
+        )
+        .expect_err("Should not be able to update price data while paused");
+
+    // Checking that the error is `Paused`.
+    assert_eq!(reject_code(&update), -23, "Error is wrong");
+
+    // Checking that reads continue to work while paused.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query getPriceData while paused");
+
+    let stored_price_data: PriceData =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, price_data);
+
+    // Invoking 'unpause' from an account that is not the owner of the registry.
+
+    let update = invoke_entrypoint(&mut chain, OTHER_ACCOUNT, "unpause")
+        .expect_err("Should not be able to unpause");
+
+    // Checking that the error is `Unauthorized`.
+    assert_eq!(reject_code(&update), -10, "Error is wrong");
+
+    // Invoking 'unpause'.
+
+    let update = invoke_entrypoint(&mut chain, ACC_ADDR_OWNER, "unpause")
+        .expect("Should be able to unpause");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::Unpaused(UnpausedEvent {
+            sender: Address::Account(ACC_ADDR_OWNER),
+        })],
+        "Events are wrong"
+    );
+
+    assert!(!paused(&chain));
+}
+
+/// Test overriding the decimals of a price feed.
+#[test]
+fn test_set_feed_decimals() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let key_1: String = String::from("Contract1");
+
+    let set_feed_decimals = |chain: &mut Chain, sender: AccountAddress, decimals: u8| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setFeedDecimals".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SetFeedDecimalsParams {
+                    key: key_1.clone(),
+                    decimals,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    // Checking that an account that is not the owner of the registry contract cannot set the decimals.
+
+    let update =
+        set_feed_decimals(&mut chain, OTHER_ACCOUNT, 18).expect_err("Should fail to set decimals");
+
+    // Checking that the error is `Unauthorized`.
+    assert_eq!(reject_code(&update), -10, "Error is wrong");
+
+    // Checking that decimals above `MAX_DECIMALS` are rejected.
+
+    let update = set_feed_decimals(&mut chain, ACC_ADDR_OWNER, MAX_DECIMALS + 1)
+        .expect_err("Should fail to set decimals");
+
+    // Checking that the error is `InvalidDecimals`.
+    assert_eq!(reject_code(&update), -24, "Error is wrong");
+
+    // Setting the decimals of the price feed before its first price data is submitted.
+
+    let update =
+        set_feed_decimals(&mut chain, ACC_ADDR_OWNER, 18).expect("Should be able to set decimals");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::FeedDecimalsChanged(FeedDecimalsChangedEvent {
+            key: key_1.clone(),
+            previous_decimals: 4,
+            new_decimals: 18,
+        })],
+        "Events are wrong"
+    );
+
+    // Updating price data in contract
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(key_1.clone(), price_data)],
         },
+    };
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    // Checking that `getPriceData` returns the decimals of the price feed.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query getPriceData");
+
+    let stored_price_data: PriceDataWithDecimals =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        stored_price_data,
+        PriceDataWithDecimals {
+            price_data,
+            decimals: 18,
+        }
     );
 
-    // Creating input parameter for pice data update
+    // Checking that the `DECIMALS` of the contract are not changed.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.DECIMALS".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query");
+
+    let value: u8 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, 4);
+}
+
+/// Test setting the heartbeat of a price feed: it takes precedence over the heartbeat of the stored price data in
+/// `getPrice` and `getPriceTimestampHeartbeat`.
+#[test]
+fn test_set_heartbeat() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(100000),
+    );
+
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let key_1: String = String::from("Contract1");
+
+    let set_heartbeat = |chain: &mut Chain, sender: AccountAddress, heartbeat: u64| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setHeartbeat".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SetHeartbeatParams {
+                    key: key_1.clone(),
+                    heartbeat,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let view = |chain: &Chain, entrypoint: &str| {
+        chain.contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(entrypoint.to_string()),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let heartbeat = |chain: &Chain| {
+        let invoke = view(chain, "umbrella_feeds.getHeartbeat")
+            .expect("Should be able to query getHeartbeat");
+
+        from_bytes::<Option<u64>>(&invoke.return_value).expect("Should return a valid result")
+    };
+
+    assert_eq!(heartbeat(&chain), None);
+
+    // Checking that an account that is not the owner of the registry contract cannot set the heartbeat.
+
+    let update =
+        set_heartbeat(&mut chain, OTHER_ACCOUNT, 3600).expect_err("Should fail to set heartbeat");
+
+    // Checking that the error is `Unauthorized`.
+    assert_eq!(reject_code(&update), -10, "Error is wrong");
+
+    // Checking that a zero heartbeat and a heartbeat above `MAX_HEARTBEAT` are rejected.
+
+    for invalid_heartbeat in [0, MAX_HEARTBEAT + 1] {
+        let update = set_heartbeat(&mut chain, ACC_ADDR_OWNER, invalid_heartbeat)
+            .expect_err("Should fail to set heartbeat");
+
+        // Checking that the error is `InvalidHeartbeat`.
+        assert_eq!(reject_code(&update), -20, "Error is wrong");
+    }
+
+    // Updating price data in contract (with a heartbeat of 12 seconds)
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
 
     let update_param = UpdateParams {
         signers_and_signatures: vec![
-            (
-                PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
-                SIGNATURE_ETH_CCD_FEEDS_1,
-            ),
-            (
-                PublicKeyEd25519(PUBLIC_KEY_SIGNER_2),
-                SIGNATURE_ETH_CCD_FEEDS_2,
-            ),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
         ],
         message: Message {
             timestamp: Timestamp::from_timestamp_millis(10000000000),
             contract_address: initialization_umbrella_feeds.contract_address,
-            price_feed: vec![(String::from("ETH-CCD"), price_data)],
+            price_feed: vec![(key_1.clone(), price_data)],
         },
     };
 
-    // Checking message hash to be signed
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    // Checking that the price data (100 seconds old) is stale after its own heartbeat.
+
+    let invoke =
+        view(&chain, "umbrella_feeds.getPrice").expect_err("Should fail to query stale price");
+
+    // Checking that the error is `StaleData`.
+    assert_eq!(reject_code(&invoke), -21, "Error is wrong");
+
+    // Setting the heartbeat of the price feed to one hour.
+
+    let update =
+        set_heartbeat(&mut chain, ACC_ADDR_OWNER, 3600).expect("Should be able to set heartbeat");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::HeartbeatChanged(HeartbeatChangedEvent {
+            key: key_1.clone(),
+            previous_heartbeat: None,
+            new_heartbeat: 3600,
+        })],
+        "Events are wrong"
+    );
+
+    assert_eq!(heartbeat(&chain), Some(3600));
+
+    // Checking that the price data is fresh within the configured heartbeat.
+
+    let invoke = view(&chain, "umbrella_feeds.getPrice").expect("Should be able to query price");
+
+    let price: u128 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(price, price_data.price);
+
+    let invoke = view(&chain, "umbrella_feeds.getPriceTimestampHeartbeat")
+        .expect("Should be able to query getPriceTimestampHeartbeat");
+
+    let triple: SchemTypeTripleWrapper =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(triple.heartbeat, 3600);
+
+    // Changing the heartbeat logs the previous heartbeat.
+
+    let update =
+        set_heartbeat(&mut chain, ACC_ADDR_OWNER, 60).expect("Should be able to set heartbeat");
+
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::HeartbeatChanged(HeartbeatChangedEvent {
+            key: key_1.clone(),
+            previous_heartbeat: Some(3600),
+            new_heartbeat: 60,
+        })],
+        "Events are wrong"
+    );
+
+    // Checking that the price data is stale after the configured heartbeat.
+
+    let invoke =
+        view(&chain, "umbrella_feeds.getPrice").expect_err("Should fail to query stale price");
+
+    // Checking that the error is `StaleData`.
+    assert_eq!(reject_code(&invoke), -21, "Error is wrong");
+}
+
+/// Test that the single-key views reject unknown price feeds instead of returning zeros.
+#[test]
+fn test_unknown_price_feed() {
+    let (
+        chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let non_existing_key: String = String::from("blabla");
+
+    for entrypoint in [
+        "getPrice",
+        "getPriceTimestamp",
+        "getPriceTimestampHeartbeat",
+        "getPriceData",
+    ] {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: initialization_umbrella_feeds.contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(format!(
+                        "umbrella_feeds.{entrypoint}"
+                    )),
+                    message: OwnedParameter::from_serial(&non_existing_key)
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect_err("Should fail to query an unknown price feed");
+
+        // Checking that the error is `FeedNotExist`.
+        assert_eq!(reject_code(&invoke), -9, "Error of `{entrypoint}` is wrong");
+    }
+
+    // Checking that `getPriceDataRaw` does not fail but returns zeroed price data.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceDataRaw".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&non_existing_key)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query an unknown price feed");
+
+    let price_data_raw: PriceDataRaw =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        price_data_raw,
+        PriceDataRaw {
+            success: false,
+            price_data: PriceData::zero(),
+        }
+    );
+    assert_eq!(
+        price_data_raw.price_data.timestamp,
+        Timestamp::from_timestamp_millis(0)
+    );
+}
+
+/// Test that stale price data is rejected by the `getPrice` and `getPriceDataFresh` functions.
+#[test]
+fn test_stale_price_data() {
+    // The block time is 13 seconds after the price data timestamp, i.e. the price data is older than its heartbeat.
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(13009),
+    );
+
+    let key_1: String = String::from("Contract1");
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    // Updating price data with a zero heartbeat fails
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(
+                key_1.clone(),
+                PriceData {
+                    heartbeat: 0,
+                    ..price_data
+                },
+            )],
+        },
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should not be able to update price data with a zero heartbeat");
+
+    // Checking that the error is `InvalidHeartbeat`.
+    assert_eq!(reject_code(&update), -20, "Error is wrong");
+
+    // Updating price data in contract
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(key_1.clone(), price_data)],
+        },
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    let get_price = |chain: &Chain| {
+        chain.contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPrice".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let get_price_data_fresh = |chain: &Chain, max_age_seconds: u64| {
+        chain.contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceDataFresh".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&GetPriceDataFreshParams {
+                    key: key_1.clone(),
+                    max_age_seconds,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    // Checking that the price data is stale (older than its heartbeat)
+
+    let invoke = get_price(&chain).expect_err("Should not be able to query a stale price");
+
+    // Checking that the error is `StaleData`.
+    assert_eq!(reject_code(&invoke), -21, "Error is wrong");
+
+    let invoke =
+        get_price_data_fresh(&chain, 5).expect_err("Should not be able to query stale price data");
+
+    // Checking that the error is `StaleData`.
+    assert_eq!(reject_code(&invoke), -21, "Error is wrong");
+
+    let invoke =
+        get_price_data_fresh(&chain, 20).expect("Should be able to query fresh price data");
+
+    let stored_price_data: PriceData =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, price_data);
+}
+
+/// Test updating the price feed with the `ETH-CCD` feed name.
+#[test]
+fn test_update_price_feed_and_check_price_via_feed_name() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    // Creating signer_1's signature map
+
+    let mut inner_signature_map = BTreeMap::new();
+    inner_signature_map.insert(
+        0u8,
+        concordium_std::Signature::Ed25519(SIGNATURE_ETH_CCD_FEEDS_1),
+    );
+
+    let mut signature_map = BTreeMap::new();
+    signature_map.insert(
+        0u8,
+        CredentialSignatures {
+            sigs: inner_signature_map,
+        },
+    );
+
+    // Creating signer_2's signature map
+
+    let mut inner_signature_map_signer_2 = BTreeMap::new();
+    inner_signature_map_signer_2.insert(
+        0u8,
+        concordium_std::Signature::Ed25519(SIGNATURE_ETH_CCD_FEEDS_2),
+    );
+
+    let mut signature_map_signer_2 = BTreeMap::new();
+    signature_map_signer_2.insert(
+        0u8,
+        CredentialSignatures {
+            sigs: inner_signature_map_signer_2,
+        },
+    );
+
+    // Creating input parameter for pice data update
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_1),
+                SIGNATURE_ETH_CCD_FEEDS_1,
+            ),
+            (
+                PublicKeyEd25519(PUBLIC_KEY_SIGNER_2),
+                SIGNATURE_ETH_CCD_FEEDS_2,
+            ),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(String::from("ETH-CCD"), price_data)],
+        },
+    };
+
+    // Checking message hash to be signed
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.viewMessageHash".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query messageHash");
+
+    let message_hash: [u8; 32] =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    println!(
+        "Signer sign this message hash: {}",
+        HashSha2256(message_hash)
+    );
+
+    let signature:SignatureEd25519 = "9F4A71B52C1043080A802F7FF686989FF66B39DA680EDED104D90C7572B550D7EA129C8F0FB4ADAE9BD98BF017D14C425C0D1398B8D81D17DBA7D99EA65FD807".parse().unwrap();
+    println!("Signature: {:?}", signature.0);
+
+    let public_key: PublicKeyEd25519 =
+        "D96C4B1218EA7EC20F4604D6C2F02FA3F36B518443F3A2D14E885E7FF715DEDD"
+            .parse()
+            .unwrap();
+    println!("Public key: {:?}", public_key.0);
+
+    // Updating price data in contract
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update operator with permit");
+
+    // Checking price data was updated correctly in contract.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&String::from("ETH-CCD"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query timestam");
+
+    let stored_price_data: PriceData =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, price_data);
+}
+
+#[test]
+fn test_get_name() {
+    let (
+        chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    // Checking getName.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.getName".to_string()),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query contract name");
+
+    let value: String = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, String::from("UmbrellaFeeds"));
+}
+
+#[test]
+fn test_get_required_signatures() {
+    let (
+        chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    // Checking requiredSignatures.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.requiredSignatures".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query required signatures");
+
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, 2u16);
+}
+
+#[test]
+fn test_set_required_signatures() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let set_required_signatures = |chain: &mut Chain, sender: AccountAddress, value: u16| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setRequiredSignatures".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&value)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    // Invoking 'setRequiredSignatures' from an account that is not the owner of the registry.
+
+    let update = set_required_signatures(&mut chain, OTHER_ACCOUNT, 1)
+        .expect_err("Should not be able to set required signatures");
+
+    // Checking that the error is `Unauthorized`.
+    assert_eq!(reject_code(&update), -10, "Error is wrong");
+
+    // Invoking 'setRequiredSignatures' with zero and more than the number of validators (2).
+
+    for value in [0u16, 3u16] {
+        let update = set_required_signatures(&mut chain, ACC_ADDR_OWNER, value)
+            .expect_err("Should not be able to set invalid required signatures");
+
+        // Checking that the error is `InvalidRequiredSignatures`.
+        assert_eq!(reject_code(&update), -5, "Error is wrong");
+    }
+
+    // Invoking 'setRequiredSignatures'.
+
+    let update = set_required_signatures(&mut chain, ACC_ADDR_OWNER, 1)
+        .expect("Should be able to set required signatures");
+
+    // Checking logged event.
+    let events: Vec<Event> = update
+        .events()
+        .flat_map(|(_, events)| events)
+        .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+        .collect();
+
+    assert_eq!(
+        events,
+        vec![Event::RequiredSignaturesChanged(
+            RequiredSignaturesChangedEvent {
+                previous_required_signatures: 2,
+                new_required_signatures: 1,
+            }
+        )],
+        "Events are wrong"
+    );
+
+    // Checking requiredSignatures.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.requiredSignatures".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query required signatures");
+
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(value, 1u16);
+}
+
+#[test]
+fn test_upgrade_without_migration_function() {
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    // Importing umbrella_feeds into the registry contract
+
+    let input_parameter = ImportContractsParam {
+        entries: vec![initialization_umbrella_feeds.contract_address],
+    };
+
+    let _update = chain.contract_update(
+        Signer::with_one_key(), // Used for specifying the number of signatures.
+        ACC_ADDR_OWNER,         // Invoker account.
+        Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+        Energy::from(10000),    // Maximum energy allowed for the update.
+        UpdateContractPayload {
+            address: initialization_registry.contract_address, // The contract to update.
+            receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()), // The receive function to call.
+            message: OwnedParameter::from_serial(&input_parameter)
+                .expect("`UpgradeParams` should be a valid inut parameter"), // The parameter sent to the contract.
+            amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+        },
+    );
+
+    // Checking that the contract was registered correctly
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("UmbrellaFeeds"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contract address");
+
+    let contract_address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        contract_address,
+        initialization_umbrella_feeds.contract_address
+    );
+
+    // Deploying an upgraded umbrella_feeds module.
+
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("./umbrella_feeds_update.wasm.v1")
+                .expect("`Contract version2` module should be loaded"),
+        )
+        .expect("`Contract version2` deployment should always succeed");
+
+    // Upgrading umbrella_feeds contract with the new module reference.
+
+    let input_parameter = AtomicUpdateParam {
+        module: deployment.module_reference,
+        migrate: None,
+        contract_address: initialization_umbrella_feeds.contract_address,
+    };
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(100000),   // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_registry.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdate".into()), // The receive function to call.
+                message: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`UpgradeParams` should be a valid inut parameter"), // The parameter sent to the contract.
+                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to update");
+
+    // Checking that the upgrade was successfully by calling a function that only exists in the upgraded `umbrella_feeds` contract.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(), // Used for specifying the number of signatures.
+            ACC_ADDR_OWNER,         // Invoker account.
+            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
+            Energy::from(10000),    // Maximum energy allowed for the update.
+            UpdateContractPayload {
+                address: initialization_umbrella_feeds.contract_address, // The contract to update.
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.DECIMALS_2".into()), // The receive function to call.
+                message: OwnedParameter::empty(),
+                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+            },
+        )
+        .expect("Should be able to update");
+
+    // Checking that the contract was updated in the registry correctly
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_registry.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
+                message: OwnedParameter::from_serial(&String::from("UmbrellaFeeds"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query contract address");
+
+    let contract_address: ContractAddress =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        contract_address,
+        initialization_umbrella_feeds.contract_address
+    );
+}
+
+/// Sets up the chain like `setup_chain_and_contract`, but with the `dummy_contract` contract as the staking bank.
+/// It reports all keys as validators and can be set up to re-enter the umbrella feeds contract
+/// (see the `setReentrantCall` entry point).
+/// Returns the chain, the umbrella feeds contract, and the dummy contract.
+fn setup_chain_and_contract_with_dummy_staking_bank(
+) -> (Chain, ContractInitSuccess, ContractInitSuccess) {
+    let mut chain = Chain::new();
+
+    chain.create_account(Account::new(ACC_ADDR_OWNER, ACC_INITIAL_BALANCE));
+
+    // Deploying 'registry' contract
+
+    let deployment_registry = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../registry/registry.wasm.v1")
+                .expect("`registry.wasm.v1` module should be loaded"),
+        )
+        .expect("`registry.wasm.v1` deployment should always succeed");
+
+    let initialization_registry = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment_registry.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_registry".to_string()),
+                param: OwnedParameter::empty(),
+            },
+        )
+        .expect("Initialization of `registry` should always succeed");
+
+    // Deploying 'dummy_contract' contract as the staking bank. It is initialized at the address of the staking bank
+    // in `setup_chain_and_contract`, so that the umbrella feeds contract gets the same address and the signatures
+    // of `test_update_price_feed` are valid.
+
+    let deployment_dummy_contract = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("../dummy-contract/dummy-contract.wasm.v1")
+                .expect("`dummy-contract.wasm.v1` module should be loaded"),
+        )
+        .expect("`dummy-contract.wasm.v1` deployment should always succeed");
+
+    let initialization_dummy_contract = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment_dummy_contract.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_dummy_contract".to_string()),
+                param: OwnedParameter::empty(),
+            },
+        )
+        .expect("Initialization of `dummy-contract` should always succeed");
+
+    // Deploy 'umbrella_feeds' contract
+
+    let deployment = chain
+        .module_deploy_v1(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            module_load_v1("./umbrella_feeds.wasm.v1")
+                .expect("`Umbrella_feeds.wasm.v1` module should be loaded"),
+        )
+        .expect("`Umbrella_feeds.wasm.v1` deployment should always succeed");
+
+    let input_parameter = InitParamsUmbrellaFeeds {
+        registry: initialization_registry.contract_address,
+        required_signatures: 2,
+        staking_bank: initialization_dummy_contract.contract_address,
+        decimals: 4,
+        signature_scheme: SignatureScheme::Ed25519,
+        old_data_policy: OldDataPolicy::Reject,
+        name: DEFAULT_DEPLOYMENT_NAME.to_string(),
+        chain_id: DEFAULT_CHAIN_ID.to_string(),
+        code_version: String::new(),
+        restrict_submitter: false,
+        max_future_skew: DEFAULT_MAX_FUTURE_SKEW,
+    };
+
+    let initialization_umbrella_feeds = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: deployment.module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitContractsParam` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `umbrella_feeds` should always succeed");
+
+    (
+        chain,
+        initialization_umbrella_feeds,
+        initialization_dummy_contract,
+    )
+}
+
+#[test]
+fn test_update_reentrancy() {
+    let (mut chain, initialization_umbrella_feeds, initialization_dummy_contract) =
+        setup_chain_and_contract_with_dummy_staking_bank();
+
+    let key_1: String = String::from("Contract1");
+
+    let price_data = PriceData {
+        data: 7,
+        heartbeat: 12,
+        timestamp: Timestamp::from_timestamp_millis(9),
+        price: 4,
+    };
+
+    let update_param = UpdateParams {
+        signers_and_signatures: vec![
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_1), SIGNATURE_1),
+            (PublicKeyEd25519(PUBLIC_KEY_SIGNER_2), SIGNATURE_2),
+        ],
+        message: Message {
+            timestamp: Timestamp::from_timestamp_millis(10000000000),
+            contract_address: initialization_umbrella_feeds.contract_address,
+            price_feed: vec![(key_1.clone(), price_data)],
+        },
+    };
+
+    // Setting up the malicious staking bank to invoke the `update` entry point with the same parameter
+    // while the signers are verified. The parameter is serialized like the `ReentrantCall` of the dummy contract.
+
+    let reentrant_call = (
+        initialization_umbrella_feeds.contract_address,
+        OwnedEntrypointName::new_unchecked("update".to_string()),
+        to_bytes(&update_param),
+    );
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_dummy_contract.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "dummy_contract.setReentrantCall".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&reentrant_call)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to set the reentrant call");
+
+    // Updating price data in contract. The nested invoke of the `update` entry point is rejected by the guard.
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(100000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    // Checking that the nested invoke was rejected with `Reentrancy`
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_dummy_contract.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "dummy_contract.getLastRejectCode".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the last reject code");
+
+    let last_reject_code: Option<i32> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(last_reject_code, Some(-25));
+
+    // Checking that the price data of the outer update was stored
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&key_1)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query prices");
+
+    let stored_price_data: PriceDataWithDecimals =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data.price_data, price_data);
+}
+
+#[cfg(feature = "dev")]
+#[test]
+fn test_update_with_generated_validator_keys() {
+    use sha2::{Digest, Sha256};
+    use umbrella_feeds::test_harness::{
+        build_update_message, build_update_params, generate_validator_keys, sign_update,
+    };
+
+    let (mut chain, initialization_umbrella_feeds, _initialization_dummy_contract) =
+        setup_chain_and_contract_with_dummy_staking_bank();
+
+    let keys = generate_validator_keys(3);
+
+    let timestamp = Timestamp::from_timestamp_millis(10000000000);
+
+    let prices = vec![
+        (
+            String::from("ETH-USD"),
+            PriceData {
+                data: 0,
+                heartbeat: 60,
+                timestamp: Timestamp::from_timestamp_millis(9),
+                price: 1800,
+            },
+        ),
+        (
+            String::from("BTC-USD"),
+            PriceData {
+                data: 0,
+                heartbeat: 60,
+                timestamp: Timestamp::from_timestamp_millis(9),
+                price: 30000,
+            },
+        ),
+    ];
+
+    let update_param = build_update_params(
+        &keys,
+        initialization_umbrella_feeds.contract_address,
+        timestamp,
+        prices.clone(),
+    );
+
+    // Checking that the signed message bytes match the message hash of the contract
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.viewMessageHash".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query messageHash");
+
+    let message_hash: [u8; 32] =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    let message = build_update_message(
+        initialization_umbrella_feeds.contract_address,
+        timestamp,
+        prices.clone(),
+    );
+
+    assert_eq!(message_hash, <[u8; 32]>::from(Sha256::digest(&message)));
+
+    // Signatures over another message are rejected
+
+    let other_message = build_update_message(
+        initialization_umbrella_feeds.contract_address,
+        Timestamp::from_timestamp_millis(10000000001),
+        prices.clone(),
+    );
+
+    let wrong_update_param = UpdateParams {
+        signers_and_signatures: sign_update(&keys, &other_message),
+        message: update_param.message.clone(),
+    };
+
+    let update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&wrong_update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Update with signatures over another message should fail");
+
+    // WrongSignature
+    assert_eq!(reject_code(&update), -15);
+
+    // Updating price data in contract
+
+    let _update = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    // Checking price data was updated correctly in contract
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getManyPriceDataRaw".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![
+                    String::from("ETH-USD"),
+                    String::from("BTC-USD"),
+                ])
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query prices");
+
+    let stored_price_data: Vec<Option<PriceData>> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored_price_data, [Some(prices[0].1), Some(prices[1].1)]);
+}
+
+/// Test the checks of the signers in the `update` function: fewer signatures than required, exactly the required
+/// signatures, a duplicate signer (also beyond the required signatures), and a signer that is not a validator.
+#[cfg(feature = "dev")]
+#[test]
+fn test_update_signature_checks() {
+    use umbrella_feeds::test_harness::{
+        build_update_message, generate_validator_keys, sign_update,
+    };
+
+    let update = |chain: &mut Chain, contract_address: ContractAddress, param: &UpdateParams| {
+        chain.contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let timestamp = Timestamp::from_timestamp_millis(10000000000);
+
+    let prices = vec![(
+        String::from("ETH-USD"),
+        PriceData {
+            data: 0,
+            heartbeat: 60,
+            timestamp: Timestamp::from_timestamp_millis(9),
+            price: 1800,
+        },
+    )];
+
+    // The staking bank reports all keys as validators, so that only the checks of this contract are tested.
+    let (mut chain, initialization_umbrella_feeds, _initialization_dummy_contract) =
+        setup_chain_and_contract_with_dummy_staking_bank();
+
+    let contract_address = initialization_umbrella_feeds.contract_address;
+
+    let keys = generate_validator_keys(3);
+
+    let message = Message {
+        contract_address,
+        timestamp,
+        price_feed: prices.clone(),
+    };
+
+    let signatures = sign_update(
+        &keys,
+        &build_update_message(contract_address, timestamp, prices.clone()),
+    );
+
+    // Fewer signatures than the required signatures (2).
+
+    let param = UpdateParams {
+        signers_and_signatures: signatures[..1].to_vec(),
+        message: message.clone(),
+    };
+
+    let error = update(&mut chain, contract_address, &param)
+        .expect_err("Update with fewer signatures than required should fail");
+
+    // NotEnoughSignatures
+    assert_eq!(reject_code(&error), -17);
+
+    // The same signer twice to reach the required signatures.
+
+    let param = UpdateParams {
+        signers_and_signatures: vec![signatures[0], signatures[0]],
+        message: message.clone(),
+    };
+
+    let error = update(&mut chain, contract_address, &param)
+        .expect_err("Update with a duplicate signer should fail");
+
+    // SignaturesOutOfOrder
+    assert_eq!(reject_code(&error), -18);
+
+    // A duplicate signer beyond the required signatures.
+
+    let param = UpdateParams {
+        signers_and_signatures: vec![signatures[0], signatures[1], signatures[1]],
+        message: message.clone(),
+    };
+
+    let error = update(&mut chain, contract_address, &param)
+        .expect_err("Update with a duplicate signer should fail");
+
+    // SignaturesOutOfOrder
+    assert_eq!(reject_code(&error), -18);
+
+    // Exactly the required signatures.
+
+    let param = UpdateParams {
+        signers_and_signatures: signatures[..2].to_vec(),
+        message: message.clone(),
+    };
+
+    update(&mut chain, contract_address, &param)
+        .expect("Update with exactly the required signatures should succeed");
+
+    // Signers that are not validators in the staking bank.
+
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        _initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let contract_address = initialization_umbrella_feeds.contract_address;
+
+    let param = UpdateParams {
+        signers_and_signatures: sign_update(
+            &keys[..2],
+            &build_update_message(contract_address, timestamp, prices.clone()),
+        ),
+        message: Message {
+            contract_address,
+            timestamp,
+            price_feed: prices,
+        },
+    };
+
+    let error = update(&mut chain, contract_address, &param)
+        .expect_err("Update with unknown signers should fail");
+
+    // InvalidSigner
+    assert_eq!(reject_code(&error), -19);
+}
+
+/// Test the maximum deviation of a price feed: setting and removing it, a new price within and beyond the maximum
+/// deviation (the other price data of the update are stored), a stored price of zero, and the `emergencyUpdate` entry
+/// point that skips the check.
+#[cfg(feature = "dev")]
+#[test]
+fn test_max_deviation() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+    use umbrella_feeds::{
+        DeviationRejectedEvent, MaxDeviationChangedEvent, PriceUpdatedEvent, SetMaxDeviationParams,
+    };
+
+    let (mut chain, initialization_umbrella_feeds, _initialization_dummy_contract) =
+        setup_chain_and_contract_with_dummy_staking_bank();
+
+    chain.create_account(Account::new(OTHER_ACCOUNT, ACC_INITIAL_BALANCE));
+
+    let contract_address = initialization_umbrella_feeds.contract_address;
+
+    let keys = generate_validator_keys(2);
+
+    let events_of = |update: &ContractInvokeSuccess| -> Vec<Event> {
+        update
+            .events()
+            .flat_map(|(_, events)| events)
+            .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+            .collect()
+    };
+
+    let set_max_deviation =
+        |chain: &mut Chain, sender: AccountAddress, max_deviation_bps: Option<u16>| {
+            chain.contract_update(
+                Signer::with_one_key(),
+                sender,
+                Address::Account(sender),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.setMaxDeviation".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&SetMaxDeviationParams {
+                        key: String::from("ETH-USD"),
+                        max_deviation_bps,
+                    })
+                    .expect("Should be a valid inut parameter"),
+                },
+            )
+        };
+
+    let update = |chain: &mut Chain,
+                  sender: AccountAddress,
+                  entrypoint: &str,
+                  prices: Vec<(&str, u128)>,
+                  price_timestamp: u64| {
+        let prices = prices
+            .into_iter()
+            .map(|(key, price)| {
+                (
+                    String::from(key),
+                    PriceData {
+                        data: 0,
+                        heartbeat: 60,
+                        timestamp: Timestamp::from_timestamp_millis(price_timestamp),
+                        price,
+                    },
+                )
+            })
+            .collect();
+
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(20000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(entrypoint.to_string()),
+                message: OwnedParameter::from_serial(&build_update_params(
+                    &keys,
+                    contract_address,
+                    Timestamp::from_timestamp_millis(10000000000),
+                    prices,
+                ))
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    let get_price = |chain: &Chain, key: &str| -> u128 {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.getPriceData".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&String::from(key))
+                        .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to query the price data");
+
+        let price_data: PriceData =
+            from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        price_data.price
+    };
+
+    // Checking that an account that is not the owner of the registry contract cannot set the maximum deviation.
+
+    let error = set_max_deviation(&mut chain, OTHER_ACCOUNT, Some(500))
+        .expect_err("Should fail to set the maximum deviation");
+
+    // Unauthorized
+    assert_eq!(reject_code(&error), -10, "Error is wrong");
+
+    // Checking that a maximum deviation of zero is rejected.
+
+    let error = set_max_deviation(&mut chain, ACC_ADDR_OWNER, Some(0))
+        .expect_err("Should fail to set the maximum deviation");
+
+    // InvalidMaxDeviation
+    assert_eq!(reject_code(&error), -26, "Error is wrong");
+
+    // Setting a maximum deviation of 5%.
+
+    let result = set_max_deviation(&mut chain, ACC_ADDR_OWNER, Some(500))
+        .expect("Should be able to set the maximum deviation");
+
+    assert_eq!(
+        events_of(&result),
+        vec![Event::MaxDeviationChanged(MaxDeviationChangedEvent {
+            key: String::from("ETH-USD"),
+            previous_max_deviation_bps: None,
+            new_max_deviation_bps: Some(500),
+        })],
+        "Events are wrong"
+    );
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getMaxDeviation".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&String::from("ETH-USD"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the maximum deviation");
+
+    let max_deviation_bps: Option<u16> =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(max_deviation_bps, Some(500));
+
+    // The first price of a price feed, a stored price of zero, and a price within the maximum deviation are stored.
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.update",
+        vec![("ETH-USD", 0)],
+        1,
+    )
+    .expect("Should be able to update price data");
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.update",
+        vec![("ETH-USD", 1000)],
+        2,
+    )
+    .expect("Should be able to update price data");
+
+    assert_eq!(get_price(&chain, "ETH-USD"), 1000);
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.update",
+        vec![("ETH-USD", 950)],
+        3,
+    )
+    .expect("Should be able to update price data");
+
+    assert_eq!(get_price(&chain, "ETH-USD"), 950);
+
+    // A price beyond the maximum deviation is not stored, while the other price data of the update are stored.
+
+    let result = update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.update",
+        vec![("ETH-USD", 1000), ("BTC-USD", 30000)],
+        4,
+    )
+    .expect("Should be able to update price data");
+
+    assert_eq!(
+        events_of(&result),
+        vec![
+            Event::DeviationRejected(DeviationRejectedEvent {
+                key: String::from("ETH-USD"),
+                stored_price: 950,
+                rejected_price: 1000,
+                max_deviation_bps: 500,
+            }),
+            Event::PriceUpdated(PriceUpdatedEvent {
+                key: String::from("BTC-USD"),
+                price: 30000,
+                timestamp: Timestamp::from_timestamp_millis(4),
+            })
+        ],
+        "Events are wrong"
+    );
+
+    assert_eq!(get_price(&chain, "ETH-USD"), 950);
+    assert_eq!(get_price(&chain, "BTC-USD"), 30000);
+
+    // The `emergencyUpdate` entry point can only be invoked by the owner of the registry contract and stores the price.
+
+    let error = update(
+        &mut chain,
+        OTHER_ACCOUNT,
+        "umbrella_feeds.emergencyUpdate",
+        vec![("ETH-USD", 2000)],
+        5,
+    )
+    .expect_err("Should fail to update price data");
+
+    // Unauthorized
+    assert_eq!(reject_code(&error), -10, "Error is wrong");
+
+    let result = update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.emergencyUpdate",
+        vec![("ETH-USD", 2000)],
+        5,
+    )
+    .expect("Should be able to update price data");
+
+    assert_eq!(
+        events_of(&result),
+        vec![Event::PriceUpdated(PriceUpdatedEvent {
+            key: String::from("ETH-USD"),
+            price: 2000,
+            timestamp: Timestamp::from_timestamp_millis(5),
+        })],
+        "Events are wrong"
+    );
+    assert_eq!(get_price(&chain, "ETH-USD"), 2000);
+
+    // Removing the maximum deviation.
+
+    let result = set_max_deviation(&mut chain, ACC_ADDR_OWNER, None)
+        .expect("Should be able to remove the maximum deviation");
+
+    assert_eq!(
+        events_of(&result),
+        vec![Event::MaxDeviationChanged(MaxDeviationChangedEvent {
+            key: String::from("ETH-USD"),
+            previous_max_deviation_bps: Some(500),
+            new_max_deviation_bps: None,
+        })],
+        "Events are wrong"
+    );
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        "umbrella_feeds.update",
+        vec![("ETH-USD", 10000)],
+        6,
+    )
+    .expect("Should be able to update price data");
+
+    assert_eq!(get_price(&chain, "ETH-USD"), 10000);
+}
+
+#[cfg(feature = "dev")]
+#[test]
+fn test_upgrade_with_price_migration() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+    use umbrella_feeds::{MigrationParams, SetMaxDeviationParams};
+
+    let (mut chain, initialization_umbrella_feeds, _initialization_dummy_contract) =
+        setup_chain_and_contract_with_dummy_staking_bank();
+
+    let contract_address = initialization_umbrella_feeds.contract_address;
+
+    // The registry is the first contract initialized by the setup.
+    let registry_address = ContractAddress::new(0, 0);
+
+    let keys = generate_validator_keys(2);
+
+    let price_data = PriceData {
+        data: 0,
+        heartbeat: 60,
+        timestamp: Timestamp::from_timestamp_millis(5),
+        price: 1000,
+    };
+
+    // Storing price data.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(20000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&build_update_params(
+                    &keys,
+                    contract_address,
+                    Timestamp::from_timestamp_millis(10000000000),
+                    vec![(String::from("ETH-USD"), price_data)],
+                ))
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to update price data");
+
+    // Setting the decimals of the price feed.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setFeedDecimals".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SetFeedDecimalsParams {
+                    key: String::from("ETH-USD"),
+                    decimals: 6,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to set the decimals");
+
+    // Setting the maximum deviation of the price feed.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setMaxDeviation".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SetMaxDeviationParams {
+                    key: String::from("ETH-USD"),
+                    max_deviation_bps: Some(500),
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to set the maximum deviation");
+
+    // Setting the heartbeat of the price feed.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setHeartbeat".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&SetHeartbeatParams {
+                    key: String::from("ETH-USD"),
+                    heartbeat: 3600,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to set the heartbeat");
+
+    // Exporting the stored price data.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.exportPrices".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to export the price data");
+
+    let exported: MigrationParams =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(
+        exported,
+        MigrationParams {
+            prices: vec![(String::from("ETH-USD"), price_data)],
+            feed_decimals: vec![(String::from("ETH-USD"), 6)],
+            max_deviations: vec![(String::from("ETH-USD"), 500)],
+            feed_heartbeats: vec![(String::from("ETH-USD"), 3600)],
+        }
+    );
+
+    // Checking that the export of an older module (only the price data) is a valid migration parameter.
+
+    assert_eq!(
+        from_bytes::<MigrationParams>(&to_bytes(&exported.prices))
+            .expect("Should be a valid migration parameter"),
+        MigrationParams {
+            prices: exported.prices.clone(),
+            feed_decimals: Vec::new(),
+            max_deviations: Vec::new(),
+            feed_heartbeats: Vec::new(),
+        }
+    );
+
+    // Checking that an account cannot invoke the migration function.
+
+    let error = chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.migrate".to_string()),
+                message: OwnedParameter::from_serial(&exported)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect_err("Should fail to migrate the price data");
+
+    // Unauthorized
+    assert_eq!(reject_code(&error), -10, "Error is wrong");
+
+    // Importing umbrella_feeds into the registry contract.
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()),
+                message: OwnedParameter::from_serial(&ImportContractsParam {
+                    entries: vec![contract_address],
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to import the contract");
+
+    // Upgrading umbrella_feeds contract with the migration function. The module is already deployed by the setup.
+
+    let module_reference = module_load_v1("./umbrella_feeds.wasm.v1")
+        .expect("`Umbrella_feeds.wasm.v1` module should be loaded")
+        .get_module_ref();
+
+    chain
+        .contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(100000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: registry_address,
+                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdate".into()),
+                message: OwnedParameter::from_serial(&AtomicUpdateParam {
+                    module: module_reference,
+                    migrate: Some((
+                        OwnedEntrypointName::new_unchecked("migrate".to_string()),
+                        OwnedParameter::from_serial(&exported)
+                            .expect("Should be a valid inut parameter"),
+                    )),
+                    contract_address,
+                })
+                .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to upgrade with the migration function");
+
+    // Checking that the price data carried over.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getPriceData".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&String::from("ETH-USD"))
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+        .expect("Should be able to query the price data");
+
+    let stored: PriceData = from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(stored, price_data);
+
+    // Checking that the per-feed settings carried over.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.exportPrices".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to export the price data");
+
+    let exported_after: MigrationParams =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(exported_after, exported);
+}
+
+/// Registers the given keys as validators in the staking bank.
+#[cfg(feature = "dev")]
+fn register_validators(
+    chain: &mut Chain,
+    staking_bank: ContractAddress,
+    keys: &[ed25519_dalek::Keypair],
+) {
+    use staking_bank::RegisterValidatorParams;
+    use umbrella_feeds::test_harness::public_key;
+
+    for key in keys {
+        let _update = chain
+            .contract_update(
+                Signer::with_one_key(),
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: staking_bank,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "staking_bank.registerValidator".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&RegisterValidatorParams {
+                        public_key: public_key(key),
+                        location: String::from("https://validator.umb.network"),
+                        ecdsa_public_key: None,
+                    })
+                    .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to register a validator");
+    }
+}
+
+/// Test the `update` function of a contract initialized with `restrict_submitter`: only an account with an account key
+/// that is registered as a validator in the staking bank can submit price feed updates.
+#[cfg(feature = "dev")]
+#[test]
+fn test_restrict_submitter() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let keys = generate_validator_keys(3);
+
+    // Registering the first two keys as validators in the staking bank
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys[..2],
+    );
+
+    // Creating an account with the key of a validator and an account with a key that is not a validator
+
+    let account_with_key = |address: AccountAddress, key: &ed25519_dalek::Keypair| {
+        let mut credential_keys = BTreeMap::new();
+        credential_keys.insert(KeyIndex(0), VerifyKey::Ed25519VerifyKey(key.public));
+
+        let mut keys = BTreeMap::new();
+        keys.insert(
+            CredentialIndex { index: 0 },
+            CredentialPublicKeys {
+                keys: credential_keys,
+                threshold: SignatureThreshold::ONE,
+            },
+        );
+
+        Account::new_with_keys(
+            address,
+            AccountBalance::new(ACC_INITIAL_BALANCE, Amount::zero(), Amount::zero())
+                .expect("Should be a valid balance"),
+            AccountAccessStructure {
+                keys,
+                threshold: AccountThreshold::ONE,
+            },
+        )
+    };
+
+    const VALIDATOR_ACCOUNT: AccountAddress = AccountAddress([2u8; 32]);
+    const NON_VALIDATOR_ACCOUNT: AccountAddress = AccountAddress([3u8; 32]);
+
+    chain.create_account(account_with_key(VALIDATOR_ACCOUNT, &keys[0]));
+    chain.create_account(account_with_key(NON_VALIDATOR_ACCOUNT, &keys[2]));
+
+    // Initializing an umbrella feeds contract that restricts the submitters
+
+    let module_reference = chain
+        .get_contract(initialization_umbrella_feeds.contract_address)
+        .expect("The umbrella feeds contract should exist")
+        .module_reference;
+
+    let input_parameter = InitParamsUmbrellaFeeds {
+        registry: initialization_registry.contract_address,
+        required_signatures: 2,
+        staking_bank: initialization_staking_bank.contract_address,
+        decimals: 4,
+        signature_scheme: SignatureScheme::Ed25519,
+        old_data_policy: OldDataPolicy::Reject,
+        name: DEFAULT_DEPLOYMENT_NAME.to_string(),
+        chain_id: DEFAULT_CHAIN_ID.to_string(),
+        code_version: String::new(),
+        restrict_submitter: true,
+        max_future_skew: DEFAULT_MAX_FUTURE_SKEW,
+    };
+
+    let restricted_contract = chain
+        .contract_init(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Energy::from(10000),
+            InitContractPayload {
+                amount: Amount::zero(),
+                mod_ref: module_reference,
+                init_name: OwnedContractName::new_unchecked("init_umbrella_feeds".to_string()),
+                param: OwnedParameter::from_serial(&input_parameter)
+                    .expect("`InitParamsUmbrellaFeeds` should be a valid inut parameter"),
+            },
+        )
+        .expect("Initialization of `umbrella_feeds` should always succeed")
+        .contract_address;
+
+    // Checking `restrictSubmitter`
+
+    for (contract, expected) in [
+        (initialization_umbrella_feeds.contract_address, false),
+        (restricted_contract, true),
+    ] {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(10000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.restrictSubmitter".to_string(),
+                    ),
+                    message: OwnedParameter::empty(),
+                },
+            )
+            .expect("Should be able to query restrictSubmitter");
+
+        let restrict_submitter: bool =
+            from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+        assert_eq!(restrict_submitter, expected);
+    }
+
+    let update = |chain: &mut Chain,
+                  sender: AccountAddress,
+                  contract: ContractAddress,
+                  timestamp: u64| {
+        let update_param = build_update_params(
+            &keys[..2],
+            contract,
+            Timestamp::from_timestamp_millis(10000000000),
+            vec![(
+                String::from("ETH-USD"),
+                PriceData {
+                    data: 0,
+                    heartbeat: 60,
+                    timestamp: Timestamp::from_timestamp_millis(timestamp),
+                    price: 1800,
+                },
+            )],
+        );
+
+        chain.contract_update(
+            Signer::with_one_key(),
+            sender,
+            Address::Account(sender),
+            Energy::from(20000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: contract,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    // Any account can submit to the contract that does not restrict the submitters
+
+    update(
+        &mut chain,
+        ACC_ADDR_OWNER,
+        initialization_umbrella_feeds.contract_address,
+        1,
+    )
+    .expect("Any account should be able to update price data");
+
+    // Accounts without a validator key cannot submit to the contract that restricts the submitters
+
+    for sender in [ACC_ADDR_OWNER, NON_VALIDATOR_ACCOUNT] {
+        let error = update(&mut chain, sender, restricted_contract, 1)
+            .expect_err("Only validators should be able to update price data");
+
+        // Unauthorized
+        assert_eq!(reject_code(&error), -10);
+    }
+
+    // The validator account can submit to the contract that restricts the submitters
+
+    update(&mut chain, VALIDATOR_ACCOUNT, restricted_contract, 2)
+        .expect("A validator should be able to update price data");
+}
+
+/// Test the timestamp window of the `update` function: price data stamped in the past or slightly (up to
+/// `max_future_skew` seconds) ahead of the block time are stored, price data stamped further ahead are rejected.
+#[cfg(feature = "dev")]
+#[test]
+fn test_max_future_skew() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+
+    const BLOCK_TIME: u64 = 1700000000000;
+
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(BLOCK_TIME),
+    );
+
+    let keys = generate_validator_keys(2);
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys,
+    );
+
+    // Checking `maxFutureSkew`
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.maxFutureSkew".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query maxFutureSkew");
+
+    let max_future_skew: u64 =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(max_future_skew, DEFAULT_MAX_FUTURE_SKEW);
+
+    let update = |chain: &mut Chain, price_timestamp: u64| {
+        let update_param = build_update_params(
+            &keys,
+            initialization_umbrella_feeds.contract_address,
+            Timestamp::from_timestamp_millis(BLOCK_TIME + 3600000),
+            vec![(
+                String::from("ETH-USD"),
+                PriceData {
+                    data: 0,
+                    heartbeat: 60,
+                    timestamp: Timestamp::from_timestamp_millis(price_timestamp),
+                    price: 1800,
+                },
+            )],
+        );
+
+        chain.contract_update(
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(20000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_umbrella_feeds.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&update_param)
+                    .expect("Should be a valid inut parameter"),
+            },
+        )
+    };
+
+    // Price data stamped in the past are stored
+
+    update(&mut chain, BLOCK_TIME - 60000).expect("Should be able to update past price data");
+
+    // Price data stamped slightly ahead of the block time are stored (up to `max_future_skew` seconds)
+
+    update(&mut chain, BLOCK_TIME + 60000)
+        .expect("Should be able to update price data slightly ahead of the block time");
+
+    update(&mut chain, BLOCK_TIME + DEFAULT_MAX_FUTURE_SKEW * 1000).expect(
+        "Should be able to update price data `max_future_skew` seconds ahead of the block time",
+    );
+
+    // Price data stamped further ahead of the block time are rejected
+
+    let error = update(&mut chain, BLOCK_TIME + DEFAULT_MAX_FUTURE_SKEW * 1000 + 1)
+        .expect_err("Should not be able to update price data far ahead of the block time");
+
+    // TimestampTooFar
+    assert_eq!(reject_code(&error), -27);
+
+    let error = update(&mut chain, BLOCK_TIME + 86400000)
+        .expect_err("Should not be able to update price data far ahead of the block time");
+
+    // TimestampTooFar
+    assert_eq!(reject_code(&error), -27);
+}
+
+/// Test the `PriceUpdated` events of the `update` function: one event per stored price data, none for price data that
+/// is skipped as old data, and a large batch of price data.
+#[cfg(feature = "dev")]
+#[test]
+fn test_price_updated_events() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+    use umbrella_feeds::PriceUpdatedEvent;
+
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Skip,
+        Timestamp::from_timestamp_millis(0),
+    );
+
+    let contract_address = initialization_umbrella_feeds.contract_address;
+
+    let keys = generate_validator_keys(2);
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys,
+    );
+
+    let update = |chain: &mut Chain, prices: Vec<(String, u128, u64)>| {
+        let prices = prices
+            .into_iter()
+            .map(|(key, price, price_timestamp)| {
+                (
+                    key,
+                    PriceData {
+                        data: 0,
+                        heartbeat: 60,
+                        timestamp: Timestamp::from_timestamp_millis(price_timestamp),
+                        price,
+                    },
+                )
+            })
+            .collect();
+
+        let update = chain
+            .contract_update(
+                Signer::with_one_key(),
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(1000000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.update".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&build_update_params(
+                        &keys,
+                        contract_address,
+                        Timestamp::from_timestamp_millis(10000000000),
+                        prices,
+                    ))
+                    .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to update price data");
+
+        update
+            .events()
+            .flat_map(|(_, events)| events)
+            .map(|event| from_bytes(event.as_ref()).expect("Should be a valid event"))
+            .collect::<Vec<Event>>()
+    };
+
+    let price_updated = |key: &str, price: u128, price_timestamp: u64| {
+        Event::PriceUpdated(PriceUpdatedEvent {
+            key: String::from(key),
+            price,
+            timestamp: Timestamp::from_timestamp_millis(price_timestamp),
+        })
+    };
+
+    // One event is logged per stored price data
+
+    let events = update(
+        &mut chain,
+        vec![
+            (String::from("BTC-USD"), 30000, 10),
+            (String::from("ETH-USD"), 1800, 10),
+        ],
+    );
+
+    assert_eq!(
+        events,
+        vec![
+            price_updated("BTC-USD", 30000, 10),
+            price_updated("ETH-USD", 1800, 10)
+        ],
+        "Events are wrong"
+    );
+
+    // No event is logged for price data that is skipped as old data
+
+    let events = update(
+        &mut chain,
+        vec![
+            (String::from("BTC-USD"), 31000, 10),
+            (String::from("ETH-USD"), 1900, 11),
+        ],
+    );
+
+    assert_eq!(
+        events,
+        vec![price_updated("ETH-USD", 1900, 11)],
+        "Events are wrong"
+    );
+
+    // A large batch of price data logs one event per price feed
+
+    let events = update(
+        &mut chain,
+        (0..100)
+            .map(|index| (format!("FEED-{index}"), index, 20))
+            .collect(),
+    );
+
+    assert_eq!(events.len(), 100, "Events are wrong");
+    assert_eq!(events[99], price_updated("FEED-99", 99, 20));
+}
+
+/// Test a staking bank with more than 255 validators: the number of validators is a `u16`, so a required signatures
+/// threshold beyond the `u8` range can be set (258 would become 2 as a `u8`) and is not reachable with fewer
+/// signatures.
+#[cfg(feature = "dev")]
+#[test]
+fn test_large_number_of_validators() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+
+    // The two validators of the staking bank and the generated validators.
+    const NUMBER_OF_VALIDATORS: u16 = 258;
+
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract();
+
+    let keys = generate_validator_keys(NUMBER_OF_VALIDATORS - 2);
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys,
+    );
+
+    // Checking getNumberOfValidators of the staking bank.
+
+    let invoke = chain
+        .contract_invoke(
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(10000),
+            UpdateContractPayload {
+                amount: Amount::zero(),
+                address: initialization_staking_bank.contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "staking_bank.getNumberOfValidators".to_string(),
+                ),
+                message: OwnedParameter::empty(),
+            },
+        )
+        .expect("Should be able to query the number of validators");
+
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    let invoke = chain
-        .contract_invoke(
+    assert_eq!(value, NUMBER_OF_VALIDATORS);
+
+    // Setting more required signatures than validators.
+
+    let error = chain
+        .contract_update(
+            Signer::with_one_key(),
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
             Energy::from(10000),
@@ -764,32 +4178,18 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.viewMessageHash".to_string(),
+                    "umbrella_feeds.setRequiredSignatures".to_string(),
                 ),
-                message: OwnedParameter::from_serial(&update_param)
+                message: OwnedParameter::from_serial(&(NUMBER_OF_VALIDATORS + 1))
                     .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query messageHash");
+        .expect_err("Should not be able to set more required signatures than validators");
 
-    let message_hash: [u8; 32] =
-        from_bytes(&invoke.return_value).expect("Should return a valid result");
-
-    println!(
-        "Signer sign this message hash: {}",
-        HashSha2256(message_hash)
-    );
-
-    let signature:SignatureEd25519 = "9F4A71B52C1043080A802F7FF686989FF66B39DA680EDED104D90C7572B550D7EA129C8F0FB4ADAE9BD98BF017D14C425C0D1398B8D81D17DBA7D99EA65FD807".parse().unwrap();
-    println!("Signature: {:?}", signature.0);
-
-    let public_key: PublicKeyEd25519 =
-        "D96C4B1218EA7EC20F4604D6C2F02FA3F36B518443F3A2D14E885E7FF715DEDD"
-            .parse()
-            .unwrap();
-    println!("Public key: {:?}", public_key.0);
+    // InvalidRequiredSignatures
+    assert_eq!(reject_code(&error), -5);
 
-    // Updating price data in contract
+    // Setting as many required signatures as validators.
 
     let _update = chain
         .contract_update(
@@ -800,14 +4200,16 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
             UpdateContractPayload {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
-                message: OwnedParameter::from_serial(&update_param)
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.setRequiredSignatures".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&NUMBER_OF_VALIDATORS)
                     .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to update operator with permit");
+        .expect("Should be able to set the required signatures");
 
-    // Checking price data was updated correctly in contract.
+    // Checking requiredSignatures.
 
     let invoke = chain
         .contract_invoke(
@@ -818,189 +4220,364 @@ fn test_update_price_feed_and_check_price_via_feed_name() {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.getPriceData".to_string(),
+                    "umbrella_feeds.requiredSignatures".to_string(),
                 ),
-                message: OwnedParameter::from_serial(&String::from("ETH-CCD"))
-                    .expect("Should be a valid inut parameter"),
+                message: OwnedParameter::empty(),
             },
         )
-        .expect("Should be able to query timestam");
+        .expect("Should be able to query required signatures");
 
-    let stored_price_data: PriceData =
-        from_bytes(&invoke.return_value).expect("Should return a valid result");
+    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(stored_price_data, price_data);
-}
+    assert_eq!(value, NUMBER_OF_VALIDATORS);
 
-#[test]
-fn test_get_name() {
-    let (
-        chain,
-        initialization_umbrella_feeds,
-        _initialization_registry,
-        _initialization_staking_bank,
-    ) = setup_chain_and_contract();
+    // Updating with the signatures of the generated validators (more than the truncated threshold).
 
-    // Checking getName.
+    let prices = vec![(
+        String::from("ETH-USD"),
+        PriceData {
+            data: 0,
+            heartbeat: 60,
+            timestamp: Timestamp::from_timestamp_millis(9),
+            price: 1800,
+        },
+    )];
 
-    let invoke = chain
-        .contract_invoke(
+    let error = chain
+        .contract_update(
+            Signer::with_one_key(),
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
-            Energy::from(10000),
+            Energy::from(100000),
             UpdateContractPayload {
                 amount: Amount::zero(),
                 address: initialization_umbrella_feeds.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.getName".to_string()),
-                message: OwnedParameter::empty(),
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&build_update_params(
+                    &keys,
+                    initialization_umbrella_feeds.contract_address,
+                    Timestamp::from_timestamp_millis(10000000000),
+                    prices,
+                ))
+                .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query contract name");
-
-    let value: String = from_bytes(&invoke.return_value).expect("Should return a valid result");
+        .expect_err("Update with fewer signatures than the required signatures should fail");
 
-    assert_eq!(value, String::from("UmbrellaFeeds"));
+    // NotEnoughSignatures
+    assert_eq!(reject_code(&error), -17);
 }
 
+/// Test the `getStaleFeeds` function: it returns the sorted keys of the price feeds older than the given age (price data
+/// in the future are fresh) among at most `limit` price feeds, and the cursor of the next page. Also test that
+/// `exportPrices` is sorted by the key.
+#[cfg(feature = "dev")]
 #[test]
-fn test_get_required_signatures() {
+fn test_get_stale_feeds() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+    use umbrella_feeds::{GetStaleFeedsParams, MigrationParams, StaleFeeds};
+
+    const BLOCK_TIME: u64 = 1700000000000;
+
     let (
-        chain,
+        mut chain,
         initialization_umbrella_feeds,
         _initialization_registry,
-        _initialization_staking_bank,
-    ) = setup_chain_and_contract();
+        initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(BLOCK_TIME),
+    );
 
-    // Checking requiredSignatures.
+    let contract_address = initialization_umbrella_feeds.contract_address;
 
-    let invoke = chain
+    let keys = generate_validator_keys(2);
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys,
+    );
+
+    let update = |chain: &mut Chain, prices: Vec<(String, u64)>| {
+        let prices = prices
+            .into_iter()
+            .map(|(key, price_timestamp)| {
+                (
+                    key,
+                    PriceData {
+                        data: 0,
+                        heartbeat: 60,
+                        timestamp: Timestamp::from_timestamp_millis(price_timestamp),
+                        price: 1800,
+                    },
+                )
+            })
+            .collect();
+
+        chain
+            .contract_update(
+                Signer::with_one_key(),
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(1000000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.update".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&build_update_params(
+                        &keys,
+                        contract_address,
+                        Timestamp::from_timestamp_millis(BLOCK_TIME + 3600000),
+                        prices,
+                    ))
+                    .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to update price data");
+    };
+
+    let get_stale_feeds_page = |chain: &Chain, max_age_seconds: u64, start: u32, limit: u32| {
+        let invoke = chain
+            .contract_invoke(
+                ACC_ADDR_OWNER,
+                Address::Account(ACC_ADDR_OWNER),
+                Energy::from(1000000),
+                UpdateContractPayload {
+                    amount: Amount::zero(),
+                    address: contract_address,
+                    receive_name: OwnedReceiveName::new_unchecked(
+                        "umbrella_feeds.getStaleFeeds".to_string(),
+                    ),
+                    message: OwnedParameter::from_serial(&GetStaleFeedsParams {
+                        max_age_seconds,
+                        start,
+                        limit,
+                    })
+                    .expect("Should be a valid inut parameter"),
+                },
+            )
+            .expect("Should be able to query the stale feeds");
+
+        from_bytes::<StaleFeeds>(&invoke.return_value).expect("Should return a valid result")
+    };
+
+    // Queries the first page with the maximum limit, which covers all price feeds as long as at most `MAX_KEYS` price
+    // feeds are stored
+    let get_stale_feeds = |chain: &Chain, max_age_seconds: u64| {
+        let stale_feeds = get_stale_feeds_page(chain, max_age_seconds, 0, MAX_KEYS as u32);
+
+        assert_eq!(stale_feeds.next, None, "All price feeds should be checked");
+
+        stale_feeds.keys
+    };
+
+    // No price feeds are stale before any price data is stored
+
+    assert_eq!(get_stale_feeds(&chain, 0), Vec::<String>::new());
+
+    update(
+        &mut chain,
+        vec![
+            (String::from("BTC-USD"), BLOCK_TIME - 100000),
+            (String::from("ETH-USD"), BLOCK_TIME - 10000),
+            (String::from("CCD-USD"), BLOCK_TIME + 60000),
+        ],
+    );
+
+    // Checking the stale feeds for different ages (the `CCD-USD` price data is in the future and always fresh)
+
+    assert_eq!(
+        get_stale_feeds(&chain, 5),
+        vec![String::from("BTC-USD"), String::from("ETH-USD")]
+    );
+    assert_eq!(get_stale_feeds(&chain, 10), vec![String::from("BTC-USD")]);
+    assert_eq!(get_stale_feeds(&chain, 50), vec![String::from("BTC-USD")]);
+    assert_eq!(get_stale_feeds(&chain, 100), Vec::<String>::new());
+
+    // Paging through the price feeds one by one (the cursor is the number of checked price feeds)
+
+    let mut stale_feeds = Vec::new();
+    let mut cursors = Vec::new();
+    let mut start = Some(0);
+
+    while let Some(page_start) = start {
+        let page = get_stale_feeds_page(&chain, 5, page_start, 1);
+
+        assert!(
+            page.keys.len() <= 1,
+            "At most `limit` price feeds should be checked"
+        );
+
+        stale_feeds.extend(page.keys);
+        cursors.push(page_start);
+        start = page.next;
+    }
+
+    stale_feeds.sort();
+
+    assert_eq!(cursors, vec![0, 1, 2]);
+    assert_eq!(
+        stale_feeds,
+        vec![String::from("BTC-USD"), String::from("ETH-USD")]
+    );
+
+    // A start beyond the stored price feeds returns an empty last page
+
+    assert_eq!(
+        get_stale_feeds_page(&chain, 5, 10, 1),
+        StaleFeeds {
+            keys: Vec::new(),
+            next: None
+        }
+    );
+
+    // A limit of more than `MAX_KEYS` price feeds is rejected
+
+    let error = chain
         .contract_invoke(
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
-            Energy::from(10000),
+            Energy::from(1000000),
             UpdateContractPayload {
                 amount: Amount::zero(),
-                address: initialization_umbrella_feeds.contract_address,
+                address: contract_address,
                 receive_name: OwnedReceiveName::new_unchecked(
-                    "umbrella_feeds.requiredSignatures".to_string(),
+                    "umbrella_feeds.getStaleFeeds".to_string(),
                 ),
-                message: OwnedParameter::empty(),
+                message: OwnedParameter::from_serial(&GetStaleFeedsParams {
+                    max_age_seconds: 5,
+                    start: 0,
+                    limit: MAX_KEYS as u32 + 1,
+                })
+                .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query required signatures");
+        .expect_err("Should not be able to check more than `MAX_KEYS` price feeds");
 
-    let value: u16 = from_bytes(&invoke.return_value).expect("Should return a valid result");
+    // TooManyKeys
+    assert_eq!(reject_code(&error), -22);
 
-    assert_eq!(value, 2u16);
-}
+    // More than `MAX_KEYS` price feeds need two pages
 
-#[test]
-fn test_upgrade_without_migration_function() {
-    let (
-        mut chain,
-        initialization_umbrella_feeds,
-        initialization_registry,
-        _initialization_staking_bank,
-    ) = setup_chain_and_contract();
+    update(
+        &mut chain,
+        (0..MAX_KEYS)
+            .map(|index| (format!("FEED-{index}"), BLOCK_TIME - 100000))
+            .collect(),
+    );
 
-    // Importing umbrella_feeds into the registry contract
+    let first_page = get_stale_feeds_page(&chain, 50, 0, MAX_KEYS as u32);
 
-    let input_parameter = ImportContractsParam {
-        entries: vec![initialization_umbrella_feeds.contract_address],
-    };
+    assert_eq!(first_page.next, Some(MAX_KEYS as u32));
 
-    let _update = chain.contract_update(
-        Signer::with_one_key(), // Used for specifying the number of signatures.
-        ACC_ADDR_OWNER,         // Invoker account.
-        Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
-        Energy::from(10000),    // Maximum energy allowed for the update.
-        UpdateContractPayload {
-            address: initialization_registry.contract_address, // The contract to update.
-            receive_name: OwnedReceiveName::new_unchecked("registry.importContracts".into()), // The receive function to call.
-            message: OwnedParameter::from_serial(&input_parameter)
-                .expect("`UpgradeParams` should be a valid inut parameter"), // The parameter sent to the contract.
-            amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
-        },
-    );
+    let second_page = get_stale_feeds_page(&chain, 50, MAX_KEYS as u32, MAX_KEYS as u32);
 
-    // Checking that the contract was registered correctly
+    assert_eq!(second_page.next, None);
+
+    let mut stale_feeds: Vec<String> = first_page
+        .keys
+        .into_iter()
+        .chain(second_page.keys)
+        .collect();
+    stale_feeds.sort();
+
+    assert_eq!(stale_feeds.len(), MAX_KEYS + 1);
+    assert_eq!(stale_feeds[0], "BTC-USD");
+    assert_eq!(stale_feeds[1], "FEED-0");
+
+    // Checking that `exportPrices` returns the price data sorted by the key as well (the state iterates the keys by
+    // their serialization, i.e. the shorter keys `FEED-0` to `FEED-9` first)
 
     let invoke = chain
         .contract_invoke(
             ACC_ADDR_OWNER,
             Address::Account(ACC_ADDR_OWNER),
-            Energy::from(10000),
+            Energy::from(1000000),
             UpdateContractPayload {
                 amount: Amount::zero(),
-                address: initialization_registry.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
-                message: OwnedParameter::from_serial(&String::from("UmbrellaFeeds"))
-                    .expect("Should be a valid inut parameter"),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.exportPrices".to_string(),
+                ),
+                message: OwnedParameter::empty(),
             },
         )
-        .expect("Should be able to query contract address");
+        .expect("Should be able to export the price data");
 
-    let contract_address: ContractAddress =
+    let exported: MigrationParams =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
-    assert_eq!(
-        contract_address,
-        initialization_umbrella_feeds.contract_address
-    );
+    let keys: Vec<&String> = exported.prices.iter().map(|(key, _)| key).collect();
 
-    // Deploying an upgraded umbrella_feeds module.
+    let mut sorted_keys = keys.clone();
+    sorted_keys.sort();
 
-    let deployment = chain
-        .module_deploy_v1(
-            Signer::with_one_key(),
-            ACC_ADDR_OWNER,
-            module_load_v1("./umbrella_feeds_update.wasm.v1")
-                .expect("`Contract version2` module should be loaded"),
-        )
-        .expect("`Contract version2` deployment should always succeed");
+    assert_eq!(keys.len(), MAX_KEYS + 3);
+    assert_eq!(keys, sorted_keys);
+}
 
-    // Upgrading umbrella_feeds contract with the new module reference.
+/// Test the `getManyPriceDataRawWithBlockTime` function: it returns the price data aligned with the input keys (zeroed
+/// for a non-existing price feed) together with the block time.
+#[cfg(feature = "dev")]
+#[test]
+fn test_get_many_price_data_raw_with_block_time() {
+    use umbrella_feeds::test_harness::{build_update_params, generate_validator_keys};
+    use umbrella_feeds::ManyPriceDataRawWithBlockTime;
 
-    let input_parameter = AtomicUpdateParam {
-        module: deployment.module_reference,
-        migrate: None,
-        contract_address: initialization_umbrella_feeds.contract_address,
-    };
+    const BLOCK_TIME: u64 = 1700000000000;
 
-    let _update = chain
-        .contract_update(
-            Signer::with_one_key(), // Used for specifying the number of signatures.
-            ACC_ADDR_OWNER,         // Invoker account.
-            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
-            Energy::from(100000),   // Maximum energy allowed for the update.
-            UpdateContractPayload {
-                address: initialization_registry.contract_address, // The contract to update.
-                receive_name: OwnedReceiveName::new_unchecked("registry.atomicUpdate".into()), // The receive function to call.
-                message: OwnedParameter::from_serial(&input_parameter)
-                    .expect("`UpgradeParams` should be a valid inut parameter"), // The parameter sent to the contract.
-                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
-            },
-        )
-        .expect("Should be able to update");
+    let (
+        mut chain,
+        initialization_umbrella_feeds,
+        _initialization_registry,
+        initialization_staking_bank,
+    ) = setup_chain_and_contract_with(
+        SignatureScheme::Ed25519,
+        OldDataPolicy::Reject,
+        Timestamp::from_timestamp_millis(BLOCK_TIME),
+    );
 
-    // Checking that the upgrade was successfully by calling a function that only exists in the upgraded `umbrella_feeds` contract.
+    let contract_address = initialization_umbrella_feeds.contract_address;
 
-    let _update = chain
+    let keys = generate_validator_keys(2);
+
+    register_validators(
+        &mut chain,
+        initialization_staking_bank.contract_address,
+        &keys,
+    );
+
+    let price_data = PriceData {
+        data: 0,
+        heartbeat: 60,
+        timestamp: Timestamp::from_timestamp_millis(BLOCK_TIME - 10000),
+        price: 1800,
+    };
+
+    chain
         .contract_update(
-            Signer::with_one_key(), // Used for specifying the number of signatures.
-            ACC_ADDR_OWNER,         // Invoker account.
-            Address::Account(ACC_ADDR_OWNER), // Sender (can also be a contract).
-            Energy::from(10000),    // Maximum energy allowed for the update.
+            Signer::with_one_key(),
+            ACC_ADDR_OWNER,
+            Address::Account(ACC_ADDR_OWNER),
+            Energy::from(1000000),
             UpdateContractPayload {
-                address: initialization_umbrella_feeds.contract_address, // The contract to update.
-                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.DECIMALS_2".into()), // The receive function to call.
-                message: OwnedParameter::empty(),
-                amount: Amount::from_ccd(0), // Sending the contract 0 CCD.
+                amount: Amount::zero(),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked("umbrella_feeds.update".to_string()),
+                message: OwnedParameter::from_serial(&build_update_params(
+                    &keys,
+                    contract_address,
+                    Timestamp::from_timestamp_millis(BLOCK_TIME + 3600000),
+                    vec![(String::from("ETH-USD"), price_data)],
+                ))
+                .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to update");
-
-    // Checking that the contract was updated in the registry correctly
+        .expect("Should be able to update price data");
 
     let invoke = chain
         .contract_invoke(
@@ -1009,19 +4586,38 @@ fn test_upgrade_without_migration_function() {
             Energy::from(10000),
             UpdateContractPayload {
                 amount: Amount::zero(),
-                address: initialization_registry.contract_address,
-                receive_name: OwnedReceiveName::new_unchecked("registry.getAddress".to_string()),
-                message: OwnedParameter::from_serial(&String::from("UmbrellaFeeds"))
-                    .expect("Should be a valid inut parameter"),
+                address: contract_address,
+                receive_name: OwnedReceiveName::new_unchecked(
+                    "umbrella_feeds.getManyPriceDataRawWithBlockTime".to_string(),
+                ),
+                message: OwnedParameter::from_serial(&vec![
+                    String::from("BTC-USD"),
+                    String::from("ETH-USD"),
+                ])
+                .expect("Should be a valid inut parameter"),
             },
         )
-        .expect("Should be able to query contract address");
+        .expect("Should be able to query prices");
 
-    let contract_address: ContractAddress =
+    let stored_price_data: ManyPriceDataRawWithBlockTime =
         from_bytes(&invoke.return_value).expect("Should return a valid result");
 
     assert_eq!(
-        contract_address,
-        initialization_umbrella_feeds.contract_address
+        stored_price_data,
+        ManyPriceDataRawWithBlockTime {
+            price_data: vec![PriceData::zero(), price_data],
+            block_time: Timestamp::from_timestamp_millis(BLOCK_TIME),
+        }
+    );
+
+    // The return value is serialized as the tuple `(Vec<PriceData>, Timestamp)`.
+
+    let (tuple_price_data, tuple_block_time): (Vec<PriceData>, Timestamp) =
+        from_bytes(&invoke.return_value).expect("Should return a valid result");
+
+    assert_eq!(tuple_price_data, vec![PriceData::zero(), price_data]);
+    assert_eq!(
+        tuple_block_time,
+        Timestamp::from_timestamp_millis(BLOCK_TIME)
     );
 }